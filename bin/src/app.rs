@@ -116,17 +116,39 @@ impl EthUIApp {
                 ethui_wallets::commands::wallets_get_mnemonic_addresses,
                 ethui_wallets::commands::wallets_validate_mnemonic,
                 ethui_wallets::commands::wallets_ledger_derive,
+                ethui_wallets::commands::wallets_change_password,
+                ethui_wallets::commands::wallets_export_keystore,
+                ethui_wallets::commands::wallets_reveal_mnemonic,
+                ethui_wallets::commands::wallets_unlock_keystore,
+                ethui_wallets::commands::wallets_sign_authorization,
+                ethui_wallets::commands::wallets_get_delegations,
+                ethui_wallets::commands::wallets_set_lock_policy,
+                ethui_wallets::commands::wallets_lock_all,
+                ethui_wallets::commands::wallets_get_lock_states,
                 ethui_dialogs::commands::dialog_get_payload,
                 ethui_dialogs::commands::dialog_send,
                 ethui_rpc::commands::rpc_send_transaction,
                 ethui_rpc::commands::rpc_eth_call,
                 ethui_rpc::commands::rpc_get_code,
                 ethui_rpc::commands::rpc_is_contract,
+                ethui_rpc::commands::rpc_history,
+                ethui_rpc::commands::rpc_history_clear,
+                ethui_rpc::commands::rpc_pending_txs,
+                ethui_rpc::commands::rpc_speed_up,
+                ethui_rpc::commands::rpc_cancel,
+                ethui_rpc::commands::rpc_safe_proposals,
+                ethui_rpc::commands::rpc_safe_sign,
+                ethui_rpc::commands::rpc_safe_add_signature,
+                ethui_rpc::commands::rpc_safe_execute,
+                ethui_rpc::commands::rpc_safe_override_threshold,
                 ethui_connections::commands::connections_affinity_for,
                 ethui_connections::commands::connections_set_affinity,
+                ethui_connections::commands::connections_account_for,
+                ethui_connections::commands::connections_set_account,
                 ethui_sync::commands::sync_alchemy_is_network_supported,
                 ethui_sync::commands::sync_get_native_balance,
                 ethui_simulator::commands::simulator_run,
+                ethui_simulator::commands::simulator_run_bundle,
                 ethui_simulator::commands::simulator_get_call_count,
                 ethui_walletconnect::commands::wc_pair,
                 ethui_walletconnect::commands::wc_disconnect,
@@ -233,9 +255,11 @@ async fn init(app: &tauri::App, args: &Args) -> color_eyre::Result<()> {
     ethui_sync::init().await;
     ethui_settings::init(resource(app, "settings.json", args))?;
     ethui_ws::init(args).await;
+    ethui_http::init(args).await;
     ethui_connections::init(resource(app, "connections.json", args)).await;
     ethui_wallets::init(resource(app, "wallets.json", args)).await;
     ethui_networks::init(resource(app, "networks.json", args)).await;
+    ethui_rpc::init().await;
     ethui_walletconnect::init().await;
     ethui_sol_artifacts::init().await?;
     ethui_analytics::init(app.handle()).await;
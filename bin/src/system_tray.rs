@@ -1,9 +1,12 @@
+use ethui_broadcast::InternalMsg;
 use tauri::{
     AppHandle, Manager,
     menu::{MenuBuilder, MenuEvent, MenuItemBuilder},
     tray::TrayIconBuilder,
 };
 
+const TRAY_ID: &str = "main";
+
 pub(crate) fn build(app: &AppHandle) -> color_eyre::Result<()> {
     let menu_builder = MenuBuilder::new(app);
 
@@ -11,15 +14,19 @@ pub(crate) fn build(app: &AppHandle) -> color_eyre::Result<()> {
         .item(&MenuItemBuilder::with_id("tray/show", "Show").build(app)?)
         .item(&MenuItemBuilder::with_id("tray/hide", "Hide").build(app)?)
         .separator()
+        .item(&MenuItemBuilder::with_id("tray/lock", "Lock all wallets").build(app)?)
+        .separator()
         .item(&MenuItemBuilder::with_id("tray/quit", "Quit").build(app)?)
         .build()?;
 
-    TrayIconBuilder::new()
+    TrayIconBuilder::with_id(TRAY_ID)
         .icon(app.default_window_icon().unwrap().clone())
         .menu(&menu)
         .on_menu_event(event_handler)
         .build(app)?;
 
+    tokio::spawn(lock_state_receiver(app.clone()));
+
     Ok(())
 }
 
@@ -36,6 +43,31 @@ fn event_handler(app: &AppHandle, event: MenuEvent) {
         "tray/show" => {
             tokio::spawn(async { ethui_broadcast::main_window_show().await });
         }
+        "tray/lock" => {
+            tokio::spawn(async { ethui_wallets::lock_all().await });
+        }
         _ => {}
     }
 }
+
+/// Keeps the tray tooltip up to date with how many wallets are unlocked
+async fn lock_state_receiver(app: AppHandle) {
+    let mut rx = ethui_broadcast::subscribe_internal().await;
+
+    loop {
+        if let Ok(InternalMsg::WalletsLockChanged) = rx.recv().await {
+            let states = ethui_wallets::get_lock_states().await;
+            let unlocked = states.values().filter(|unlocked| **unlocked).count();
+
+            let tooltip = match unlocked {
+                0 => "ethui".to_string(),
+                1 => "ethui (1 wallet unlocked)".to_string(),
+                n => format!("ethui ({n} wallets unlocked)"),
+            };
+
+            if let Some(tray) = app.tray_by_id(TRAY_ID) {
+                let _ = tray.set_tooltip(Some(tooltip));
+            }
+        }
+    }
+}
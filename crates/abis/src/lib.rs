@@ -25,3 +25,66 @@ sol! {
         function tokenURI(uint256 tokenId) public view returns (string uri);
     }
 }
+
+sol! {
+    contract IERC1155 {
+        event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value);
+        event TransferBatch(address indexed operator, address indexed from, address indexed to, uint256[] ids, uint256[] values);
+    }
+}
+
+sol! {
+    #[sol(rpc)]
+    contract ISafe {
+        function VERSION() public view returns (string memory version);
+        function getOwners() public view returns (address[] owners);
+        function getThreshold() public view returns (uint256 threshold);
+        function nonce() public view returns (uint256 nonce);
+        function execTransaction(
+            address to,
+            uint256 value,
+            bytes calldata data,
+            uint8 operation,
+            uint256 safeTxGas,
+            uint256 baseGas,
+            uint256 gasPrice,
+            address gasToken,
+            address refundReceiver,
+            bytes memory signatures
+        ) public payable returns (bool success);
+    }
+}
+
+sol! {
+    /// ERC-4337 v0.7 user operation, as packed by the EntryPoint
+    #[derive(Debug, Default)]
+    struct PackedUserOperation {
+        address sender;
+        uint256 nonce;
+        bytes initCode;
+        bytes callData;
+        bytes32 accountGasLimits;
+        uint256 preVerificationGas;
+        bytes32 gasFees;
+        bytes paymasterAndData;
+        bytes signature;
+    }
+
+    #[sol(rpc)]
+    contract IEntryPoint {
+        function getNonce(address sender, uint192 key) public view returns (uint256 nonce);
+        function getUserOpHash(PackedUserOperation calldata userOp) public view returns (bytes32);
+    }
+
+    /// The reference `SimpleAccountFactory` from eth-infinitism
+    #[sol(rpc)]
+    contract ISimpleAccountFactory {
+        function createAccount(address owner, uint256 salt) public returns (address account);
+        function getAddress(address owner, uint256 salt) public view returns (address);
+    }
+
+    #[sol(rpc)]
+    contract ISimpleAccount {
+        function execute(address dest, uint256 value, bytes calldata func) external;
+    }
+}
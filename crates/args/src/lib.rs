@@ -16,6 +16,9 @@ pub struct Args {
     #[arg(long, default_value_t = default_ws_port(), env = "ETHUI_WS_PORT")]
     pub ws_port: u16,
 
+    #[arg(long, default_value_t = default_http_port(), env = "ETHUI_HTTP_PORT")]
+    pub http_port: u16,
+
     #[arg(long, default_value_t = default_stacks_port(), env = "ETHUI_STACKS_PORT")]
     pub stacks_port: u16,
 
@@ -58,6 +61,10 @@ const fn default_ws_port() -> u16 {
     if cfg!(debug_assertions) { 9102 } else { 9002 }
 }
 
+const fn default_http_port() -> u16 {
+    if cfg!(debug_assertions) { 9103 } else { 9003 }
+}
+
 const fn default_stacks_port() -> u16 {
     if cfg!(debug_assertions) { 9110 } else { 9010 }
 }
@@ -22,6 +22,8 @@ pub enum InternalMsg {
 
     WalletCreated,
     WalletConnected(String), // wallet_type
+    /// a wallet was unlocked or locked
+    WalletsLockChanged,
 
     PeerAdded,
 
@@ -126,6 +128,10 @@ mod internal_msgs {
         send(WalletConnected(wallet_type)).await;
     }
 
+    pub async fn wallets_lock_changed() {
+        send(WalletsLockChanged).await;
+    }
+
     pub async fn transaction_submitted(chain_id: u64) {
         send(TransactionSubmitted(chain_id)).await;
     }
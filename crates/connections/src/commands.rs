@@ -29,3 +29,22 @@ pub async fn connections_set_affinity(domain: &str, affinity: Affinity) -> Tauri
 
     Ok(())
 }
+
+#[tauri::command]
+pub async fn connections_account_for(domain: String) -> Option<Address> {
+    Store::read().await.get_account(&domain)
+}
+
+#[tauri::command]
+pub async fn connections_set_account(domain: &str, account: Option<Address>) -> TauriResult<()> {
+    if let Some(account) = account
+        && ethui_wallets::find_wallet(account).await.is_none()
+    {
+        return Err(eyre!("Unknown account {account}").into());
+    }
+
+    Store::write().await.set_account(domain, account)?;
+    ethui_broadcast::accounts_changed(account.into_iter().collect()).await;
+
+    Ok(())
+}
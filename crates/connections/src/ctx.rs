@@ -1,9 +1,12 @@
+use std::sync::Arc;
+
 use ethui_networks::{NetworksActorExt as _, networks};
-use ethui_types::{Affinity, GlobalState, Network, NetworkId, eyre};
+use ethui_types::{Address, Affinity, GlobalState, Network, NetworkId, eyre};
+use tokio::sync::RwLock;
 
 use crate::{
     Store,
-    permissions::{Permission, PermissionRequest, RequestedPermission},
+    permissions::{self, ETH_ACCOUNTS, Permission, PermissionRequest, RequestedPermission},
 };
 
 /// Context for a provider connection
@@ -14,12 +17,22 @@ use crate::{
 pub struct Ctx {
     /// The domain associated with a connection
     pub domain: Option<String>,
-    pub permissions: Vec<Permission>,
+
+    /// Permissions granted to a connection without a domain. Such connections can't be told apart
+    /// from one another, so their grants are kept only for as long as the connection lives
+    grants: Arc<RwLock<Vec<Permission>>>,
 }
 
 impl jsonrpc_core::Metadata for Ctx {}
 
 impl Ctx {
+    pub fn new(domain: Option<String>) -> Self {
+        Self {
+            domain,
+            ..Default::default()
+        }
+    }
+
     pub async fn get_affinity(&self) -> Affinity {
         if let Some(ref domain) = self.domain {
             Store::read().await.get_affinity(domain)
@@ -37,27 +50,41 @@ impl Ctx {
     }
 
     pub async fn network(&self) -> Network {
-        let chain_id = self.chain_id().await;
+        let networks = networks();
+
+        let network = match self.get_affinity().await {
+            // several networks may share the chain id. if the exact one is gone, any will do
+            Affinity::Sticky(id) => match networks.get(id).await {
+                Ok(None) => networks.get(id.chain_id()).await,
+                res => res,
+            },
+            _ => networks.get_current().await.map(Some),
+        };
 
-        networks()
-            .get(chain_id)
-            .await
+        network
             .expect("networks actor not available")
             .expect("network not found for chain_id")
     }
 
-    pub async fn switch_chain(&mut self, new_chain_id: u64) -> color_eyre::Result<()> {
-        if self.chain_id().await == new_chain_id {
+    /// Switches this connection to `new_chain_id`
+    ///
+    /// When several networks share that chain id, `dedup_id` picks one of them
+    pub async fn switch_chain(
+        &mut self,
+        new_chain_id: u64,
+        dedup_id: u64,
+    ) -> color_eyre::Result<()> {
+        let current = self.network().await.id();
+        if current.chain_id() == new_chain_id && current.dedup_id() == dedup_id {
             return Ok(());
         }
 
-        let networks = networks();
-        if networks.validate_chain_id(new_chain_id).await? {
-            let dedup_id = networks.get_lowest_dedup_id(new_chain_id).await?;
+        if networks().validate_chain_id(new_chain_id).await? {
+            let internal_id: NetworkId = (new_chain_id, dedup_id).into();
+
             match self.get_affinity().await {
                 // If affinity is not set, or sticky, update local affinity, and publish event
                 Affinity::Unset | Affinity::Sticky(_) => {
-                    let internal_id: NetworkId = (new_chain_id, 0u32).into();
                     let affinity = internal_id.into();
                     self.set_affinity(affinity).await?;
 
@@ -68,12 +95,7 @@ impl Ctx {
                 // If current affinity is global, there's nothing to update on this Ctx, and the
                 // domain is irrelevant in the update,
                 Affinity::Global => {
-                    ethui_broadcast::chain_changed(
-                        (new_chain_id, dedup_id).into(),
-                        None,
-                        Affinity::Global,
-                    )
-                    .await;
+                    ethui_broadcast::chain_changed(internal_id, None, Affinity::Global).await;
                 }
             };
 
@@ -94,32 +116,85 @@ impl Ctx {
         }
     }
 
-    // TODO: make this itneractive instead of blindly accepting all permissions
-    pub fn request_permissions(&mut self, request: PermissionRequest) -> Vec<RequestedPermission> {
-        let ret = request.clone().into_request_permissions_result();
+    /// Grants the requested permissions to this connection's domain, persisting them
+    ///
+    /// Callers are expected to have had the user approve the request. `accounts` are the ones the
+    /// user chose to expose, and restrict the `eth_accounts` permission, if it was requested
+    pub async fn request_permissions(
+        &self,
+        request: PermissionRequest,
+        accounts: Vec<Address>,
+    ) -> color_eyre::Result<Vec<RequestedPermission>> {
+        let ret = request.into_request_permissions_result();
 
         let new_permissions: Vec<_> = request
             .into_permissions(self.domain.clone().unwrap_or_default())
+            .map(|p| match p.parent_capability.as_str() {
+                ETH_ACCOUNTS => Permission::eth_accounts(p.invoker, accounts.clone()),
+                _ => p,
+            })
             .collect();
 
-        self.permissions.extend(new_permissions);
+        self.grant_permissions(new_permissions).await?;
+        ethui_broadcast::accounts_changed(accounts).await;
 
-        ret
+        Ok(ret)
     }
 
-    pub fn revoke_permissions(&mut self, request: PermissionRequest) -> Vec<RequestedPermission> {
-        let ret = request.clone().into_request_permissions_result();
+    /// Grants permissions to this connection, replacing any previous grant of the same capability
+    ///
+    /// They're persisted for the connection's domain, or kept in memory if it has none
+    pub async fn grant_permissions(&self, permissions: Vec<Permission>) -> color_eyre::Result<()> {
+        match self.domain {
+            Some(ref domain) => Store::write()
+                .await
+                .grant_permissions(domain, permissions)?,
+            None => permissions::merge(&mut *self.grants.write().await, permissions),
+        }
 
-        let to_revoke: Vec<_> = request
-            .into_permissions(self.domain.clone().unwrap_or_default())
-            .collect();
+        Ok(())
+    }
+
+    pub async fn revoke_permissions(
+        &self,
+        request: PermissionRequest,
+    ) -> color_eyre::Result<Vec<RequestedPermission>> {
+        let ret = request.into_request_permissions_result();
+        let to_revoke: Vec<_> = request.capabilities().cloned().collect();
+
+        match self.domain {
+            Some(ref domain) => Store::write()
+                .await
+                .revoke_permissions(domain, &to_revoke)?,
+            None => self
+                .grants
+                .write()
+                .await
+                .retain(|p| !to_revoke.contains(&p.parent_capability)),
+        }
+        ethui_broadcast::accounts_changed(vec![]).await;
+
+        Ok(ret)
+    }
 
-        self.permissions.retain(|p| !to_revoke.contains(p));
+    pub async fn get_permissions(&self) -> Vec<Permission> {
+        match self.domain {
+            Some(ref domain) => Store::read().await.get_permissions(domain),
+            None => self.grants.read().await.clone(),
+        }
+    }
 
-        ret
+    /// Account pinned to this connection's domain, if any
+    pub async fn account(&self) -> Option<Address> {
+        let domain = self.domain.as_deref()?;
+        Store::read().await.get_account(domain)
     }
 
-    pub fn get_permissions(&self) -> &Vec<Permission> {
-        &self.permissions
+    /// Accounts this connection is allowed to see. `None` if it was never granted `eth_accounts`
+    pub async fn exposed_accounts(&self) -> Option<Vec<Address>> {
+        match self.domain {
+            Some(ref domain) => Store::read().await.exposed_accounts(domain),
+            None => permissions::exposed_accounts(&self.grants.read().await),
+        }
     }
 }
@@ -12,14 +12,15 @@ use serde_json::json;
 
 use crate::{Store, store::SerializedStore};
 
-pub type LatestVersion = ConstI64<2>;
+pub type LatestVersion = ConstI64<3>;
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(untagged)]
 enum Versions {
     V0(SerializedStoreV0),
     V1(SerializedStoreV1),
-    V2(SerializedStore),
+    V2(SerializedStoreV2),
+    V3(SerializedStore),
 }
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
@@ -36,6 +37,13 @@ pub struct SerializedStoreV1 {
     version: ConstI64<1>,
 }
 
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SerializedStoreV2 {
+    affinities: HashMap<String, Affinity>,
+    version: ConstI64<2>,
+}
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, Default, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum AffinityV0 {
@@ -74,11 +82,18 @@ fn run_migrations(store: Versions) -> SerializedStore {
             affinities: v0.affinities,
             version: ConstI64,
         })),
-        Versions::V1(v1) => SerializedStore {
+        Versions::V1(v1) => run_migrations(Versions::V2(SerializedStoreV2 {
             affinities: migrate_affinities_from_v1_to_v2(v1.affinities),
             version: ConstI64,
+        })),
+        // v3 introduces persisted permissions. no domain had any before
+        Versions::V2(v2) => SerializedStore {
+            affinities: v2.affinities,
+            permissions: Default::default(),
+            accounts: Default::default(),
+            version: ConstI64,
         },
-        Versions::V2(latest) => latest,
+        Versions::V3(latest) => latest,
     }
 }
 
@@ -102,7 +117,7 @@ mod tests {
         io::{BufReader, Write},
     };
 
-    use ethui_types::Affinity;
+    use ethui_types::{Address, Affinity};
     use serde_json::json;
     use tempfile::NamedTempFile;
 
@@ -129,12 +144,12 @@ mod tests {
             let reader = BufReader::new(file);
 
             let updated_store: serde_json::Value = serde_json::from_reader(reader).unwrap();
-            assert_eq!(updated_store["version"], 2);
+            assert_eq!(updated_store["version"], 3);
         }
     }
 
     #[test]
-    fn it_returns_v2_from_v2() {
+    fn it_converts_from_v2_to_v3() {
         let mut tempfile = NamedTempFile::new().unwrap();
         let store = json!({
             "version": 2,
@@ -154,10 +169,42 @@ mod tests {
             let reader = BufReader::new(file);
 
             let updated_store: serde_json::Value = serde_json::from_reader(reader).unwrap();
-            assert_eq!(updated_store["version"], 2);
+            assert_eq!(updated_store["version"], 3);
+            assert_eq!(updated_store["permissions"], json!({}));
         }
     }
 
+    #[test]
+    fn it_returns_v3_from_v3() {
+        let mut tempfile = NamedTempFile::new().unwrap();
+        let store = json!({
+            "version": 3,
+            "affinities": {
+                "localhost": "global",
+            },
+            "permissions": {
+                "localhost": [{
+                    "invoker": "localhost",
+                    "parent_capability": "eth_accounts",
+                    "caveats": [{
+                        "type": "restrictReturnedAccounts",
+                        "value": ["0x0000000000000000000000000000000000000001"]
+                    }]
+                }]
+            }
+        });
+
+        write!(tempfile, "{store}").unwrap();
+
+        let store = load_and_migrate(&tempfile.path().to_path_buf()).unwrap();
+
+        assert_eq!(
+            store.exposed_accounts("localhost"),
+            Some(vec![Address::with_last_byte(1)])
+        );
+        assert_eq!(store.exposed_accounts("example.com"), None);
+    }
+
     #[test]
     fn it_fails_for_unknown_version() {
         let mut tempfile = NamedTempFile::new().unwrap();
@@ -1,13 +1,59 @@
 use ethui_types::prelude::*;
 
-#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+/// The permission that gates access to accounts (EIP-2255)
+pub const ETH_ACCOUNTS: &str = "eth_accounts";
+
+/// Caveat restricting which accounts are exposed through `eth_accounts`
+pub const RESTRICT_RETURNED_ACCOUNTS: &str = "restrictReturnedAccounts";
+
+/// Adds `permissions` to the `granted` ones, replacing any previous grant of the same capability
+pub(crate) fn merge(granted: &mut Vec<Permission>, permissions: Vec<Permission>) {
+    granted.retain(|p| {
+        !permissions
+            .iter()
+            .any(|new| new.parent_capability == p.parent_capability)
+    });
+    granted.extend(permissions);
+}
+
+/// Accounts exposed by the `granted` permissions. `None` if `eth_accounts` isn't one of them
+pub(crate) fn exposed_accounts(granted: &[Permission]) -> Option<Vec<Address>> {
+    granted
+        .iter()
+        .find(|p| p.parent_capability == ETH_ACCOUNTS)
+        .map(|p| p.restricted_accounts().unwrap_or_default())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Permission {
     pub invoker: String,
     pub parent_capability: String,
     pub caveats: Vec<Caveat>,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+impl Permission {
+    /// An `eth_accounts` permission, restricted to the given accounts
+    pub fn eth_accounts(invoker: String, accounts: Vec<Address>) -> Self {
+        Self {
+            invoker,
+            parent_capability: ETH_ACCOUNTS.into(),
+            caveats: vec![Caveat {
+                r#type: RESTRICT_RETURNED_ACCOUNTS.into(),
+                value: json!(accounts),
+            }],
+        }
+    }
+
+    /// The accounts listed in a `restrictReturnedAccounts` caveat, if there is one
+    pub fn restricted_accounts(&self) -> Option<Vec<Address>> {
+        self.caveats
+            .iter()
+            .find(|c| c.r#type == RESTRICT_RETURNED_ACCOUNTS)
+            .and_then(|c| serde_json::from_value(c.value.clone()).ok())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Caveat {
     pub r#type: String,
     pub value: serde_json::Value,
@@ -19,6 +65,23 @@ pub struct PermissionRequest {
 }
 
 impl PermissionRequest {
+    /// A request for `eth_accounts` alone, as implied by `eth_requestAccounts`
+    pub fn accounts() -> Self {
+        Self {
+            methods: HashMap::from([(ETH_ACCOUNTS.to_string(), Default::default())]),
+        }
+    }
+
+    /// Whether `eth_accounts` is one of the requested permissions
+    pub fn includes_accounts(&self) -> bool {
+        self.methods.contains_key(ETH_ACCOUNTS)
+    }
+
+    /// Names of the requested permissions
+    pub fn capabilities(&self) -> impl Iterator<Item = &String> {
+        self.methods.keys()
+    }
+
     pub fn into_permissions(self, invoker: String) -> impl Iterator<Item = Permission> {
         self.methods
             .into_iter()
@@ -4,10 +4,13 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use ethui_types::{Affinity, NetworkId};
+use ethui_types::{Address, Affinity, NetworkId};
 use serde::{Deserialize, Serialize};
 
-use crate::migrations::LatestVersion;
+use crate::{
+    migrations::LatestVersion,
+    permissions::{self, Permission},
+};
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase", default)]
@@ -16,6 +19,10 @@ pub struct SerializedStore {
     // rule is currently a domain, but may eventually grow
     // TODO: removing networks will cause some affinities to become invalid. need to clean them up
     pub(crate) affinities: HashMap<String, Affinity>,
+    // maps domain -> permissions granted to it
+    pub(crate) permissions: HashMap<String, Vec<Permission>>,
+    // maps domain -> account pinned to it. domains without one follow the current wallet
+    pub(crate) accounts: HashMap<String, Address>,
     pub version: LatestVersion,
 }
 
@@ -45,6 +52,94 @@ impl Store {
         Ok(())
     }
 
+    pub fn get_account(&self, domain: &str) -> Option<Address> {
+        self.inner.accounts.get(domain).cloned()
+    }
+
+    /// Pins an account to a domain, exposing it if it wasn't already. Callers are expected to have
+    /// checked that it belongs to one of the user's wallets
+    ///
+    /// `None` makes the domain follow the current wallet again
+    pub fn set_account(
+        &mut self,
+        domain: &str,
+        account: Option<Address>,
+    ) -> color_eyre::Result<()> {
+        match account {
+            None => {
+                self.inner.accounts.remove(domain);
+            }
+            Some(account) => {
+                self.inner.accounts.insert(domain.to_string(), account);
+
+                let mut exposed = self.exposed_accounts(domain).unwrap_or_default();
+                if !exposed.contains(&account) {
+                    exposed.push(account);
+                    permissions::merge(
+                        self.inner
+                            .permissions
+                            .entry(domain.to_string())
+                            .or_default(),
+                        vec![Permission::eth_accounts(domain.to_string(), exposed)],
+                    );
+                }
+            }
+        };
+        self.save()?;
+
+        Ok(())
+    }
+
+    pub fn get_permissions(&self, domain: &str) -> Vec<Permission> {
+        self.inner
+            .permissions
+            .get(domain)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Grants permissions to a domain, replacing any previous grant of the same capability
+    pub fn grant_permissions(
+        &mut self,
+        domain: &str,
+        permissions: Vec<Permission>,
+    ) -> color_eyre::Result<()> {
+        let granted = self
+            .inner
+            .permissions
+            .entry(domain.to_string())
+            .or_default();
+
+        permissions::merge(granted, permissions);
+        self.save()?;
+
+        Ok(())
+    }
+
+    pub fn revoke_permissions(
+        &mut self,
+        domain: &str,
+        capabilities: &[String],
+    ) -> color_eyre::Result<()> {
+        if let Some(granted) = self.inner.permissions.get_mut(domain) {
+            granted.retain(|p| !capabilities.contains(&p.parent_capability));
+
+            if granted.is_empty() {
+                self.inner.permissions.remove(domain);
+            }
+        }
+        self.save()?;
+
+        Ok(())
+    }
+
+    /// Accounts a domain is allowed to see
+    ///
+    /// `None` means the domain was never granted `eth_accounts`
+    pub fn exposed_accounts(&self, domain: &str) -> Option<Vec<Address>> {
+        permissions::exposed_accounts(self.inner.permissions.get(domain)?)
+    }
+
     // Persists current state to disk
     pub(crate) fn save(&self) -> color_eyre::Result<()> {
         let pathbuf = self.file.clone();
@@ -6,4 +6,8 @@ mod erc721;
 mod events;
 mod kv;
 mod native_balance;
+mod pending_txs;
+mod rpc_cache;
+mod rpc_history;
+mod safe_proposals;
 mod transactions;
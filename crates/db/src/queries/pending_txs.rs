@@ -0,0 +1,79 @@
+use ethui_types::{Address, B256, NetworkId, PendingTx, PendingTxStatus};
+
+use crate::DbInner;
+
+impl DbInner {
+    pub async fn save_pending_tx(&self, tx: &PendingTx) -> color_eyre::Result<()> {
+        sqlx::query(
+            r#" INSERT OR REPLACE INTO pending_txs (hash, chain_id, dedup_id, from_address, nonce, request, status, replaces)
+                VALUES (?,?,?,?,?,?,?,?) "#,
+        )
+        .bind(format!("0x{:x}", tx.hash))
+        .bind(tx.chain_id as i64)
+        .bind(tx.dedup_id as i64)
+        .bind(format!("0x{:x}", tx.from))
+        .bind(tx.nonce as i64)
+        .bind(tx.request.to_string())
+        .bind(tx.status.as_str())
+        .bind(tx.replaces.map(|h| format!("0x{h:x}")))
+        .execute(self.pool())
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_pending_tx(&self, hash: B256) -> color_eyre::Result<Option<PendingTx>> {
+        sqlx::query(r#" SELECT * FROM pending_txs WHERE hash = ? "#)
+            .bind(format!("0x{hash:x}"))
+            .fetch_optional(self.pool())
+            .await?
+            .map(PendingTx::try_from)
+            .transpose()
+    }
+
+    /// Transactions from an address that are still waiting to be mined, lowest nonce first
+    pub async fn get_pending_txs(
+        &self,
+        network_id: NetworkId,
+        from: Address,
+    ) -> color_eyre::Result<Vec<PendingTx>> {
+        sqlx::query(
+            r#" SELECT *
+                FROM pending_txs
+                WHERE chain_id = ? AND dedup_id = ? AND from_address = ? AND status = 'pending'
+                ORDER BY nonce ASC, created_at ASC "#,
+        )
+        .bind(network_id.chain_id() as i64)
+        .bind(network_id.dedup_id() as i64)
+        .bind(format!("0x{from:x}"))
+        .fetch_all(self.pool())
+        .await?
+        .into_iter()
+        .map(PendingTx::try_from)
+        .collect()
+    }
+
+    /// Every transaction still waiting to be mined, across all networks
+    pub async fn get_all_pending_txs(&self) -> color_eyre::Result<Vec<PendingTx>> {
+        sqlx::query(r#" SELECT * FROM pending_txs WHERE status = 'pending' "#)
+            .fetch_all(self.pool())
+            .await?
+            .into_iter()
+            .map(PendingTx::try_from)
+            .collect()
+    }
+
+    pub async fn set_pending_tx_status(
+        &self,
+        hash: B256,
+        status: PendingTxStatus,
+    ) -> color_eyre::Result<()> {
+        sqlx::query(r#" UPDATE pending_txs SET status = ? WHERE hash = ? "#)
+            .bind(status.as_str())
+            .bind(format!("0x{hash:x}"))
+            .execute(self.pool())
+            .await?;
+
+        Ok(())
+    }
+}
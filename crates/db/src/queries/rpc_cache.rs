@@ -0,0 +1,65 @@
+use sqlx::Row;
+
+use crate::DbInner;
+
+impl DbInner {
+    pub async fn rpc_cache_get(
+        &self,
+        chain_id: u64,
+        dedup_id: u64,
+        key: &str,
+    ) -> color_eyre::Result<Option<String>> {
+        let row = sqlx::query(
+            r#" UPDATE rpc_cache
+                SET accessed_at = strftime('%s', 'now')
+                WHERE chain_id = ? AND dedup_id = ? AND key = ?
+                RETURNING value "#,
+        )
+        .bind(chain_id as i64)
+        .bind(dedup_id as i64)
+        .bind(key)
+        .fetch_optional(self.pool())
+        .await?;
+
+        Ok(row.map(|r| r.get("value")))
+    }
+
+    /// Caches a response, then evicts the least recently used ones until the total size is back
+    /// under `max_size` bytes
+    pub async fn rpc_cache_set(
+        &self,
+        chain_id: u64,
+        dedup_id: u64,
+        key: &str,
+        value: &str,
+        max_size: u64,
+    ) -> color_eyre::Result<()> {
+        sqlx::query(
+            r#" INSERT OR REPLACE INTO rpc_cache (chain_id, dedup_id, key, value, size, accessed_at)
+                VALUES (?,?,?,?,?, strftime('%s', 'now')) "#,
+        )
+        .bind(chain_id as i64)
+        .bind(dedup_id as i64)
+        .bind(key)
+        .bind(value)
+        .bind(value.len() as i64)
+        .execute(self.pool())
+        .await?;
+
+        sqlx::query(
+            r#" DELETE FROM rpc_cache
+                WHERE rowid IN (
+                  SELECT rowid FROM (
+                    SELECT rowid, SUM(size) OVER (ORDER BY accessed_at DESC, rowid DESC) AS total
+                    FROM rpc_cache
+                  )
+                  WHERE total > ?
+                ) "#,
+        )
+        .bind(max_size as i64)
+        .execute(self.pool())
+        .await?;
+
+        Ok(())
+    }
+}
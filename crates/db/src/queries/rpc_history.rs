@@ -0,0 +1,74 @@
+use ethui_types::RpcRecord;
+
+use crate::DbInner;
+
+impl DbInner {
+    /// Persists a record, dropping the oldest ones so that at most `max_rows` are kept
+    pub async fn save_rpc_record(
+        &self,
+        record: &RpcRecord,
+        max_rows: u64,
+    ) -> color_eyre::Result<()> {
+        sqlx::query(
+            r#" INSERT INTO rpc_history (timestamp, domain, chain_id, dedup_id, method, params, result, error, latency_ms)
+                VALUES (?,?,?,?,?,?,?,?,?) "#,
+        )
+        .bind(record.timestamp as i64)
+        .bind(&record.domain)
+        .bind(record.network_id.chain_id() as i64)
+        .bind(record.network_id.dedup_id() as i64)
+        .bind(&record.method)
+        .bind(record.params.to_string())
+        .bind(record.result.as_ref().map(ToString::to_string))
+        .bind(record.error.as_ref().map(ToString::to_string))
+        .bind(record.latency_ms as i64)
+        .execute(self.pool())
+        .await?;
+
+        sqlx::query(
+            r#" DELETE FROM rpc_history
+                WHERE id <= (SELECT MAX(id) FROM rpc_history) - ? "#,
+        )
+        .bind(max_rows as i64)
+        .execute(self.pool())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Most recent records first, optionally filtered by domain and method
+    pub async fn get_rpc_records(
+        &self,
+        domain: Option<&str>,
+        method: Option<&str>,
+        limit: u64,
+    ) -> color_eyre::Result<Vec<RpcRecord>> {
+        let res = sqlx::query(
+            r#" SELECT *
+                FROM rpc_history
+                WHERE (? IS NULL OR domain = ?) AND (? IS NULL OR method = ?)
+                ORDER BY id DESC
+                LIMIT ? "#,
+        )
+        .bind(domain)
+        .bind(domain)
+        .bind(method)
+        .bind(method)
+        .bind(limit as i64)
+        .fetch_all(self.pool())
+        .await?
+        .into_iter()
+        .map(RpcRecord::try_from)
+        .collect::<Result<_, _>>()?;
+
+        Ok(res)
+    }
+
+    pub async fn clear_rpc_records(&self) -> color_eyre::Result<()> {
+        sqlx::query("DELETE FROM rpc_history")
+            .execute(self.pool())
+            .await?;
+
+        Ok(())
+    }
+}
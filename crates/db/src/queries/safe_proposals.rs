@@ -0,0 +1,56 @@
+use ethui_types::{Address, B256, SafeProposal};
+
+use crate::DbInner;
+
+impl DbInner {
+    pub async fn save_safe_proposal(&self, proposal: &SafeProposal) -> color_eyre::Result<()> {
+        sqlx::query(
+            r#" INSERT OR REPLACE INTO safe_proposals (safe_tx_hash, chain_id, safe, nonce, tx, signatures, executed_in)
+                VALUES (?,?,?,?,?,?,?) "#,
+        )
+        .bind(format!("0x{:x}", proposal.safe_tx_hash))
+        .bind(proposal.chain_id as i64)
+        .bind(format!("0x{:x}", proposal.safe))
+        .bind(proposal.nonce as i64)
+        .bind(proposal.tx.to_string())
+        .bind(serde_json::to_string(&proposal.signatures)?)
+        .bind(proposal.executed_in.map(|h| format!("0x{h:x}")))
+        .execute(self.pool())
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_safe_proposal(
+        &self,
+        safe_tx_hash: B256,
+    ) -> color_eyre::Result<Option<SafeProposal>> {
+        sqlx::query(r#" SELECT * FROM safe_proposals WHERE safe_tx_hash = ? "#)
+            .bind(format!("0x{safe_tx_hash:x}"))
+            .fetch_optional(self.pool())
+            .await?
+            .map(SafeProposal::try_from)
+            .transpose()
+    }
+
+    /// Proposals for a Safe, most recent nonce first
+    pub async fn get_safe_proposals(
+        &self,
+        chain_id: u64,
+        safe: Address,
+    ) -> color_eyre::Result<Vec<SafeProposal>> {
+        sqlx::query(
+            r#" SELECT *
+                FROM safe_proposals
+                WHERE chain_id = ? AND safe = ?
+                ORDER BY nonce DESC "#,
+        )
+        .bind(chain_id as i64)
+        .bind(format!("0x{safe:x}"))
+        .fetch_all(self.pool())
+        .await?
+        .into_iter()
+        .map(SafeProposal::try_from)
+        .collect()
+    }
+}
@@ -20,6 +20,15 @@ pub(super) static PRESETS: Lazy<HashMap<String, Preset>> = Lazy::new(|| {
         },
     );
 
+    presets.insert(
+        "calls-review".into(),
+        Preset {
+            title: "Review Calls".into(),
+            w: 1200.0,
+            h: 900.0,
+        },
+    );
+
     presets.insert(
         "msg-sign".into(),
         Preset {
@@ -38,6 +47,15 @@ pub(super) static PRESETS: Lazy<HashMap<String, Preset>> = Lazy::new(|| {
         },
     );
 
+    presets.insert(
+        "connection-request".into(),
+        Preset {
+            title: "Connection Request".into(),
+            w: 400.0,
+            h: 500.0,
+        },
+    );
+
     presets.insert(
         "chain-add".into(),
         Preset {
@@ -56,6 +74,15 @@ pub(super) static PRESETS: Lazy<HashMap<String, Preset>> = Lazy::new(|| {
         },
     );
 
+    presets.insert(
+        "chain-select".into(),
+        Preset {
+            title: "Select Network".into(),
+            w: 400.0,
+            h: 450.0,
+        },
+    );
+
     presets.insert(
         "erc20-add".into(),
         Preset {
@@ -0,0 +1,9 @@
+use ethui_args::Args;
+
+use crate::server::server;
+
+pub async fn init(args: &Args) {
+    let port = args.http_port;
+
+    tokio::spawn(async move { server(port).await });
+}
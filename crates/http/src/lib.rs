@@ -0,0 +1,4 @@
+mod init;
+mod server;
+
+pub use init::init;
@@ -0,0 +1,232 @@
+use std::collections::{HashMap, VecDeque};
+
+use axum::{
+    Router,
+    extract::Path,
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::post,
+};
+use ethui_types::prelude::*;
+use jsonrpc_core::Version;
+use once_cell::sync::Lazy;
+use tokio::net::TcpListener;
+
+/// Header scripts can use to identify themselves, when not using a path segment
+const DOMAIN_HEADER: &str = "x-ethui-domain";
+
+/// How many domains keep a cached handler. Handlers are cheap to rebuild, so the oldest ones are
+/// dropped once this is reached
+const MAX_HANDLERS: usize = 64;
+
+/// One handler per domain, built on first use and reused for every request after that
+static HANDLERS: Lazy<RwLock<Handlers>> = Lazy::new(Default::default);
+
+#[derive(Default)]
+struct Handlers {
+    by_domain: HashMap<Option<String>, Arc<ethui_rpc::Handler>>,
+    /// insertion order, oldest first
+    order: VecDeque<Option<String>>,
+}
+
+/// HTTP JSON-RPC server, for scripts and tools that can't speak websockets (e.g. `forge script
+/// --rpc-url`)
+///
+/// Requests go through the same `ethui_rpc::Handler` as the WS server. The domain (used for
+/// network affinity and permissions) is either the first path segment (`POST /my-script`) or the
+/// `x-ethui-domain` header
+///
+/// Only non-browser clients are served: requests carrying an `Origin` header are refused, and
+/// bodies must be sent as `application/json`, which browsers can't do cross-origin without a
+/// preflight this server never answers
+#[instrument(level = "debug")]
+pub(crate) async fn server(port: u16) {
+    let addr = format!("127.0.0.1:{port}");
+    let listener = TcpListener::bind(&addr).await.expect("Can't listen to");
+
+    debug!("HTTP server listening on: {}", addr);
+
+    let app = Router::new()
+        .route("/", post(root))
+        .route("/{domain}", post(with_domain));
+
+    if let Err(e) = axum::serve(listener, app).await {
+        error!("HTTP server error: {}", e);
+    }
+}
+
+async fn root(headers: HeaderMap, body: String) -> Response {
+    if let Err(status) = check_headers(&headers) {
+        return status.into_response();
+    }
+
+    let domain = headers
+        .get(DOMAIN_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(ToOwned::to_owned);
+
+    handle(domain, body).await
+}
+
+async fn with_domain(Path(domain): Path<String>, headers: HeaderMap, body: String) -> Response {
+    if let Err(status) = check_headers(&headers) {
+        return status.into_response();
+    }
+
+    handle(Some(domain), body).await
+}
+
+/// Keeps web pages from reaching the server through the user's browser
+fn check_headers(headers: &HeaderMap) -> std::result::Result<(), StatusCode> {
+    if headers.contains_key(header::ORIGIN) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let is_json = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(';').next())
+        .is_some_and(|v| v.trim().eq_ignore_ascii_case("application/json"));
+
+    if !is_json {
+        return Err(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    Ok(())
+}
+
+async fn handle(domain: Option<String>, body: String) -> Response {
+    let response = match serde_json::from_str(&body) {
+        Ok(request) => handler(domain).await.handle(request).await,
+        Err(_) => Some(jsonrpc_core::Response::from(
+            jsonrpc_core::Error::parse_error(),
+            Some(Version::V2),
+        )),
+    };
+
+    // notifications get no response
+    let Some(response) = response else {
+        return StatusCode::NO_CONTENT.into_response();
+    };
+
+    match serde_json::to_string(&response) {
+        Ok(body) => ([(header::CONTENT_TYPE, "application/json")], body).into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+async fn handler(domain: Option<String>) -> Arc<ethui_rpc::Handler> {
+    if let Some(handler) = HANDLERS.read().await.by_domain.get(&domain) {
+        return handler.clone();
+    }
+
+    let mut handlers = HANDLERS.write().await;
+
+    // another request may have built it while waiting for the lock
+    if let Some(handler) = handlers.by_domain.get(&domain) {
+        return handler.clone();
+    }
+
+    if handlers.order.len() >= MAX_HANDLERS
+        && let Some(oldest) = handlers.order.pop_front()
+    {
+        handlers.by_domain.remove(&oldest);
+    }
+
+    let handler = Arc::new(ethui_rpc::Handler::new(domain.clone()));
+    handlers.by_domain.insert(domain.clone(), handler.clone());
+    handlers.order.push_back(domain);
+
+    handler
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    fn headers(pairs: &[(header::HeaderName, &'static str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.clone(), HeaderValue::from_static(value)))
+            .collect()
+    }
+
+    #[test]
+    fn accepts_json_from_non_browser_clients() {
+        let res = check_headers(&headers(&[(
+            header::CONTENT_TYPE,
+            "Application/JSON; charset=utf-8",
+        )]));
+
+        assert_eq!(res, Ok(()));
+    }
+
+    #[test]
+    fn refuses_requests_with_an_origin() {
+        let res = check_headers(&headers(&[
+            (header::CONTENT_TYPE, "application/json"),
+            (header::ORIGIN, "https://example.com"),
+        ]));
+
+        assert_eq!(res, Err(StatusCode::FORBIDDEN));
+    }
+
+    #[test]
+    fn refuses_non_json_bodies() {
+        for content_type in [
+            None,
+            Some("text/plain"),
+            Some("application/x-www-form-urlencoded"),
+        ] {
+            let headers = match content_type {
+                Some(content_type) => headers(&[(header::CONTENT_TYPE, content_type)]),
+                None => HeaderMap::new(),
+            };
+
+            assert_eq!(
+                check_headers(&headers),
+                Err(StatusCode::UNSUPPORTED_MEDIA_TYPE)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn refused_requests_never_reach_the_handler() {
+        let res = with_domain(Path("script".into()), HeaderMap::new(), "{".into()).await;
+
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[tokio::test]
+    async fn answers_malformed_bodies_with_a_parse_error() {
+        let res = root(
+            headers(&[(header::CONTENT_TYPE, "application/json")]),
+            "not json".into(),
+        )
+        .await;
+
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: Json = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], json!(-32700));
+    }
+
+    #[tokio::test]
+    async fn reuses_handlers_and_drops_the_oldest() {
+        let first = handler(Some("first".into())).await;
+        assert!(Arc::ptr_eq(&first, &handler(Some("first".into())).await));
+
+        for i in 0..MAX_HANDLERS {
+            handler(Some(format!("script-{i}"))).await;
+        }
+
+        let handlers = HANDLERS.read().await;
+        assert_eq!(handlers.order.len(), MAX_HANDLERS);
+        assert!(!handlers.by_domain.contains_key(&Some("first".into())));
+    }
+}
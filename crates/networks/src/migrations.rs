@@ -156,6 +156,7 @@ fn migrate_networks_from_v2_to_v3(
                     decimals: network.decimals,
                     status: NetworkStatus::Unknown,
                     is_stack: false,
+                    rpc_cache: false,
                 },
             )
         })
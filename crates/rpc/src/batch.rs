@@ -0,0 +1,170 @@
+use futures::{StreamExt as _, future};
+use jsonrpc_core::{Call, Id, Output, Response, Version};
+use tokio::sync::Mutex;
+
+use crate::Handler;
+
+/// How many calls of a single batch are dispatched at the same time
+const BATCH_CONCURRENCY: usize = 8;
+
+/// Methods that prompt the user with a dialog
+///
+/// Within a batch, these are run one at a time, so that dialogs don't pile up on top of each other
+const DIALOG_METHODS: &[&str] = &[
+    "eth_requestAccounts",
+    "eth_sendTransaction",
+    "eth_signTransaction",
+    "eth_sign",
+    "personal_sign",
+    "eth_signTypedData",
+    "eth_signTypedData_v4",
+    "wallet_requestPermissions",
+    "wallet_addEthereumChain",
+    "wallet_updateEthereumChain",
+    "wallet_switchEthereumChain",
+    "wallet_watchAsset",
+    "wallet_sendCalls",
+];
+
+impl Handler {
+    /// Handles a JSON-RPC batch
+    ///
+    /// Calls are dispatched concurrently, up to `BATCH_CONCURRENCY` at a time, and their outputs
+    /// are returned in the same order as the calls
+    pub(crate) async fn handle_batch(&self, calls: Vec<Call>) -> Option<Response> {
+        // an empty batch is an invalid request in itself
+        if calls.is_empty() {
+            return Some(Response::Single(Output::invalid_request(
+                Id::Null,
+                Some(Version::V2),
+            )));
+        }
+
+        let outputs = dispatch(calls, |call| self.handle_call(call)).await;
+
+        // a batch made of notifications only gets no response at all
+        if outputs.is_empty() {
+            None
+        } else {
+            Some(Response::Batch(outputs))
+        }
+    }
+}
+
+/// Runs every call through `handle`, keeping the outputs in the same order as the calls
+async fn dispatch<F, Fut>(calls: Vec<Call>, handle: F) -> Vec<Output>
+where
+    F: Fn(Call) -> Fut,
+    Fut: Future<Output = Option<Output>>,
+{
+    let dialogs = Mutex::new(());
+
+    futures::stream::iter(calls)
+        .map(|call| {
+            let dialogs = &dialogs;
+            let handle = &handle;
+            async move {
+                let _guard = match method(&call) {
+                    Some(m) if DIALOG_METHODS.contains(&m) => Some(dialogs.lock().await),
+                    _ => None,
+                };
+
+                handle(call).await
+            }
+        })
+        .buffered(BATCH_CONCURRENCY)
+        .filter_map(future::ready)
+        .collect()
+        .await
+}
+
+fn method(call: &Call) -> Option<&str> {
+    match call {
+        Call::MethodCall(c) => Some(&c.method),
+        Call::Notification(n) => Some(&n.method),
+        Call::Invalid { .. } => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use jsonrpc_core::{MethodCall, Params, Success};
+    use serde_json::json;
+
+    use super::*;
+
+    fn call(id: u64, method: &str, delay_ms: u64) -> Call {
+        Call::MethodCall(MethodCall {
+            jsonrpc: Some(Version::V2),
+            method: method.into(),
+            params: Params::Array(vec![json!(delay_ms)]),
+            id: Id::Num(id),
+        })
+    }
+
+    /// Sleeps for the call's delay, keeping track of how many calls are running at once
+    async fn handle(call: Call, running: &AtomicUsize, peak: &AtomicUsize) -> Option<Output> {
+        let Call::MethodCall(call) = call else {
+            return None;
+        };
+        let Params::Array(ref params) = call.params else {
+            unreachable!()
+        };
+        let delay = params[0].as_u64().unwrap();
+
+        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+        peak.fetch_max(now, Ordering::SeqCst);
+        tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+        running.fetch_sub(1, Ordering::SeqCst);
+
+        Some(Output::Success(Success {
+            jsonrpc: Some(Version::V2),
+            result: json!(delay),
+            id: call.id,
+        }))
+    }
+
+    fn ids(outputs: &[Output]) -> Vec<Id> {
+        outputs.iter().map(|o| o.id().clone()).collect()
+    }
+
+    #[tokio::test]
+    async fn outputs_follow_call_order() {
+        let (running, peak) = (AtomicUsize::new(0), AtomicUsize::new(0));
+
+        // earlier calls take longer, so they finish last
+        let calls = (0..20).map(|i| call(i, "eth_call", (20 - i) * 5)).collect();
+        let outputs = dispatch(calls, |call| handle(call, &running, &peak)).await;
+
+        assert_eq!(ids(&outputs), (0..20).map(Id::Num).collect::<Vec<_>>());
+        assert_eq!(peak.load(Ordering::SeqCst), BATCH_CONCURRENCY);
+    }
+
+    #[tokio::test]
+    async fn dialog_calls_run_one_at_a_time() {
+        let (running, peak) = (AtomicUsize::new(0), AtomicUsize::new(0));
+
+        let calls = (0..4).map(|i| call(i, "personal_sign", 10)).collect();
+        let outputs = dispatch(calls, |call| handle(call, &running, &peak)).await;
+
+        assert_eq!(ids(&outputs), (0..4).map(Id::Num).collect::<Vec<_>>());
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn notifications_get_no_output() {
+        let (running, peak) = (AtomicUsize::new(0), AtomicUsize::new(0));
+
+        let notification = Call::Notification(jsonrpc_core::Notification {
+            jsonrpc: Some(Version::V2),
+            method: "eth_call".into(),
+            params: Params::Array(vec![json!(0)]),
+        });
+        let calls = vec![call(0, "eth_call", 5), notification, call(2, "eth_call", 0)];
+        let outputs = dispatch(calls, |call| handle(call, &running, &peak)).await;
+
+        assert_eq!(ids(&outputs), vec![Id::Num(0), Id::Num(2)]);
+    }
+}
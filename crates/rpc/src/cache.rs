@@ -0,0 +1,210 @@
+//! Opt-in cache for upstream responses that can never change (e.g. receipts of finalized
+//! transactions, or state queries pinned to a finalized block)
+
+use std::{sync::LazyLock, time::Instant};
+
+use alloy::providers::Provider as _;
+use ethui_types::prelude::*;
+use jsonrpc_core::Params;
+
+use crate::error;
+
+/// Upper bound for the total size of cached responses, across all networks
+const MAX_SIZE: u64 = 64 * 1024 * 1024;
+
+/// How long the finalized block number of a network is trusted before being fetched again
+const FINALIZED_TTL: Duration = Duration::from_secs(60);
+
+/// Whether each network is a dev one. Memoized, since for most networks checking it costs an RPC
+/// call. Keyed by url as well, so editing a network invalidates it
+static DEV: LazyLock<RwLock<HashMap<(NetworkId, String), bool>>> = LazyLock::new(Default::default);
+
+static FINALIZED: LazyLock<RwLock<HashMap<NetworkId, (Instant, u64)>>> =
+    LazyLock::new(Default::default);
+
+/// Proxies a request to the network's provider, going through the cache when possible
+pub(crate) async fn request(
+    network: &Network,
+    method: &'static str,
+    params: Params,
+) -> jsonrpc_core::Result<Json> {
+    if !network.rpc_cache || is_dev(network).await || !is_immutable(network, method, &params).await
+    {
+        return proxy(network, method, params).await;
+    }
+
+    let db = ethui_db::get();
+    let key = json!([method, params]).to_string();
+
+    if let Ok(Some(cached)) = db
+        .rpc_cache_get(network.chain_id(), network.dedup_id(), &key)
+        .await
+        && let Ok(res) = serde_json::from_str(&cached)
+    {
+        return Ok(res);
+    }
+
+    let res = proxy(network, method, params).await?;
+
+    if is_final(network, method, &res).await
+        && let Err(e) = db
+            .rpc_cache_set(
+                network.chain_id(),
+                network.dedup_id(),
+                &key,
+                &res.to_string(),
+                MAX_SIZE,
+            )
+            .await
+    {
+        warn!("failed to cache {method} response: {e}");
+    }
+
+    Ok(res)
+}
+
+async fn proxy(
+    network: &Network,
+    method: &'static str,
+    params: Params,
+) -> jsonrpc_core::Result<Json> {
+    let res = network
+        .get_provider()
+        .raw_request::<_, Json>(method.into(), params.clone())
+        .await;
+
+    match res {
+        Ok(res) => Ok(res),
+        Err(e) if matches!(method, "eth_call" | "eth_estimateGas") => {
+            Err(error::with_revert_reason(error::alloy_to_jsonrpc_error(e), network, &params).await)
+        }
+        Err(e) => Err(error::alloy_to_jsonrpc_error(e)),
+    }
+}
+
+/// Dev networks get reverted, reset and re-forked all the time, so nothing is immutable there.
+/// Failing to tell is treated as dev as well, but only until the next successful check
+async fn is_dev(network: &Network) -> bool {
+    let key = (network.id(), network.http_url.to_string());
+
+    if let Some(dev) = DEV.read().await.get(&key) {
+        return *dev;
+    }
+
+    match network.is_dev().await {
+        Ok(dev) => {
+            DEV.write().await.insert(key, dev);
+            dev
+        }
+        Err(_) => true,
+    }
+}
+
+/// Whether the request targets data that cannot change: either addressed by hash, or pinned to a
+/// finalized block
+async fn is_immutable(network: &Network, method: &str, params: &Params) -> bool {
+    let Params::Array(params) = params else {
+        return false;
+    };
+
+    let block = match method {
+        "eth_getBlockByHash"
+        | "eth_getBlockTransactionCountByHash"
+        | "eth_getUncleCountByBlockHash"
+        | "eth_getUncleByBlockHashAndIndex"
+        | "eth_getTransactionByBlockHashAndIndex"
+        | "eth_getTransactionByHash"
+        | "eth_getTransactionReceipt" => return true,
+
+        "eth_getBlockByNumber"
+        | "eth_getBlockTransactionCountByNumber"
+        | "eth_getUncleCountByBlockNumber"
+        | "eth_getUncleByBlockNumberAndIndex"
+        | "eth_getTransactionByBlockNumberAndIndex" => params.first(),
+
+        "eth_getBalance" | "eth_getCode" | "eth_getTransactionCount" | "eth_call" => params.get(1),
+
+        "eth_getStorageAt" => params.get(2),
+
+        _ => None,
+    };
+
+    match block.map(BlockRef::from) {
+        Some(BlockRef::Hash) => true,
+        Some(BlockRef::Number(number)) => is_finalized(network, number).await,
+        _ => false,
+    }
+}
+
+/// Responses that are not worth caching, since a later request may find something different
+async fn is_final(network: &Network, method: &str, res: &Json) -> bool {
+    match method {
+        // pending transactions have no block yet, and the block of a mined one may still be
+        // reorged out
+        "eth_getTransactionByHash" | "eth_getTransactionReceipt" => {
+            match BlockRef::from(&res["blockNumber"]) {
+                BlockRef::Number(number) => is_finalized(network, number).await,
+                _ => false,
+            }
+        }
+        _ => !res.is_null(),
+    }
+}
+
+async fn is_finalized(network: &Network, number: u64) -> bool {
+    finalized(network)
+        .await
+        .is_some_and(|finalized| number <= finalized)
+}
+
+async fn finalized(network: &Network) -> Option<u64> {
+    if let Some((at, number)) = FINALIZED.read().await.get(&network.id())
+        && at.elapsed() < FINALIZED_TTL
+    {
+        return Some(*number);
+    }
+
+    let block = network
+        .get_provider()
+        .get_block_by_number(alloy::eips::BlockNumberOrTag::Finalized)
+        .await
+        .ok()
+        .flatten()?;
+
+    let number = block.header.number;
+    FINALIZED
+        .write()
+        .await
+        .insert(network.id(), (Instant::now(), number));
+    Some(number)
+}
+
+/// The block a request is pinned to, as given either by a plain block param or an EIP-1898 object
+enum BlockRef {
+    Hash,
+    Number(u64),
+    /// tags such as `latest`, or anything unexpected
+    Other,
+}
+
+impl From<&Json> for BlockRef {
+    fn from(value: &Json) -> Self {
+        if !value["blockHash"].is_null() {
+            return Self::Hash;
+        }
+
+        let value = match value.get("blockNumber") {
+            Some(number) => number,
+            None => value,
+        };
+
+        match value.as_str() {
+            Some(s) if s.len() == 66 => Self::Hash,
+            Some(s) => s
+                .strip_prefix("0x")
+                .and_then(|hex| u64::from_str_radix(hex, 16).ok())
+                .map_or(Self::Other, Self::Number),
+            None => Self::Other,
+        }
+    }
+}
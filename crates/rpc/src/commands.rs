@@ -1,17 +1,23 @@
-use ethui_types::prelude::*;
+use ethui_types::{NetworkId, PendingTx, RpcRecord, SafeProposal, prelude::*};
 
-use super::{Result, methods, methods::Method, utils};
+use super::{
+    Result, inspector, methods,
+    methods::Method,
+    nonces::{self, Replacement},
+    safe, utils,
+};
 
 #[tauri::command]
 pub async fn rpc_send_transaction(params: Json) -> Result<Json> {
     let params: methods::send_call::CallParams = serde_json::from_value(params)?;
-    let (from, request) = params.into_request_with_from().await?;
+    let (from, request) = params.into_request_with_from(&Default::default()).await?;
     let network = utils::get_current_network().await;
 
     let method = methods::SendTransaction {
         network,
         from,
         request,
+        replaces: None,
     };
 
     method.run().await
@@ -37,3 +43,116 @@ pub async fn rpc_is_contract(address: Address, chain_id: u64) -> Result<bool> {
     let code = utils::get_code(address, chain_id).await?;
     Ok(code.is_some())
 }
+
+/// Calls recorded by the request inspector, most recent first
+#[tauri::command]
+pub async fn rpc_history(
+    domain: Option<String>,
+    method: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<RpcRecord>> {
+    let limit = limit.unwrap_or(100);
+
+    Ok(inspector::get(domain.as_deref(), method.as_deref(), limit).await?)
+}
+
+#[tauri::command]
+pub async fn rpc_history_clear() -> Result<()> {
+    Ok(inspector::clear().await?)
+}
+
+#[tauri::command]
+pub async fn rpc_safe_proposals(chain_id: u64, safe: Address) -> Result<Vec<SafeProposal>> {
+    Ok(ethui_db::get().get_safe_proposals(chain_id, safe).await?)
+}
+
+/// Collects signatures for a Safe proposal from local owners (e.g. after adding an owner's wallet)
+#[tauri::command]
+pub async fn rpc_safe_sign(safe_tx_hash: B256) -> Result<SafeProposal> {
+    let proposal = get_safe_proposal(safe_tx_hash).await?;
+    let network = ethui_networks::get_network(proposal.chain_id).await?;
+
+    Ok(safe::sign(&network, proposal).await?)
+}
+
+/// Adds a signature collected from an owner outside of ethui
+#[tauri::command]
+pub async fn rpc_safe_add_signature(
+    safe_tx_hash: B256,
+    owner: Address,
+    signature: Bytes,
+) -> Result<SafeProposal> {
+    let proposal = get_safe_proposal(safe_tx_hash).await?;
+
+    Ok(safe::add_signature(proposal, owner, signature).await?)
+}
+
+#[tauri::command]
+pub async fn rpc_safe_execute(safe_tx_hash: B256) -> Result<B256> {
+    let proposal = get_safe_proposal(safe_tx_hash).await?;
+    let network = ethui_networks::get_network(proposal.chain_id).await?;
+    let pending = safe::execute(&network, proposal).await?;
+
+    Ok(*pending.tx_hash())
+}
+
+/// Overrides the threshold of a Safe on a dev network (e.g. an anvil fork)
+#[tauri::command]
+pub async fn rpc_safe_override_threshold(
+    chain_id: u64,
+    safe: Address,
+    threshold: u64,
+) -> Result<()> {
+    let network = ethui_networks::get_network(chain_id).await?;
+
+    Ok(safe::override_threshold(&network, safe, threshold).await?)
+}
+
+/// Transactions from `from` that are still waiting to be mined
+#[tauri::command]
+pub async fn rpc_pending_txs(
+    chain_id: u64,
+    dedup_id: u64,
+    from: Address,
+) -> Result<Vec<PendingTx>> {
+    let network_id = NetworkId::from(chain_id, dedup_id);
+
+    Ok(ethui_db::get().get_pending_txs(network_id, from).await?)
+}
+
+/// Resends a pending transaction with higher fees, going through the review dialog again
+#[tauri::command]
+pub async fn rpc_speed_up(hash: B256) -> Result<B256> {
+    replace(hash, Replacement::SpeedUp).await
+}
+
+/// Replaces a pending transaction with a 0-value self-send, using up its nonce
+#[tauri::command]
+pub async fn rpc_cancel(hash: B256) -> Result<B256> {
+    replace(hash, Replacement::Cancel).await
+}
+
+async fn replace(hash: B256, kind: Replacement) -> Result<B256> {
+    let tx = ethui_db::get()
+        .get_pending_tx(hash)
+        .await?
+        .ok_or_else(|| eyre!("unknown transaction {hash}"))?;
+    let (network, request) = nonces::replacement(&tx, kind).await?;
+
+    let mut method = methods::SendTransaction {
+        network,
+        from: tx.from,
+        request,
+        replaces: Some(hash),
+    };
+    let pending = method.finish().await?;
+
+    Ok(*pending.tx_hash())
+}
+
+async fn get_safe_proposal(safe_tx_hash: B256) -> color_eyre::Result<SafeProposal> {
+    ethui_db::get()
+        .get_safe_proposal(safe_tx_hash)
+        .await?
+        .ok_or_else(|| eyre!("unknown Safe transaction {safe_tx_hash}"))
+}
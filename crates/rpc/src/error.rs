@@ -46,6 +46,9 @@ pub enum Error {
     #[error("cannot simulate transaction")]
     CannotSimulate,
 
+    #[error("failed to sign transaction")]
+    TxNotSigned,
+
     #[error("RPC error: {0}")]
     Rpc(i64),
 
@@ -70,6 +73,9 @@ pub enum Error {
     #[error("The Provider is not connected to the requested chain")]
     NetworkInvalid,
 
+    #[error("Provided chainId {got} must match the active chainId {expected}")]
+    TypedDataChainIdMismatch { expected: u64, got: U256 },
+
     #[error("Asset type {0} not supported")]
     TypeInvalid(String),
 
@@ -86,12 +92,55 @@ pub enum Error {
 
     #[error("Invalid params for JSON-RPC method")]
     InvalidParams,
+
+    #[error("The requested account has not been authorized by the user")]
+    Unauthorized,
+
+    #[error("Unsupported non-optional capability: {0}")]
+    UnsupportedCapability(String),
+
+    #[error("Unsupported chain id: {0}")]
+    UnsupportedChainId(u64),
+
+    #[error("Duplicate batch id: {0}")]
+    DuplicateBatchId(String),
+
+    #[error("Unknown batch id: {0}")]
+    UnknownBatchId(String),
+
+    #[error("The wallet does not support atomic execution of this batch")]
+    AtomicityNotSupported,
+
+    #[error("Impersonated accounts can only send transactions on anvil networks")]
+    ImpersonationUnavailable,
+
+    #[error("Safe transaction {0} is awaiting more owner signatures")]
+    SafeTxPending(B256),
+
+    #[error("User operation {0} was submitted, but not included in time")]
+    UserOpPending(B256),
+
+    #[error("execution reverted{}", .reason.as_ref().map(|r| format!(": {r}")).unwrap_or_default())]
+    ExecutionReverted {
+        data: Bytes,
+        reason: Option<ethui_simulator::RevertReason>,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
 
 impl From<Error> for jsonrpc_core::Error {
     fn from(value: Error) -> Self {
+        let data = match value {
+            Error::ExecutionReverted {
+                ref data,
+                ref reason,
+            } => Some(revert_data(data, reason.as_ref())),
+            Error::SafeTxPending(hash) => Some(json!({ "safeTxHash": hash })),
+            Error::UserOpPending(hash) => Some(json!({ "userOpHash": hash })),
+            _ => None,
+        };
+
         let code = match value {
             Error::TxDialogRejected | Error::SignatureRejected | Error::UserRejectedDialog => {
                 ErrorCode::ServerError(4001)
@@ -104,9 +153,18 @@ impl From<Error> for jsonrpc_core::Error {
             | Error::DecimalsInvalid
             | Error::TokenInvalid
             | Error::SymbolMissing
-            | Error::SymbolInvalid => ErrorCode::InvalidParams,
-            Error::WalletNotFound(..) => ErrorCode::ServerError(4100),
+            | Error::SymbolInvalid
+            | Error::TypedDataChainIdMismatch { .. } => ErrorCode::InvalidParams,
+            Error::WalletNotFound(..) | Error::Unauthorized => ErrorCode::ServerError(4100),
             Error::NetworkInvalid => ErrorCode::ServerError(4901),
+            // https://eips.ethereum.org/EIPS/eip-5792#error-codes
+            Error::UnsupportedCapability(..) => ErrorCode::ServerError(5700),
+            Error::UnsupportedChainId(..) => ErrorCode::ServerError(5710),
+            Error::DuplicateBatchId(..) => ErrorCode::ServerError(5720),
+            Error::UnknownBatchId(..) => ErrorCode::ServerError(5730),
+            Error::AtomicityNotSupported => ErrorCode::ServerError(5760),
+            // https://geth.ethereum.org/docs/interacting-with-geth/rpc/objects#error-codes
+            Error::ExecutionReverted { .. } => ErrorCode::ServerError(3),
             // https://github.com/MetaMask/metamask-mobile/blob/5fe6aceffcf4c80ed1f3530282640aebcd201935/app/core/RPCMethods/wallet_switchEthereumChain.js#L88C11-L88C15
             Error::Ethui(ref e) => {
                 if e.to_string().to_lowercase().contains("invalid chain id") {
@@ -120,7 +178,7 @@ impl From<Error> for jsonrpc_core::Error {
 
         Self {
             code,
-            data: None,
+            data,
             message: value.to_string(),
         }
     }
@@ -147,3 +205,91 @@ pub(crate) fn alloy_to_jsonrpc_error(e: RpcError<TransportErrorKind>) -> jsonrpc
         jsonrpc_core::Error::internal_error()
     }
 }
+
+/// Decodes the revert data of a failed `eth_call` or `eth_estimateGas`, which nodes only return as
+/// hex. The original data is kept under `data.data`, where dapps look for it
+pub(crate) async fn with_revert_reason(
+    mut err: jsonrpc_core::Error,
+    network: &Network,
+    params: &jsonrpc_core::Params,
+) -> jsonrpc_core::Error {
+    let Some(data) = err
+        .data
+        .as_ref()
+        .and_then(|d| d.as_str())
+        .and_then(|d| Bytes::from_str(d).ok())
+    else {
+        return err;
+    };
+
+    let chain_id = network.chain_id();
+    let to = match params {
+        jsonrpc_core::Params::Array(params) => params
+            .first()
+            .and_then(|call| serde_json::from_value::<Address>(call["to"].clone()).ok()),
+        _ => None,
+    };
+    let contract = match to {
+        Some(to) => Some((
+            to,
+            crate::utils::get_code(to, chain_id).await.ok().flatten(),
+        )),
+        None => None,
+    };
+
+    let reason = ethui_simulator::decode_revert(&data, chain_id, contract).await;
+
+    if err.message == "execution reverted"
+        && let Some(ref reason) = reason
+    {
+        err.message = format!("execution reverted: {reason}");
+    }
+    err.data = Some(revert_data(&data, reason.as_ref()));
+
+    err
+}
+
+/// The data of every revert error, whether it comes from the node or a simulation. The raw revert
+/// data is always there, with the decoded reason alongside it when there is one
+fn revert_data(data: &Bytes, reason: Option<&ethui_simulator::RevertReason>) -> Json {
+    match reason {
+        Some(reason) => json!({
+            "data": data,
+            "message": reason.to_string(),
+            "reason": reason,
+        }),
+        None => json!({ "data": data }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undecoded_reverts_keep_the_data_object() {
+        let err: jsonrpc_core::Error = Error::ExecutionReverted {
+            data: Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]),
+            reason: None,
+        }
+        .into();
+
+        assert_eq!(err.data, Some(json!({ "data": "0xdeadbeef" })));
+    }
+
+    #[test]
+    fn decoded_reverts_add_the_reason() {
+        let err: jsonrpc_core::Error = Error::ExecutionReverted {
+            data: Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]),
+            reason: Some(ethui_simulator::RevertReason::Error {
+                message: "nope".into(),
+            }),
+        }
+        .into();
+
+        let data = err.data.unwrap();
+        assert_eq!(data["data"], "0xdeadbeef");
+        assert_eq!(data["message"], "nope");
+        assert_eq!(data["reason"]["kind"], "error");
+    }
+}
@@ -0,0 +1,284 @@
+//! Request inspector: records every call handled on behalf of a peer, so that it's possible to
+//! tell what a dapp actually asked the wallet
+
+use std::{
+    collections::VecDeque,
+    sync::{
+        LazyLock, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use ethui_broadcast::InternalMsg;
+use ethui_settings::{SettingsActorExt as _, settings};
+use ethui_types::{RpcRecord, prelude::*};
+use jsonrpc_core::{Call, Output};
+
+use crate::Handler;
+
+/// How many records are kept in memory
+const CAPACITY: usize = 1000;
+
+/// How many records are kept in the database, when persistence is enabled
+const MAX_PERSISTED: u64 = 50_000;
+
+const REDACTED: &str = "<redacted>";
+
+/// Methods whose results are signatures, and where the signing account sits among their params,
+/// in the order their handlers read them. Every other param carries what's being signed
+const SIGNING_METHODS: &[(&str, Option<usize>)] = &[
+    ("eth_sign", Some(1)),
+    ("personal_sign", Some(1)),
+    ("eth_signTypedData", Some(0)),
+    ("eth_signTypedData_v3", Some(0)),
+    ("eth_signTypedData_v4", Some(0)),
+    ("eth_signTransaction", None),
+];
+
+/// Methods whose params carry signed payloads
+const SIGNED_PARAMS_METHODS: &[&str] = &["eth_sendRawTransaction"];
+
+static RECORDS: LazyLock<Mutex<VecDeque<RpcRecord>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::with_capacity(CAPACITY)));
+
+/// Mirrors of the `redact_rpc_history` and `persist_rpc_history` settings, so that recording a
+/// call doesn't need to fetch every setting
+static REDACT: AtomicBool = AtomicBool::new(true);
+static PERSIST: AtomicBool = AtomicBool::new(false);
+
+/// Loads the inspector's settings, and keeps them up to date
+pub(crate) async fn init() {
+    load_settings().await;
+
+    tokio::spawn(async {
+        let mut rx = ethui_broadcast::subscribe_internal().await;
+
+        loop {
+            if let Ok(InternalMsg::SettingsUpdated) = rx.recv().await {
+                load_settings().await;
+            }
+        }
+    });
+}
+
+async fn load_settings() {
+    match settings().get_all().await {
+        Ok(settings) => {
+            REDACT.store(settings.redact_rpc_history, Ordering::Relaxed);
+            PERSIST.store(settings.persist_rpc_history, Ordering::Relaxed);
+        }
+        Err(e) => warn!("failed to load inspector settings: {e}"),
+    }
+}
+
+impl Handler {
+    /// Handles a single call, recording it in the inspector
+    pub(crate) async fn handle_call(&self, call: Call) -> Option<Output> {
+        let (method, params) = match call {
+            Call::MethodCall(ref c) => (c.method.clone(), json!(c.params)),
+            Call::Notification(ref n) => (n.method.clone(), json!(n.params)),
+            Call::Invalid { .. } => return self.io.handle_call(call, self.ctx.clone()).await,
+        };
+
+        let network_id = self.ctx.network().await.id();
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let start = Instant::now();
+
+        let output = self.io.handle_call(call, self.ctx.clone()).await;
+
+        let (result, error) = match output {
+            Some(Output::Success(ref s)) => (Some(s.result.clone()), None),
+            Some(Output::Failure(ref f)) => (None, Some(json!(f.error))),
+            None => (None, None),
+        };
+
+        record(RpcRecord {
+            timestamp,
+            domain: self.ctx.domain.clone(),
+            network_id,
+            method,
+            params,
+            result,
+            error,
+            latency_ms: start.elapsed().as_millis() as u64,
+        })
+        .await;
+
+        output
+    }
+}
+
+async fn record(mut record: RpcRecord) {
+    if REDACT.load(Ordering::Relaxed) {
+        redact(&mut record);
+    }
+
+    {
+        let mut records = RECORDS.lock().unwrap();
+        if records.len() == CAPACITY {
+            records.pop_front();
+        }
+        records.push_back(record.clone());
+    }
+
+    if PERSIST.load(Ordering::Relaxed) {
+        tokio::spawn(async move {
+            if let Err(e) = ethui_db::get()
+                .save_rpc_record(&record, MAX_PERSISTED)
+                .await
+            {
+                warn!("failed to persist rpc record: {e}");
+            }
+        });
+    }
+}
+
+fn redact(record: &mut RpcRecord) {
+    let method = record.method.as_str();
+
+    if let Some((_, account)) = SIGNING_METHODS.iter().find(|(m, _)| *m == method) {
+        if record.result.is_some() {
+            record.result = Some(REDACTED.into());
+        }
+
+        match record.params.as_array_mut() {
+            Some(params) => params
+                .iter_mut()
+                .enumerate()
+                .filter(|(i, _)| Some(*i) != *account)
+                .for_each(|(_, p)| *p = REDACTED.into()),
+            None => record.params = REDACTED.into(),
+        }
+    }
+
+    if SIGNED_PARAMS_METHODS.contains(&method) {
+        record.params = json!([REDACTED]);
+    }
+}
+
+/// Recorded calls, most recent first
+///
+/// Served from the database when persistence is enabled, since it outlives the in-memory buffer
+pub(crate) async fn get(
+    domain: Option<&str>,
+    method: Option<&str>,
+    limit: usize,
+) -> color_eyre::Result<Vec<RpcRecord>> {
+    if PERSIST.load(Ordering::Relaxed) {
+        return ethui_db::get()
+            .get_rpc_records(domain, method, limit as u64)
+            .await;
+    }
+
+    Ok(RECORDS
+        .lock()
+        .unwrap()
+        .iter()
+        .rev()
+        .filter(|r| domain.is_none_or(|d| r.domain.as_deref() == Some(d)))
+        .filter(|r| method.is_none_or(|m| r.method == m))
+        .take(limit)
+        .cloned()
+        .collect())
+}
+
+pub(crate) async fn clear() -> color_eyre::Result<()> {
+    RECORDS.lock().unwrap().clear();
+    ethui_db::get().clear_rpc_records().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+    fn record(method: &str, params: Json, result: Json) -> RpcRecord {
+        RpcRecord {
+            timestamp: 0,
+            domain: None,
+            network_id: (31337, 0).into(),
+            method: method.into(),
+            params,
+            result: Some(result),
+            error: None,
+            latency_ms: 0,
+        }
+    }
+
+    #[test]
+    fn redacts_personal_sign() {
+        let mut r = record(
+            "personal_sign",
+            json!(["0x68656c6c6f", ACCOUNT]),
+            json!("0x1234"),
+        );
+        redact(&mut r);
+
+        assert_eq!(r.params, json!([REDACTED, ACCOUNT]));
+        assert_eq!(r.result, Some(json!(REDACTED)));
+    }
+
+    #[test]
+    fn redacts_messages_that_look_like_addresses() {
+        let message = "0x000000000000000000000000000000000000dEaD";
+        let mut r = record("personal_sign", json!([message, ACCOUNT]), json!("0x1234"));
+        redact(&mut r);
+
+        assert_eq!(r.params, json!([REDACTED, ACCOUNT]));
+    }
+
+    #[test]
+    fn redacts_signed_transaction_requests() {
+        let mut r = record(
+            "eth_signTransaction",
+            json!([{ "from": ACCOUNT, "to": ACCOUNT, "value": "0x1" }]),
+            json!("0x02f870"),
+        );
+        redact(&mut r);
+
+        assert_eq!(r.params, json!([REDACTED]));
+        assert_eq!(r.result, Some(json!(REDACTED)));
+    }
+
+    #[test]
+    fn redacts_typed_data() {
+        let typed_data = json!({ "primaryType": "Permit", "message": { "value": "1" } });
+        let mut r = record(
+            "eth_signTypedData_v4",
+            json!([ACCOUNT, typed_data.to_string()]),
+            json!("0x1234"),
+        );
+        redact(&mut r);
+
+        assert_eq!(r.params, json!([ACCOUNT, REDACTED]));
+        assert_eq!(r.result, Some(json!(REDACTED)));
+    }
+
+    #[test]
+    fn redacts_signed_transactions() {
+        let mut r = record(
+            "eth_sendRawTransaction",
+            json!(["0x02f870"]),
+            json!("0xabcd"),
+        );
+        redact(&mut r);
+
+        assert_eq!(r.params, json!([REDACTED]));
+        assert_eq!(r.result, Some(json!("0xabcd")));
+    }
+
+    #[test]
+    fn keeps_other_methods() {
+        let params = json!([{ "to": ACCOUNT, "data": "0x1234" }, "latest"]);
+        let mut r = record("eth_call", params.clone(), json!("0x"));
+        redact(&mut r);
+
+        assert_eq!(r.params, params);
+        assert_eq!(r.result, Some(json!("0x")));
+    }
+}
@@ -1,22 +1,38 @@
+mod batch;
+mod cache;
 pub mod commands;
 mod error;
+mod inspector;
 mod methods;
+mod nonces;
 mod params;
+mod safe;
+mod smart_account;
+mod typed_data;
 mod utils;
 
-use alloy::providers::Provider as _;
+use alloy::primitives::keccak256;
 use ethui_connections::Ctx;
 use ethui_types::prelude::*;
-use ethui_wallets::{WalletControl, Wallets};
 use jsonrpc_core::{MetaIoHandler, Params};
 use serde_json::json;
 
 pub use self::error::{Error, Result};
 use self::{
     methods::Method,
-    params::{Empty, PermissionRequestParams, SwitchChainParams},
+    params::{Empty, PermissionRequestParams, Sha3Params},
 };
 
+pub async fn init() {
+    inspector::init().await;
+    nonces::init().await;
+}
+
+/// Accounts exposed to a connection, as `eth_accounts` would return them
+pub async fn accounts_for(ctx: &Ctx) -> Vec<Address> {
+    utils::exposed_accounts(ctx).await
+}
+
 pub struct Handler {
     io: MetaIoHandler<Ctx>,
     ctx: Ctx,
@@ -24,19 +40,31 @@ pub struct Handler {
 
 impl Handler {
     pub fn new(domain: Option<String>) -> Self {
+        Self::with_ctx(Ctx::new(domain))
+    }
+
+    /// Builds a handler for an existing connection context, sharing its grants
+    pub fn with_ctx(ctx: Ctx) -> Self {
         let mut res = Self {
             io: MetaIoHandler::default(),
-            ctx: Ctx {
-                domain,
-                ..Default::default()
-            },
+            ctx,
         };
         res.add_handlers();
         res
     }
 
+    pub fn ctx(&self) -> &Ctx {
+        &self.ctx
+    }
+
     pub async fn handle(&self, request: jsonrpc_core::Request) -> Option<jsonrpc_core::Response> {
-        self.io.handle_rpc_request(request, self.ctx.clone()).await
+        match request {
+            jsonrpc_core::Request::Batch(calls) => self.handle_batch(calls).await,
+            jsonrpc_core::Request::Single(call) => self
+                .handle_call(call)
+                .await
+                .map(jsonrpc_core::Response::Single),
+        }
     }
 
     /// Names of every RPC method this handler has registered — used to filter
@@ -78,13 +106,7 @@ impl Handler {
             ($name:literal) => {
                 self.io
                     .add_method_with_meta($name, |params: Params, ctx: Ctx| async move {
-                        let provider = ctx.network().await.get_provider();
-
-                        let res: jsonrpc_core::Result<Json> = provider
-                            .raw_request::<_, Json>($name.into(), params)
-                            .await
-                            .map_err(error::alloy_to_jsonrpc_error);
-                        res
+                        cache::request(&ctx.network().await, $name, params).await
                     });
             };
         }
@@ -99,12 +121,23 @@ impl Handler {
         provider_handler!("eth_getStorageAt");
         provider_handler!("eth_getTransactionCount");
         provider_handler!("eth_getCode");
-        provider_handler!("eth_call");
+        self.io
+            .add_method_with_meta("eth_call", |params: Params, ctx: Ctx| async move {
+                if methods::CallWithOverrides::has_overrides(&params) {
+                    let method = methods::CallWithOverrides::build(params, ctx).await?;
+                    return method.run().await.map_err(Into::into);
+                }
+
+                cache::request(&ctx.network().await, "eth_call", params).await
+            });
         provider_handler!("eth_estimateGas");
         provider_handler!("eth_protocolVersion");
         provider_handler!("eth_syncing");
         provider_handler!("eth_mining");
+        provider_handler!("eth_gasPrice");
         provider_handler!("net_version");
+        provider_handler!("net_listening");
+        provider_handler!("net_peerCount");
 
         // history methods
         // delegate directly to provider
@@ -132,31 +165,31 @@ impl Handler {
 
         // handle internally
         self_handler!("eth_accounts", Self::accounts);
-        self_handler!("eth_requestAccounts", Self::accounts);
+        method_handler!("eth_requestAccounts", methods::RequestAccounts);
         self_handler!("eth_chainId", Self::chain_id);
         method_handler!("eth_sendTransaction", methods::SendTransaction);
+        method_handler!("eth_signTransaction", methods::SignTransaction);
         method_handler!("eth_sign", methods::EthSign);
         method_handler!("personal_sign", methods::EthSign);
         method_handler!("eth_signTypedData", methods::EthSignTypedData);
         method_handler!("eth_signTypedData_v4", methods::EthSignTypedData);
-        self_handler!("wallet_requestPermissions", Self::request_permissions);
+        method_handler!("wallet_requestPermissions", methods::RequestPermissions);
         self_handler!("wallet_revokePermissions", Self::revoke_permissions);
         self_handler!("wallet_getPermissions", Self::get_permissions);
         method_handler!("wallet_addEthereumChain", methods::ChainAdd);
         method_handler!("wallet_updateEthereumChain", methods::ChainUpdate);
-        self_handler!("wallet_switchEthereumChain", Self::switch_chain);
+        method_handler!("wallet_switchEthereumChain", methods::SwitchChain);
         method_handler!("wallet_watchAsset", methods::TokenAdd);
+        method_handler!("wallet_sendCalls", methods::SendCalls);
+        method_handler!("wallet_getCallsStatus", methods::GetCallsStatus);
+        method_handler!("wallet_getCapabilities", methods::GetCapabilities);
 
         // metamask
         self_handler!("metamask_getProviderState", Self::metamask_provider_state);
 
-        // not yet implemented
-        self_handler!("web3_clientVersion", Self::unimplemented);
-        self_handler!("web3_sha3", Self::unimplemented);
-        self_handler!("net_listening", Self::unimplemented);
-        self_handler!("net_peerCount", Self::unimplemented);
-        self_handler!("eth_gasPrice", Self::unimplemented);
-        self_handler!("eth_signTransaction", Self::unimplemented);
+        // web3
+        self_handler!("web3_clientVersion", Self::client_version);
+        self_handler!("web3_sha3", Self::sha3);
 
         self_handler!("ethui_getProviderState", Self::ethui_provider_state);
         method_handler!("ethui_getContractAbi", methods::ethui::AbiForContract);
@@ -166,11 +199,8 @@ impl Handler {
         method_handler!("ethui_forgeTestSubmitRun", methods::ethui::ForgeTestTraces);
     }
 
-    async fn accounts(_: Empty, _: Ctx) -> Result<Json> {
-        let wallets = Wallets::read().await;
-        let address = wallets.get_current_wallet().get_current_address().await;
-
-        Ok(json!([address]))
+    async fn accounts(_: Empty, ctx: Ctx) -> Result<Json> {
+        Ok(json!(utils::exposed_accounts(&ctx).await))
     }
 
     async fn chain_id(_: Empty, ctx: Ctx) -> Result<Json> {
@@ -179,61 +209,40 @@ impl Handler {
     }
 
     async fn metamask_provider_state(_: Empty, ctx: Ctx) -> Result<Json> {
-        let wallets = Wallets::read().await;
-
         let network = ctx.network().await;
-        let address = wallets.get_current_wallet().get_current_address().await;
+        let accounts = utils::exposed_accounts(&ctx).await;
 
         Ok(json!({
             "isUnlocked": true,
             "chainId": network.chain_id_hex(),
             "networkVersion": network.chain_id().to_string(),
-            "accounts": [address],
+            "accounts": accounts,
         }))
     }
 
     #[tracing::instrument(skip(request))]
-    async fn request_permissions(request: PermissionRequestParams, mut ctx: Ctx) -> Result<Json> {
-        let ret = ctx.request_permissions(request.into());
-
-        Ok(json!(ret))
-    }
-
-    #[tracing::instrument(skip(request))]
-    async fn revoke_permissions(request: PermissionRequestParams, mut ctx: Ctx) -> Result<Json> {
-        let ret = ctx.revoke_permissions(request.into());
+    async fn revoke_permissions(request: PermissionRequestParams, ctx: Ctx) -> Result<Json> {
+        let ret = ctx.revoke_permissions(request.into()).await?;
 
         Ok(json!(ret))
     }
 
     #[tracing::instrument(skip(_params, ctx))]
     async fn get_permissions(_params: Empty, ctx: Ctx) -> Result<Json> {
-        Ok(json!(ctx.get_permissions()))
+        Ok(json!(ctx.get_permissions().await))
     }
 
-    #[tracing::instrument()]
-    async fn switch_chain(params: SwitchChainParams, mut ctx: Ctx) -> Result<Json> {
-        let new_chain_id = params.chain_id()?;
-
-        // TODO future work
-        // multiple networks with same chain id should display a dialog so user can select which
-        // network to switch to
-        ctx.switch_chain(new_chain_id).await.map_err(Error::Ethui)?;
-
-        Ok(Json::Null)
+    async fn client_version(_: Empty, _: Ctx) -> Result<Json> {
+        Ok(json!(format!("ethui/v{}", env!("CARGO_PKG_VERSION"))))
     }
 
-    async fn unimplemented(_: Empty, _: Ctx) -> Result<Json> {
-        tracing::warn!("unimplemented method called");
-
-        Err(Error::JsonRpc(jsonrpc_core::Error::internal_error()))
+    async fn sha3(params: Sha3Params, _: Ctx) -> Result<Json> {
+        Ok(json!(keccak256(&params.0)))
     }
 
     async fn ethui_provider_state(_: Empty, ctx: Ctx) -> Result<Json> {
-        let wallets = Wallets::read().await;
-
         let network = ctx.network().await;
-        let address = wallets.get_current_wallet().get_current_address().await;
+        let accounts = utils::exposed_accounts(&ctx).await;
 
         Ok(json!({
             "ethui": {
@@ -242,7 +251,7 @@ impl Handler {
             "network": {
                 "chainId": network.chain_id_hex(),
             },
-            "accounts": [address],
+            "accounts": accounts,
         }))
     }
 }
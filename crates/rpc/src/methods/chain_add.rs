@@ -96,6 +96,7 @@ impl TryFrom<ChainAdd> for NewNetworkParams {
             currency: params.native_currency.symbol,
             decimals: params.native_currency.decimals as u32,
             is_stack: false,
+            rpc_cache: false,
         })
     }
 }
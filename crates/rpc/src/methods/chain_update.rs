@@ -80,7 +80,7 @@ impl ChainUpdate {
         let chain_name = self.chain_name.clone();
 
         let chain_id = TryInto::<u64>::try_into(self.chain_id).map_err(|_| Error::ParseError)?;
-        let new_network_params = NewNetworkParams {
+        let mut new_network_params = NewNetworkParams {
             chain_id,
             name: chain_name.clone(),
             explorer_url: self
@@ -103,14 +103,18 @@ impl ChainUpdate {
             currency: self.native_currency.symbol.clone(),
             decimals: self.native_currency.decimals as u32,
             is_stack: false,
+            rpc_cache: false,
         };
 
-        let dedup_id = networks()
-            .get(chain_name)
-            .await?
+        let existing = networks().get(chain_name).await?;
+        let dedup_id = existing
+            .as_ref()
             .map(|network| network.id().dedup_id())
             .unwrap_or(0);
 
+        // the cache is a local preference, which the dapp has no say in
+        new_network_params.rpc_cache = existing.is_some_and(|network| network.rpc_cache);
+
         Ok((
             new_network_params.clone().into_network(dedup_id),
             new_network_params,
@@ -1,20 +1,28 @@
 pub(crate) mod chain_add;
 mod chain_update;
 pub(crate) mod ethui;
+pub(crate) mod request_permissions;
 pub(crate) mod send_call;
 pub(crate) mod send_transaction;
 pub(crate) mod sign_message;
+pub(crate) mod sign_transaction;
+mod switch_chain;
 pub(crate) mod token_add;
+pub(crate) mod wallet_calls;
 
 pub(crate) use chain_add::ChainAdd;
 pub(crate) use chain_update::ChainUpdate;
 use ethui_connections::Ctx;
 use ethui_types::Json;
 use jsonrpc_core::Params;
-pub(crate) use send_call::{CallParams, SendCall};
+pub(crate) use request_permissions::{RequestAccounts, RequestPermissions};
+pub(crate) use send_call::{CallParams, CallWithOverrides, SendCall};
 pub(crate) use send_transaction::SendTransaction;
 pub(crate) use sign_message::{EthSign, EthSignTypedData};
+pub(crate) use sign_transaction::SignTransaction;
+pub(crate) use switch_chain::SwitchChain;
 pub(crate) use token_add::TokenAdd;
+pub(crate) use wallet_calls::{GetCallsStatus, GetCapabilities, SendCalls};
 
 use crate::Result;
 
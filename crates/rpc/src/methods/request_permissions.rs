@@ -0,0 +1,97 @@
+use ethui_connections::{Ctx, permissions::PermissionRequest};
+use ethui_dialogs::{Dialog, DialogMsg};
+use ethui_types::prelude::*;
+use ethui_wallets::WalletControl as _;
+use jsonrpc_core::Params as RpcParams;
+
+use crate::{Error, Result, methods::Method, params::PermissionRequestParams, utils};
+
+/// Handler for wallet_requestPermissions
+///
+/// Always asks the user, even if the domain already holds some of the requested permissions
+pub(crate) struct RequestPermissions {
+    ctx: Ctx,
+    request: PermissionRequest,
+}
+
+impl Method for RequestPermissions {
+    async fn build(params: RpcParams, ctx: Ctx) -> Result<Self> {
+        let request = PermissionRequestParams::try_from(params)?.into();
+
+        Ok(Self { ctx, request })
+    }
+
+    async fn run(self) -> Result<Json> {
+        let accounts = if self.request.includes_accounts() {
+            approve_accounts(&self.ctx).await?
+        } else {
+            vec![]
+        };
+
+        let ret = self.ctx.request_permissions(self.request, accounts).await?;
+
+        Ok(json!(ret))
+    }
+}
+
+/// Handler for eth_requestAccounts
+///
+/// Only asks the user the first time a domain connects. Afterwards, it returns whatever accounts
+/// were previously exposed to it
+pub(crate) struct RequestAccounts {
+    ctx: Ctx,
+}
+
+impl Method for RequestAccounts {
+    async fn build(_params: RpcParams, ctx: Ctx) -> Result<Self> {
+        Ok(Self { ctx })
+    }
+
+    async fn run(self) -> Result<Json> {
+        if self.ctx.exposed_accounts().await.is_none() {
+            let accounts = approve_accounts(&self.ctx).await?;
+            self.ctx
+                .request_permissions(PermissionRequest::accounts(), accounts)
+                .await?;
+        }
+
+        Ok(json!(utils::exposed_accounts(&self.ctx).await))
+    }
+}
+
+/// Opens the connection dialog, letting the user pick which accounts to expose to the domain
+async fn approve_accounts(ctx: &Ctx) -> Result<Vec<Address>> {
+    let current = ethui_wallets::get_current_wallet()
+        .await
+        .get_current_address()
+        .await;
+
+    let dialog = Dialog::new(
+        "connection-request",
+        json!({ "domain": ctx.domain, "current": current }),
+    );
+    dialog.open().await?;
+
+    let Some(DialogMsg::Data(msg)) = dialog.recv().await else {
+        return Err(Error::UserRejectedDialog);
+    };
+
+    if msg["event"].as_str() != Some("accept") {
+        return Err(Error::UserRejectedDialog);
+    }
+
+    let accounts: Vec<Address> = serde_json::from_value(msg["accounts"].clone())?;
+
+    // only addresses from known wallets can be exposed
+    for address in accounts.iter() {
+        if ethui_wallets::find_wallet(*address).await.is_none() {
+            return Err(Error::WalletNotFound(*address));
+        }
+    }
+
+    if accounts.is_empty() {
+        return Err(Error::UserRejectedDialog);
+    }
+
+    Ok(accounts)
+}
@@ -1,11 +1,15 @@
 use alloy::{
-    network::TransactionBuilder as _, providers::Provider as _, rpc::types::TransactionRequest,
+    eips::{BlockId, BlockNumberOrTag, eip7702::SignedAuthorization},
+    network::{TransactionBuilder as _, TransactionBuilder7702 as _},
+    providers::Provider as _,
+    rpc::types::TransactionRequest,
 };
+use ethui_connections::Ctx;
 use ethui_types::prelude::*;
-use ethui_wallets::WalletControl;
+use jsonrpc_core::Params;
 use serde::Deserialize;
 
-use crate::Result;
+use crate::{Error, Result, methods::Method, utils};
 
 /// Raw call/transaction parameters from RPC
 #[derive(Debug, Deserialize)]
@@ -16,20 +20,27 @@ pub(crate) struct CallParams {
     value: Option<U256>,
     data: Option<Bytes>,
     gas: Option<U256>,
+    /// EIP-7702 authorizations, making this a type-4 transaction
+    authorization_list: Option<Vec<SignedAuthorization>>,
 }
 
 impl CallParams {
-    /// Convert to TransactionRequest, resolving `from` address from current wallet if not provided
-    pub(crate) async fn into_request_with_from(self) -> Result<(Address, TransactionRequest)> {
+    /// Convert to TransactionRequest, resolving `from` to the connection's account if not provided
+    pub(crate) async fn into_request_with_from(
+        self,
+        ctx: &Ctx,
+    ) -> Result<(Address, TransactionRequest)> {
         let mut request: TransactionRequest = self.into();
 
-        let from = if let Some(addr) = request.from {
-            addr
-        } else {
-            let wallet = ethui_wallets::get_current_wallet().await;
-            let addr = wallet.get_current_address().await;
-            request.set_from(addr);
-            addr
+        let from = match request.from {
+            Some(addr) => addr,
+            None => {
+                let addr = utils::default_account(ctx)
+                    .await
+                    .ok_or(Error::Unauthorized)?;
+                request.set_from(addr);
+                addr
+            }
         };
 
         Ok((from, request))
@@ -55,6 +66,9 @@ impl From<CallParams> for TransactionRequest {
         if let Some(gas) = params.gas {
             request.set_gas_limit(gas.saturating_to::<u64>());
         }
+        if let Some(authorization_list) = params.authorization_list {
+            request.set_authorization_list(authorization_list);
+        }
 
         request
     }
@@ -81,3 +95,89 @@ impl SendCall {
         Ok(provider.call(self.request.clone()).await?)
     }
 }
+
+/// eth_call with state or block overrides, which is executed locally since not every node supports
+/// them
+#[derive(Debug)]
+pub(crate) struct CallWithOverrides {
+    network: Network,
+    request: ethui_simulator::Request,
+    block: Option<u64>,
+}
+
+impl CallWithOverrides {
+    /// Whether the call's params carry overrides. Params format: [call, block, state, block
+    /// overrides]
+    pub(crate) fn has_overrides(params: &Params) -> bool {
+        match params {
+            Params::Array(params) => params.iter().skip(2).any(|p| !p.is_null()),
+            _ => false,
+        }
+    }
+}
+
+impl Method for CallWithOverrides {
+    async fn build(params: Params, ctx: Ctx) -> Result<Self> {
+        let params: Vec<Json> = params.parse()?;
+        let param = |i: usize| params.get(i).cloned().unwrap_or_default();
+
+        // calls don't act on anyone's behalf, so they need no access to `from`
+        let request: TransactionRequest = serde_json::from_value::<CallParams>(param(0))?.into();
+        let from = match request.from {
+            Some(from) => from,
+            None => utils::default_account(&ctx).await.unwrap_or_default(),
+        };
+
+        let network = ctx.network().await;
+        let block = serde_json::from_value::<Option<BlockId>>(param(1))
+            .map_err(|_| Error::InvalidParams)?;
+
+        // the pending block is taken to be the latest one. Any other tag, or an EIP-1898 hash, is
+        // resolved to its number
+        let block = match block {
+            None | Some(BlockId::Number(BlockNumberOrTag::Latest | BlockNumberOrTag::Pending)) => {
+                None
+            }
+            Some(BlockId::Number(BlockNumberOrTag::Number(number))) => Some(number),
+            Some(block) => Some(
+                network
+                    .get_alloy_provider()
+                    .await?
+                    .get_block(block)
+                    .await?
+                    .ok_or(Error::InvalidParams)?
+                    .header
+                    .number,
+            ),
+        };
+
+        Ok(Self {
+            network,
+            request: ethui_simulator::Request {
+                from,
+                to: request.to.and_then(|to| to.to().copied()),
+                data: request.input.into_input(),
+                gas_limit: request.gas.unwrap_or_default(),
+                value: request.value,
+                state_overrides: serde_json::from_value(param(2))?,
+                block_overrides: serde_json::from_value(param(3))?,
+            },
+            block,
+        })
+    }
+
+    async fn run(self) -> Result<Json> {
+        let result =
+            ethui_simulator::call(self.request, self.network.http_url.to_string(), self.block)
+                .await?;
+
+        match (result.success, result.return_data) {
+            (true, data) => Ok(json!(data.unwrap_or_default())),
+            (false, Some(data)) => Err(Error::ExecutionReverted {
+                data,
+                reason: result.revert_reason,
+            }),
+            (false, None) => Err(eyre!("execution halted").into()),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use std::str::FromStr;
 
 use alloy::{
+    eips::eip2718::Encodable2718 as _,
     network::{Ethereum, EthereumWallet, NetworkWallet, TransactionBuilder as _},
     providers::{DynProvider, PendingTransactionBuilder, Provider, ProviderBuilder},
     rpc::types::TransactionRequest,
@@ -9,17 +10,22 @@ use ethui_connections::Ctx;
 use ethui_dialogs::{Dialog, DialogMsg};
 use ethui_settings::{SettingsActorExt as _, settings};
 use ethui_types::prelude::*;
-use ethui_wallets::{WalletControl, WalletType, Wallets};
+use ethui_wallets::{Signer, SmartAccountWallet, Wallet, WalletControl, WalletType, Wallets};
 use jsonrpc_core::Params as RpcParams;
 
 use super::send_call::CallParams;
-use crate::{Error, Result, methods::Method, params::extract_single_param};
+use crate::{
+    Error, Result, methods::Method, nonces, params::extract_single_param, safe, smart_account,
+    utils,
+};
 
 /// Orchestrates the signing of a transaction
 pub(crate) struct SendTransaction {
     pub(crate) network: Network,
     pub(crate) from: Address,
     pub(crate) request: TransactionRequest,
+    /// a pending transaction this one speeds up or cancels
+    pub(crate) replaces: Option<B256>,
 }
 
 /// Holds resolved wallet information during transaction processing
@@ -27,29 +33,37 @@ struct ResolvedWallet {
     name: String,
     path: String,
     wallet_type: WalletType,
+    /// the user operation shown in the review dialog, for smart accounts. It's the one sent, so
+    /// that what's reviewed is what gets signed
+    user_op: Option<smart_account::UserOperation>,
 }
 
 impl Method for SendTransaction {
     async fn build(params: RpcParams, ctx: Ctx) -> Result<Self> {
         let params: CallParams = serde_json::from_value(extract_single_param(params))?;
-        let (from, request) = params.into_request_with_from().await?;
+        let (from, request) = params.into_request_with_from(&ctx).await?;
+        utils::authorize(&ctx, from).await?;
         let network = ctx.network().await;
 
         Ok(SendTransaction {
             network,
             from,
             request,
+            replaces: None,
         })
     }
 
     async fn run(mut self) -> Result<Json> {
+        // a Safe transaction still waiting for owners outside of ethui has no transaction hash to
+        // return yet, so it fails with the SafeTx hash in the error's data instead
         let result = self.estimate_gas().await.finish().await?;
+
         Ok(format!("0x{:x}", result.tx_hash()).into())
     }
 }
 
 impl SendTransaction {
-    async fn estimate_gas(&mut self) -> &mut SendTransaction {
+    pub(crate) async fn estimate_gas(&mut self) -> &mut SendTransaction {
         // Honor an explicit gas limit from the caller as-is.
         if self.request.gas.is_some() {
             return self;
@@ -70,8 +84,33 @@ impl SendTransaction {
         self
     }
 
-    async fn finish(&mut self) -> Result<PendingTransactionBuilder<Ethereum>> {
+    pub(crate) async fn finish(&mut self) -> Result<PendingTransactionBuilder<Ethereum>> {
+        let (resolved, _dialog) = self.review().await?;
+        self.send(&resolved).await
+    }
+
+    /// Same review flow as `finish`, but returns the signed, RLP-encoded transaction instead of
+    /// broadcasting it
+    pub(crate) async fn finish_signed(&mut self) -> Result<Bytes> {
+        let (resolved, _dialog) = self.review().await?;
+        self.sign(&resolved).await
+    }
+
+    /// Sends the transaction without going through the review dialog
+    ///
+    /// Only meant for callers that already had the user review the transaction in some other way
+    /// (e.g. as part of a `wallet_sendCalls` batch)
+    pub(crate) async fn finish_reviewed(&mut self) -> Result<PendingTransactionBuilder<Ethereum>> {
         let resolved = self.resolve_wallet().await?;
+        self.send(&resolved).await
+    }
+
+    /// Resolves the sender's wallet, and walks the user through the review dialog if needed
+    ///
+    /// The dialog handle is returned so that it's kept open until the caller is done with the
+    /// signer (e.g. while waiting for a ledger confirmation)
+    async fn review(&mut self) -> Result<(ResolvedWallet, Option<Dialog>)> {
+        let mut resolved = self.resolve_wallet().await?;
 
         let wallet_is_dev = {
             let wallets = Wallets::read().await;
@@ -91,9 +130,10 @@ impl SendTransaction {
 
         // skip the dialog if both network & wallet allow for it, and if fast_mode is enabled
         if skip {
-            self.send(&resolved).await
+            Ok((resolved, None))
         } else {
-            self.dialog_and_send(&resolved).await
+            let dialog = self.dialog(&mut resolved).await?;
+            Ok((resolved, Some(dialog)))
         }
     }
 
@@ -106,16 +146,30 @@ impl SendTransaction {
             name: wallet.name(),
             path,
             wallet_type: (&wallet).into(),
+            user_op: None,
         })
     }
 
-    async fn dialog_and_send(
-        &mut self,
-        resolved: &ResolvedWallet,
-    ) -> Result<PendingTransactionBuilder<Ethereum>> {
+    async fn dialog(&mut self, resolved: &mut ResolvedWallet) -> Result<Dialog> {
         let mut params = serde_json::to_value(&self.request).unwrap();
         params["chainId"] = self.network.chain_id().into();
         params["walletType"] = resolved.wallet_type.to_string().into();
+        if let Some(replaces) = self.replaces {
+            params["replaces"] = format!("0x{replaces:x}").into();
+        }
+
+        // the accounts being delegated aren't part of an authorization, only its signature
+        if let Some(authorizations) = self.request.authorization_list.as_ref() {
+            for (i, authorization) in authorizations.iter().enumerate() {
+                params["authorizationList"][i]["authority"] =
+                    json!(authorization.recover_authority().ok());
+            }
+        }
+
+        if resolved.wallet_type == WalletType::SmartAccount {
+            self.prepare_user_op(resolved).await?;
+            params["userOperation"] = serde_json::to_value(&resolved.user_op)?;
+        }
 
         let dialog = Dialog::new("tx-review", params);
         dialog.open().await?;
@@ -130,6 +184,10 @@ impl SendTransaction {
                     Some("accept") => break,
                     Some("update") => {
                         self.update(msg);
+                        // the reviewed operation no longer matches the edited request
+                        if resolved.wallet_type == WalletType::SmartAccount {
+                            self.prepare_user_op(resolved).await?;
+                        }
                         self.simulate(&dialog, resolved).await?
                     }
                     // TODO: what's the appropriate error to return here?
@@ -147,9 +205,7 @@ impl SendTransaction {
             dialog.send("check-ledger", None).await?;
         }
 
-        let tx = self.send(resolved).await?;
-
-        Ok(tx)
+        Ok(dialog)
     }
 
     fn update(&mut self, data: serde_json::Value) {
@@ -181,14 +237,151 @@ impl SendTransaction {
         &mut self,
         resolved: &ResolvedWallet,
     ) -> Result<PendingTransactionBuilder<Ethereum>> {
+        if resolved.wallet_type == WalletType::Impersonator {
+            return self.send_impersonated().await;
+        }
+
+        if resolved.wallet_type == WalletType::Safe {
+            return self.send_safe().await;
+        }
+
+        if resolved.wallet_type == WalletType::SmartAccount {
+            return self.send_user_operation(resolved).await;
+        }
+
         let provider = self.build_provider(resolved).await?;
 
+        // held until the transaction is tracked, so that the next send from the same account
+        // picks the following nonce
+        let _lock = nonces::lock(self.network.id(), self.from).await;
+        if self.request.nonce.is_none() {
+            let nonce = nonces::next(&self.network, self.from).await?;
+            self.request.set_nonce(nonce);
+        }
+
         ethui_broadcast::transaction_submitted(self.network.chain_id()).await;
 
         let pending = provider.send_transaction(self.request.clone()).await?;
+
+        // the transaction is out at this point, so failing to track it isn't an error
+        if let Err(e) = nonces::track(
+            &self.network,
+            self.from,
+            &self.request,
+            *pending.tx_hash(),
+            self.replaces,
+        )
+        .await
+        {
+            warn!("failed to track transaction {}: {e}", pending.tx_hash());
+        }
+
         Ok(pending)
     }
 
+    /// Impersonator wallets hold no keys, so the transaction is submitted unsigned, with the anvil
+    /// node told to accept it on behalf of the sender
+    async fn send_impersonated(&mut self) -> Result<PendingTransactionBuilder<Ethereum>> {
+        let provider = self.network.get_alloy_provider().await?;
+
+        // held until impersonation stops, so that concurrent sends from the same account don't stop
+        // it from under each other
+        let _lock = nonces::lock(self.network.id(), self.from).await;
+        self.request.set_from(self.from);
+
+        send_impersonated_with(&provider, self.network.chain_id(), &self.request).await
+    }
+
+    /// Proposes the transaction to the Safe, executing it right away if enough of its owners are
+    /// local wallets
+    async fn send_safe(&self) -> Result<PendingTransactionBuilder<Ethereum>> {
+        let proposal = safe::propose(&self.network, self.from, &self.request).await?;
+
+        if proposal.signatures.len() < safe::threshold(&self.network, self.from).await? {
+            return Err(Error::SafeTxPending(proposal.safe_tx_hash));
+        }
+
+        ethui_broadcast::transaction_submitted(self.network.chain_id()).await;
+        Ok(safe::execute(&self.network, proposal).await?)
+    }
+
+    /// Submits the transaction as a user operation through the smart account's bundler. Dapps
+    /// expect a transaction hash, so this waits a while for the operation to be included, and
+    /// fails with the operation's hash in the error's data if it isn't
+    async fn send_user_operation(
+        &self,
+        resolved: &ResolvedWallet,
+    ) -> Result<PendingTransactionBuilder<Ethereum>> {
+        let wallet = self.smart_account(resolved).await?;
+
+        // operations that skipped the review dialog haven't been prepared yet
+        let op = match resolved.user_op.clone() {
+            Some(op) => op,
+            None => smart_account::prepare(&self.network, &wallet, &self.request).await?,
+        };
+        let op = smart_account::sign(&self.network, &wallet, op).await?;
+
+        ethui_broadcast::transaction_submitted(self.network.chain_id()).await;
+        let op_hash = smart_account::submit(&wallet, op).await?;
+
+        let tx_hash = smart_account::wait(&wallet, op_hash)
+            .await?
+            .ok_or(Error::UserOpPending(op_hash))?;
+
+        Ok(PendingTransactionBuilder::new(
+            self.network.get_provider(),
+            tx_hash,
+        ))
+    }
+
+    async fn prepare_user_op(&self, resolved: &mut ResolvedWallet) -> Result<()> {
+        let wallet = self.smart_account(resolved).await?;
+        resolved.user_op =
+            Some(smart_account::prepare(&self.network, &wallet, &self.request).await?);
+        Ok(())
+    }
+
+    async fn smart_account(&self, resolved: &ResolvedWallet) -> Result<SmartAccountWallet> {
+        let wallets = Wallets::read().await;
+
+        match wallets.get(&resolved.name) {
+            Some(Wallet::SmartAccount(wallet)) => Ok(wallet.clone()),
+            _ => Err(Error::WalletNameNotFound(resolved.name.clone())),
+        }
+    }
+
+    async fn sign(&self, resolved: &ResolvedWallet) -> Result<Bytes> {
+        let signer = self.build_signer(resolved).await?;
+
+        // fillers take care of nonce, fees & chain id, and the wallet filler signs the result
+        let provider = ProviderBuilder::new()
+            .wallet(signer.to_wallet())
+            .connect(self.network.http_url.as_str())
+            .await?;
+
+        let envelope = provider
+            .fill(self.request.clone())
+            .await?
+            .try_into_envelope()
+            .map_err(|_| Error::TxNotSigned)?;
+
+        Ok(envelope.encoded_2718().into())
+    }
+
+    async fn build_signer(&self, resolved: &ResolvedWallet) -> Result<Signer> {
+        let wallet = {
+            let wallets = Wallets::read().await;
+            wallets
+                .get(&resolved.name)
+                .ok_or(Error::WalletNameNotFound(resolved.name.clone()))?
+                .clone()
+        };
+
+        Ok(wallet
+            .build_signer(self.network.chain_id(), &resolved.path)
+            .await?)
+    }
+
     async fn build_provider(&mut self, resolved: &ResolvedWallet) -> Result<DynProvider> {
         let wallet = {
             let wallets = Wallets::read().await;
@@ -255,6 +448,8 @@ impl SendTransaction {
                 .gas
                 .ok_or(())
                 .map_err(|_| Error::CannotSimulate)?,
+            state_overrides: None,
+            block_overrides: None,
         })
     }
 
@@ -273,3 +468,135 @@ impl SendTransaction {
             .map_err(|_| Error::CannotSimulate)
     }
 }
+
+/// Submits `request` unsigned, on behalf of its sender. The sender is topped up first if it can't
+/// afford the gas
+async fn send_impersonated_with(
+    provider: &impl Provider,
+    chain_id: u64,
+    request: &TransactionRequest,
+) -> Result<PendingTransactionBuilder<Ethereum>> {
+    let client = provider.client();
+    let from = request.from.unwrap_or_default();
+
+    // `anvil_*` methods are specific to anvil. other dev nodes (e.g. hardhat) don't have them
+    if client
+        .request::<_, Json>("anvil_nodeInfo", ())
+        .await
+        .is_err()
+    {
+        return Err(Error::ImpersonationUnavailable);
+    }
+
+    let gas_price = match request.max_fee_per_gas.or(request.gas_price) {
+        Some(price) => price,
+        None => provider.get_gas_price().await?,
+    };
+    let required = U256::from(request.gas.unwrap_or_default()) * U256::from(gas_price)
+        + request.value.unwrap_or_default();
+
+    if provider.get_balance(from).await? < required {
+        client
+            .request::<_, Json>("anvil_setBalance", (from, required * U256::from(2)))
+            .await?;
+    }
+
+    client
+        .request::<_, Json>("anvil_impersonateAccount", (from,))
+        .await?;
+
+    ethui_broadcast::transaction_submitted(chain_id).await;
+    let pending = provider.send_transaction(request.clone()).await;
+
+    // the transaction is already out (or failed on its own), so this is only worth a warning
+    if let Err(e) = client
+        .request::<_, Json>("anvil_stopImpersonatingAccount", (from,))
+        .await
+    {
+        warn!("failed to stop impersonating {from}: {e}");
+    }
+
+    Ok(pending?)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{providers::ProviderBuilder, transports::mock::Asserter};
+
+    use super::*;
+
+    const HASH: B256 = B256::repeat_byte(1);
+
+    fn request() -> TransactionRequest {
+        TransactionRequest::default()
+            .with_from(Address::repeat_byte(2))
+            .with_to(Address::repeat_byte(3))
+            .with_gas_limit(21_000)
+            .with_gas_price(1)
+    }
+
+    #[tokio::test]
+    async fn refuses_nodes_other_than_anvil() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        asserter.push_failure_msg("method not found");
+
+        let res = send_impersonated_with(&provider, 31337, &request()).await;
+
+        assert!(matches!(res, Err(Error::ImpersonationUnavailable)));
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[tokio::test]
+    async fn tops_up_senders_that_cannot_afford_gas() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        asserter.push_success(&json!({})); // anvil_nodeInfo
+        asserter.push_success(&U256::ZERO); // eth_getBalance
+        asserter.push_success(&Json::Null); // anvil_setBalance
+        asserter.push_success(&Json::Null); // anvil_impersonateAccount
+        asserter.push_success(&HASH); // eth_sendTransaction
+        asserter.push_success(&Json::Null); // anvil_stopImpersonatingAccount
+
+        let pending = send_impersonated_with(&provider, 31337, &request())
+            .await
+            .unwrap();
+
+        assert_eq!(*pending.tx_hash(), HASH);
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[tokio::test]
+    async fn leaves_funded_senders_alone() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        asserter.push_success(&json!({})); // anvil_nodeInfo
+        asserter.push_success(&U256::MAX); // eth_getBalance
+        asserter.push_success(&Json::Null); // anvil_impersonateAccount
+        asserter.push_success(&HASH); // eth_sendTransaction
+        asserter.push_success(&Json::Null); // anvil_stopImpersonatingAccount
+
+        let pending = send_impersonated_with(&provider, 31337, &request())
+            .await
+            .unwrap();
+
+        assert_eq!(*pending.tx_hash(), HASH);
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[tokio::test]
+    async fn stops_impersonating_when_the_send_fails() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        asserter.push_success(&json!({})); // anvil_nodeInfo
+        asserter.push_success(&U256::MAX); // eth_getBalance
+        asserter.push_success(&Json::Null); // anvil_impersonateAccount
+        asserter.push_failure_msg("nonce too low"); // eth_sendTransaction
+        asserter.push_success(&Json::Null); // anvil_stopImpersonatingAccount
+
+        let res = send_impersonated_with(&provider, 31337, &request()).await;
+
+        assert!(res.is_err());
+        assert!(asserter.read_q().is_empty());
+    }
+}
@@ -9,12 +9,17 @@ use alloy::{
 use ethui_connections::Ctx;
 use ethui_dialogs::{Dialog, DialogMsg};
 use ethui_settings::{SettingsActorExt as _, settings};
-use ethui_types::{Address, Json, Network};
+use ethui_types::{Address, Json, Network, U256};
 use ethui_wallets::{Signer, Wallet, WalletControl};
 use jsonrpc_core::Params as RpcParams;
 use serde::Serialize;
 
-use crate::{Error, Result, methods::Method};
+use crate::{
+    Error, Result,
+    methods::Method,
+    typed_data::{self, TypedDataSummary},
+    utils,
+};
 
 #[derive(Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -36,15 +41,16 @@ impl Method for EthSign {
             .first()
             .and_then(|v| v.clone())
             .ok_or(Error::ParseError)?;
-        // TODO: use address to verify it matches the wallet
-        let _address = params
+        let address = params
             .get(1)
             .and_then(|v| v.as_ref())
             .and_then(|s| Address::from_str(s).ok())
             .ok_or(Error::ParseError)?;
+        utils::authorize(&ctx, address).await?;
 
-        let wallet = ethui_wallets::get_current_wallet().await;
-        let wallet_path = wallet.get_current_path();
+        let (wallet, wallet_path) = ethui_wallets::find_wallet(address)
+            .await
+            .ok_or(Error::WalletNotFound(address))?;
         let network = ctx.network().await;
 
         Ok(Self {
@@ -53,6 +59,7 @@ impl Method for EthSign {
                 wallet_path,
                 network,
                 data: SignData::Raw(message),
+                summary: None,
             },
         })
     }
@@ -72,27 +79,42 @@ impl Method for EthSignTypedData {
     async fn build(params: RpcParams, ctx: Ctx) -> Result<Self> {
         // Params format: [address, typed_data_json]
         let params: Vec<Option<String>> = params.parse()?;
-        // TODO: use address to verify it matches the wallet
-        let _address = params
+        let address = params
             .first()
             .and_then(|v| v.as_ref())
-            .and_then(|s| Address::from_str(s).ok());
+            .and_then(|s| Address::from_str(s).ok())
+            .ok_or(Error::ParseError)?;
+        utils::authorize(&ctx, address).await?;
         let typed_data_str = params
             .get(1)
             .and_then(|v| v.clone())
             .ok_or(Error::ParseError)?;
         let typed_data: TypedData = serde_json::from_str(&typed_data_str)?;
 
-        let wallet = ethui_wallets::get_current_wallet().await;
-        let wallet_path = wallet.get_current_path();
+        let (wallet, wallet_path) = ethui_wallets::find_wallet(address)
+            .await
+            .ok_or(Error::WalletNotFound(address))?;
         let network = ctx.network().await;
 
+        // a signature for another chain could be replayed there without the user noticing
+        if let Some(got) = typed_data.domain.chain_id
+            && got != U256::from(network.chain_id())
+        {
+            return Err(Error::TypedDataChainIdMismatch {
+                expected: network.chain_id(),
+                got,
+            });
+        }
+
+        let summary = typed_data::summarize(&typed_data, network.chain_id()).await;
+
         Ok(Self {
             inner: SignMessage {
                 wallet,
                 wallet_path,
                 network,
                 data: SignData::Typed(Box::new(typed_data)),
+                summary,
             },
         })
     }
@@ -109,6 +131,8 @@ struct SignMessage {
     wallet_path: String,
     network: Network,
     data: SignData,
+    /// decoded permits, orders and such, for the dialog to review
+    summary: Option<TypedDataSummary>,
 }
 
 impl SignMessage {
@@ -129,7 +153,10 @@ impl SignMessage {
     }
 
     async fn spawn_dialog(&mut self) -> Result<()> {
-        let params = serde_json::to_value(&self.data).unwrap();
+        let mut params = serde_json::to_value(&self.data).unwrap();
+        if let Some(ref summary) = self.summary {
+            params["summary"] = serde_json::to_value(summary)?;
+        }
 
         let dialog = Dialog::new("msg-sign", params);
         dialog.open().await?;
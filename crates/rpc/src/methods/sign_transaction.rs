@@ -0,0 +1,27 @@
+use ethui_connections::Ctx;
+use ethui_types::prelude::*;
+use jsonrpc_core::Params as RpcParams;
+
+use super::SendTransaction;
+use crate::{Result, methods::Method};
+
+/// Handler for eth_signTransaction
+///
+/// Goes through the same review dialog & wallet resolution as eth_sendTransaction, but returns
+/// the RLP-encoded signed transaction instead of broadcasting it
+pub(crate) struct SignTransaction {
+    inner: SendTransaction,
+}
+
+impl Method for SignTransaction {
+    async fn build(params: RpcParams, ctx: Ctx) -> Result<Self> {
+        Ok(Self {
+            inner: SendTransaction::build(params, ctx).await?,
+        })
+    }
+
+    async fn run(mut self) -> Result<Json> {
+        let signed = self.inner.estimate_gas().await.finish_signed().await?;
+        Ok(json!(signed))
+    }
+}
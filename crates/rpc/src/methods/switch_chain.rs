@@ -0,0 +1,141 @@
+use ethui_connections::Ctx;
+use ethui_dialogs::{Dialog, DialogMsg};
+use ethui_networks::{NetworksActorExt as _, networks};
+use ethui_types::{NetworkId, prelude::*};
+use jsonrpc_core::Params as RpcParams;
+
+use crate::{Error, Result, methods::Method, params::SwitchChainParams};
+
+/// Handler for wallet_switchEthereumChain
+///
+/// When several networks share the requested chain id, the user is asked which one to switch to.
+/// The pick becomes the domain's sticky affinity, same as any other switch
+pub(crate) struct SwitchChain {
+    ctx: Ctx,
+    chain_id: u64,
+}
+
+/// What a switch request comes down to, before asking the user anything
+#[derive(Debug, PartialEq)]
+enum Target {
+    /// the domain is already on the requested chain
+    Current,
+    /// the only network with the requested chain id
+    Network(u64),
+    /// several networks share the requested chain id
+    Choice,
+    Unknown,
+}
+
+impl Method for SwitchChain {
+    async fn build(params: RpcParams, ctx: Ctx) -> Result<Self> {
+        let chain_id = SwitchChainParams::try_from(params)?.chain_id()?;
+
+        Ok(Self { ctx, chain_id })
+    }
+
+    async fn run(mut self) -> Result<Json> {
+        let current = self.ctx.network().await.id();
+
+        let candidates: Vec<_> = networks()
+            .get_list()
+            .await?
+            .into_iter()
+            .filter(|n| n.chain_id() == self.chain_id)
+            .collect();
+        let ids: Vec<_> = candidates.iter().map(|n| n.id()).collect();
+
+        let dedup_id = match target(current, self.chain_id, &ids) {
+            Target::Current => return Ok(Json::Null),
+            Target::Network(dedup_id) => dedup_id,
+            Target::Choice => self.choose(&candidates).await?,
+            Target::Unknown => {
+                return Err(Error::Ethui(eyre!("Invalid chain ID {}", self.chain_id)));
+            }
+        };
+
+        self.ctx
+            .switch_chain(self.chain_id, dedup_id)
+            .await
+            .map_err(Error::Ethui)?;
+
+        Ok(Json::Null)
+    }
+}
+
+fn target(current: NetworkId, chain_id: u64, candidates: &[NetworkId]) -> Target {
+    if current.chain_id() == chain_id {
+        return Target::Current;
+    }
+
+    match candidates {
+        [] => Target::Unknown,
+        [network] => Target::Network(network.dedup_id()),
+        _ => Target::Choice,
+    }
+}
+
+impl SwitchChain {
+    /// Asks the user which of the networks sharing the chain id to switch to, returning its dedup id
+    async fn choose(&self, candidates: &[Network]) -> Result<u64> {
+        let dialog = Dialog::new(
+            "chain-select",
+            json!({
+                "domain": self.ctx.domain,
+                "chainId": self.chain_id,
+                "networks": candidates,
+            }),
+        );
+        dialog.open().await?;
+
+        let Some(DialogMsg::Data(msg)) = dialog.recv().await else {
+            return Err(Error::UserRejectedDialog);
+        };
+
+        if msg["event"].as_str() != Some("accept") {
+            return Err(Error::UserRejectedDialog);
+        }
+
+        let id: NetworkId = serde_json::from_value(msg["id"].clone())?;
+
+        // only one of the offered networks can be picked
+        if !candidates.iter().any(|n| n.id() == id) {
+            return Err(Error::InvalidParams);
+        }
+
+        Ok(id.dedup_id())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(chain_id: u64, dedup_id: u64) -> NetworkId {
+        NetworkId::from(chain_id, dedup_id)
+    }
+
+    #[test]
+    fn stays_on_the_current_chain() {
+        let candidates = [id(31337, 0), id(31337, 1)];
+
+        assert_eq!(target(id(31337, 1), 31337, &candidates), Target::Current);
+    }
+
+    #[test]
+    fn switches_to_the_only_network_with_the_chain_id() {
+        assert_eq!(target(id(1, 0), 10, &[id(10, 2)]), Target::Network(2));
+    }
+
+    #[test]
+    fn asks_when_several_networks_share_the_chain_id() {
+        let candidates = [id(31337, 0), id(31337, 1)];
+
+        assert_eq!(target(id(1, 0), 31337, &candidates), Target::Choice);
+    }
+
+    #[test]
+    fn rejects_unknown_chains() {
+        assert_eq!(target(id(1, 0), 10, &[]), Target::Unknown);
+    }
+}
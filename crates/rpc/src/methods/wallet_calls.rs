@@ -0,0 +1,563 @@
+//! EIP-5792 batch calls: `wallet_sendCalls`, `wallet_getCallsStatus` & `wallet_getCapabilities`
+//!
+//! https://eips.ethereum.org/EIPS/eip-5792
+
+use alloy::{
+    hex,
+    network::{ReceiptResponse as _, TransactionBuilder as _},
+    rpc::types::{TransactionReceipt, TransactionRequest},
+};
+use ethui_connections::Ctx;
+use ethui_dialogs::{Dialog, DialogMsg};
+use ethui_networks::{NetworksActorExt as _, networks};
+use ethui_settings::{SettingsActorExt as _, settings};
+use ethui_types::prelude::*;
+use ethui_wallets::{Wallet, WalletControl, WalletType};
+use jsonrpc_core::Params as RpcParams;
+
+use super::SendTransaction;
+use crate::{Error, Result, methods::Method, params::extract_single_param, utils};
+
+/// Batch status codes, as defined by EIP-5792
+mod status {
+    pub const PENDING: u16 = 100;
+    pub const CONFIRMED: u16 = 200;
+    pub const OFFCHAIN_FAILURE: u16 = 400;
+    pub const REVERTED: u16 = 500;
+    pub const PARTIALLY_REVERTED: u16 = 600;
+}
+
+/// How long to wait for each call to be included before giving up on the rest of the batch
+const RECEIPT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Capabilities ethui knows how to honor. Anything else must be flagged as optional by the caller
+const SUPPORTED_CAPABILITIES: &[&str] = &[];
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SendCallsParams {
+    id: Option<String>,
+    from: Option<Address>,
+    chain_id: U64,
+    #[serde(default)]
+    atomic_required: bool,
+    calls: Vec<Call>,
+    #[serde(default)]
+    capabilities: HashMap<String, Json>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Call {
+    to: Option<Address>,
+    data: Option<Bytes>,
+    value: Option<U256>,
+    #[serde(default, skip_serializing)]
+    capabilities: HashMap<String, Json>,
+}
+
+impl Call {
+    fn into_request(self, from: Address) -> TransactionRequest {
+        let mut request = TransactionRequest::default().with_from(from);
+
+        if let Some(to) = self.to {
+            request.set_to(to);
+        }
+        if let Some(value) = self.value {
+            request.set_value(value);
+        }
+        if let Some(data) = self.data {
+            request.set_input(data);
+        }
+
+        request
+    }
+}
+
+/// Whether a wallet can execute a batch atomically on a given network
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum AtomicStatus {
+    Supported,
+    Ready,
+    Unsupported,
+}
+
+/// None of ethui's wallet types can currently execute a batch atomically, so batches are always
+/// sent as sequential transactions
+pub(crate) async fn atomic_status(_wallet: &Wallet, _network: &Network) -> AtomicStatus {
+    AtomicStatus::Unsupported
+}
+
+/// A submitted batch, persisted so that `wallet_getCallsStatus` can be answered later on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CallsBatch {
+    id: String,
+    chain_id: u64,
+    from: Address,
+    atomic: bool,
+    status: u16,
+    calls: usize,
+    receipts: Vec<CallReceipt>,
+    /// whether a call failed before making it onchain (rejected, failed to sign or broadcast)
+    #[serde(default)]
+    offchain_failure: bool,
+    /// whether a call was broadcast, but its receipt never came in. It may still have landed
+    #[serde(default)]
+    unconfirmed: bool,
+}
+
+impl CallsBatch {
+    async fn get(id: &str) -> Result<Option<Self>> {
+        Ok(ethui_db::get().kv_get(&batch_key(id)).await?)
+    }
+
+    async fn save(&self) -> Result<()> {
+        ethui_db::get().kv_set(&batch_key(&self.id), self).await?;
+        Ok(())
+    }
+
+    /// Final status once no more calls are going to be sent
+    fn final_status(&self) -> u16 {
+        let successes = self
+            .receipts
+            .iter()
+            .filter(|r| r.status == U64::from(1))
+            .count();
+
+        // once anything may have landed onchain, a failure can only be a partial one
+        if successes == self.calls {
+            status::CONFIRMED
+        } else if self.receipts.is_empty() && !self.unconfirmed {
+            status::OFFCHAIN_FAILURE
+        } else if successes == 0 && !self.offchain_failure && !self.unconfirmed {
+            status::REVERTED
+        } else {
+            status::PARTIALLY_REVERTED
+        }
+    }
+}
+
+fn batch_key(id: &str) -> (&'static str, &str) {
+    ("wallet_calls", id)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CallReceipt {
+    logs: Vec<CallLog>,
+    status: U64,
+    block_hash: Option<B256>,
+    block_number: Option<U64>,
+    gas_used: U64,
+    transaction_hash: B256,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CallLog {
+    address: Address,
+    data: Bytes,
+    topics: Vec<B256>,
+}
+
+impl From<TransactionReceipt> for CallReceipt {
+    fn from(receipt: TransactionReceipt) -> Self {
+        Self {
+            logs: receipt
+                .inner
+                .logs()
+                .iter()
+                .map(|log| CallLog {
+                    address: log.address(),
+                    data: log.data().data.clone(),
+                    topics: log.topics().to_vec(),
+                })
+                .collect(),
+            status: U64::from(receipt.status() as u64),
+            block_hash: receipt.block_hash(),
+            block_number: receipt.block_number().map(U64::from),
+            gas_used: U64::from(receipt.gas_used()),
+            transaction_hash: receipt.transaction_hash(),
+        }
+    }
+}
+
+/// Handler for wallet_sendCalls
+pub(crate) struct SendCalls {
+    network: Network,
+    from: Address,
+    id: Option<String>,
+    atomic_required: bool,
+    calls: Vec<Call>,
+    capabilities: HashMap<String, Json>,
+}
+
+impl Method for SendCalls {
+    async fn build(params: RpcParams, ctx: Ctx) -> Result<Self> {
+        let params: SendCallsParams = serde_json::from_value(extract_single_param(params))?;
+        let network = ctx.network().await;
+
+        let chain_id: u64 = params.chain_id.to();
+        if chain_id != network.chain_id() {
+            return Err(Error::UnsupportedChainId(chain_id));
+        }
+
+        let from = match params.from {
+            Some(from) => from,
+            None => utils::default_account(&ctx)
+                .await
+                .ok_or(Error::Unauthorized)?,
+        };
+        utils::authorize(&ctx, from).await?;
+
+        Ok(Self {
+            network,
+            from,
+            id: params.id,
+            atomic_required: params.atomic_required,
+            calls: params.calls,
+            capabilities: params.capabilities,
+        })
+    }
+
+    async fn run(self) -> Result<Json> {
+        self.check_capabilities()?;
+
+        let id = match self.id.clone() {
+            Some(id) if CallsBatch::get(&id).await?.is_some() => {
+                return Err(Error::DuplicateBatchId(id));
+            }
+            Some(id) => id,
+            None => format!("0x{}", hex::encode(rand::random::<[u8; 32]>())),
+        };
+
+        let (wallet, _) = ethui_wallets::find_wallet(self.from)
+            .await
+            .ok_or(Error::WalletNotFound(self.from))?;
+
+        if self.atomic_required
+            && atomic_status(&wallet, &self.network).await == AtomicStatus::Unsupported
+        {
+            return Err(Error::AtomicityNotSupported);
+        }
+
+        self.review(&wallet).await?;
+
+        let batch = CallsBatch {
+            id: id.clone(),
+            chain_id: self.network.chain_id(),
+            from: self.from,
+            atomic: false,
+            status: status::PENDING,
+            calls: self.calls.len(),
+            receipts: vec![],
+            offchain_failure: false,
+            unconfirmed: false,
+        };
+        batch.save().await?;
+
+        // calls are sent in the background, and the caller polls wallet_getCallsStatus
+        tokio::spawn(async move {
+            if let Err(e) = self.execute(batch).await {
+                warn!("failed to execute calls batch: {}", e);
+            }
+        });
+
+        Ok(json!({ "id": id }))
+    }
+}
+
+impl SendCalls {
+    fn check_capabilities(&self) -> Result<()> {
+        let requested = self
+            .capabilities
+            .iter()
+            .chain(self.calls.iter().flat_map(|c| c.capabilities.iter()));
+
+        for (name, capability) in requested {
+            let optional = capability["optional"].as_bool().unwrap_or(false);
+
+            if !optional && !SUPPORTED_CAPABILITIES.contains(&name.as_str()) {
+                return Err(Error::UnsupportedCapability(name.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn review(&self, wallet: &Wallet) -> Result<()> {
+        let skip = self.network.is_dev().await?
+            && wallet.is_dev()
+            && settings()
+                .get_all()
+                .await
+                .map_err(|e| eyre!("{}", e))?
+                .fast_mode;
+
+        // skip the dialog if both network & wallet allow for it, and if fast_mode is enabled
+        if skip {
+            return Ok(());
+        }
+
+        let params = json!({
+            "from": self.from,
+            "chainId": self.network.chain_id(),
+            "walletType": WalletType::from(wallet).to_string(),
+            "calls": self.calls,
+        });
+
+        let dialog = Dialog::new("calls-review", params);
+        dialog.open().await?;
+
+        while let Some(msg) = dialog.recv().await {
+            match msg {
+                DialogMsg::Data(msg) => match msg["event"].as_str() {
+                    Some("simulate") => self.simulate(&dialog).await?,
+                    Some("accept") => return Ok(()),
+                    _ => return Err(Error::TxDialogRejected),
+                },
+
+                DialogMsg::Close => return Err(Error::TxDialogRejected),
+            }
+        }
+
+        Err(Error::TxDialogRejected)
+    }
+
+    /// Simulates the calls in order, each one on top of the previous ones
+    async fn simulate(&self, dialog: &Dialog) -> Result<()> {
+        let requests = self
+            .calls
+            .iter()
+            .map(|call| ethui_simulator::Request {
+                from: self.from,
+                to: call.to,
+                data: call.data.clone(),
+                value: call.value,
+                gas_limit: 0,
+                state_overrides: None,
+                block_overrides: None,
+            })
+            .collect();
+
+        let bundle =
+            ethui_simulator::simulate_bundle(requests, self.network.http_url.to_string(), None)
+                .await;
+
+        // calls that couldn't run are left without a simulation, and so is every call when the
+        // bundle as a whole fails (e.g. the node is unreachable)
+        let results: Vec<_> = match bundle {
+            Ok(bundle) => bundle
+                .results
+                .into_iter()
+                .map(|outcome| match outcome {
+                    ethui_simulator::TxOutcome::Simulated(result) => Some(result),
+                    ethui_simulator::TxOutcome::Invalid { .. } => None,
+                })
+                .collect(),
+            Err(_) => vec![None; self.calls.len()],
+        };
+
+        dialog
+            .send("simulation-result", Some(serde_json::to_value(results)?))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Sends each call as its own transaction, waiting for inclusion before moving on to the next
+    /// one, so that later calls see the effects of earlier ones
+    async fn execute(self, mut batch: CallsBatch) -> Result<()> {
+        for call in self.calls.into_iter() {
+            let mut tx = SendTransaction {
+                network: self.network.clone(),
+                from: self.from,
+                request: call.into_request(self.from),
+                replaces: None,
+            };
+
+            let pending = match tx.estimate_gas().await.finish_reviewed().await {
+                Ok(pending) => pending,
+                Err(e) => {
+                    warn!("failed to send call {}: {}", batch.receipts.len(), e);
+                    batch.offchain_failure = true;
+                    break;
+                }
+            };
+
+            // stop at the first failure. later calls most likely depend on it
+            let receipt = match pending
+                .with_timeout(Some(RECEIPT_TIMEOUT))
+                .get_receipt()
+                .await
+            {
+                Ok(receipt) => receipt,
+                Err(e) => {
+                    warn!("no receipt for call {}: {}", batch.receipts.len(), e);
+                    batch.unconfirmed = true;
+                    break;
+                }
+            };
+            let success = receipt.status();
+            batch.receipts.push(receipt.into());
+            batch.save().await?;
+
+            if !success {
+                break;
+            }
+        }
+
+        batch.status = batch.final_status();
+        batch.save().await
+    }
+}
+
+/// Handler for wallet_getCallsStatus
+pub(crate) struct GetCallsStatus {
+    id: String,
+}
+
+impl Method for GetCallsStatus {
+    async fn build(params: RpcParams, _ctx: Ctx) -> Result<Self> {
+        let params: Vec<String> = params.parse()?;
+        let id = params.into_iter().next().ok_or(Error::InvalidParams)?;
+
+        Ok(Self { id })
+    }
+
+    async fn run(self) -> Result<Json> {
+        let batch = CallsBatch::get(&self.id)
+            .await?
+            .ok_or_else(|| Error::UnknownBatchId(self.id.clone()))?;
+
+        Ok(json!({
+            "version": "2.0.0",
+            "id": batch.id,
+            "chainId": format!("0x{:x}", batch.chain_id),
+            "status": batch.status,
+            "atomic": batch.atomic,
+            "receipts": batch.receipts,
+        }))
+    }
+}
+
+/// Handler for wallet_getCapabilities
+pub(crate) struct GetCapabilities {
+    address: Address,
+    chain_ids: Option<Vec<U64>>,
+}
+
+impl Method for GetCapabilities {
+    async fn build(params: RpcParams, _ctx: Ctx) -> Result<Self> {
+        // Params format: [address, chain_ids?]
+        let params: Vec<Json> = params.parse()?;
+        let address = params
+            .first()
+            .cloned()
+            .map(serde_json::from_value)
+            .ok_or(Error::InvalidParams)??;
+        let chain_ids = params
+            .get(1)
+            .cloned()
+            .map(serde_json::from_value)
+            .transpose()?;
+
+        Ok(Self { address, chain_ids })
+    }
+
+    async fn run(self) -> Result<Json> {
+        let (wallet, _) = ethui_wallets::find_wallet(self.address)
+            .await
+            .ok_or(Error::WalletNotFound(self.address))?;
+
+        let mut res = serde_json::Map::new();
+
+        for network in networks().get_list().await? {
+            let requested = self
+                .chain_ids
+                .as_ref()
+                .is_none_or(|ids| ids.contains(&U64::from(network.chain_id())));
+
+            if !requested || res.contains_key(&network.chain_id_hex()) {
+                continue;
+            }
+
+            let atomic = atomic_status(&wallet, &network).await;
+            res.insert(
+                network.chain_id_hex(),
+                json!({ "atomic": { "status": atomic } }),
+            );
+        }
+
+        Ok(res.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn receipt(success: bool) -> CallReceipt {
+        CallReceipt {
+            logs: vec![],
+            status: U64::from(success as u8),
+            block_hash: None,
+            block_number: None,
+            gas_used: U64::ZERO,
+            transaction_hash: B256::ZERO,
+        }
+    }
+
+    fn batch(receipts: Vec<CallReceipt>, offchain_failure: bool, unconfirmed: bool) -> CallsBatch {
+        CallsBatch {
+            id: "0x01".into(),
+            chain_id: 1,
+            from: Address::ZERO,
+            atomic: false,
+            status: status::PENDING,
+            calls: 3,
+            receipts,
+            offchain_failure,
+            unconfirmed,
+        }
+    }
+
+    #[test]
+    fn confirmed_when_every_call_succeeded() {
+        let batch = batch(vec![receipt(true); 3], false, false);
+
+        assert_eq!(batch.final_status(), status::CONFIRMED);
+    }
+
+    #[test]
+    fn offchain_failure_when_nothing_was_sent() {
+        let batch = batch(vec![], true, false);
+
+        assert_eq!(batch.final_status(), status::OFFCHAIN_FAILURE);
+    }
+
+    #[test]
+    fn reverted_when_the_first_call_reverted() {
+        let batch = batch(vec![receipt(false)], false, false);
+
+        assert_eq!(batch.final_status(), status::REVERTED);
+    }
+
+    #[test]
+    fn partial_when_a_call_failed_after_others_landed() {
+        let offchain = batch(vec![receipt(true)], true, false);
+        let reverted = batch(vec![receipt(true), receipt(false)], false, false);
+
+        assert_eq!(offchain.final_status(), status::PARTIALLY_REVERTED);
+        assert_eq!(reverted.final_status(), status::PARTIALLY_REVERTED);
+    }
+
+    #[test]
+    fn partial_when_a_receipt_never_came_in() {
+        let batch = batch(vec![], false, true);
+
+        assert_eq!(batch.final_status(), status::PARTIALLY_REVERTED);
+    }
+}
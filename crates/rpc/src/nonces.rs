@@ -0,0 +1,388 @@
+//! Local nonce management
+//!
+//! Nonces are assigned on top of the transactions ethui has submitted itself, rather than relying
+//! on the node's count alone, so that quick successive sends don't collide. Submitted
+//! transactions are watched until their nonce is used up on-chain, and can be sped up or
+//! cancelled meanwhile
+
+use std::sync::LazyLock;
+
+use alloy::{
+    consensus::Transaction as _, network::TransactionBuilder as _, providers::Provider,
+    rpc::types::TransactionRequest,
+};
+use ethui_networks::NetworksActorExt as _;
+use ethui_types::{NetworkId, PendingTx, PendingTxStatus, prelude::*};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(4);
+
+/// How many polls in a row the node must not know about a transaction before it's considered
+/// dropped. A single miss can just be a load-balanced node that hasn't seen it yet
+const MAX_MISSES: u32 = 3;
+
+/// How many polls in a row the transaction's network can be missing (e.g. removed by the user)
+/// before giving up on it
+const MAX_NETWORK_FAILURES: u32 = 3;
+
+/// Replacements must pay meaningfully more than the original, or nodes refuse them (geth requires
+/// at least 10%)
+const FEE_BUMP_PERCENT: u128 = 115;
+
+static LOCKS: LazyLock<std::sync::Mutex<HashMap<(NetworkId, Address), Arc<Mutex<()>>>>> =
+    LazyLock::new(Default::default);
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Replacement {
+    /// the same transaction, with higher fees
+    SpeedUp,
+    /// a 0-value self-send, which does nothing but use up the nonce
+    Cancel,
+}
+
+/// Serializes sends from the same account, from nonce assignment until the transaction is tracked
+pub(crate) async fn lock(network_id: NetworkId, from: Address) -> OwnedMutexGuard<()> {
+    let lock = LOCKS
+        .lock()
+        .unwrap()
+        .entry((network_id, from))
+        .or_default()
+        .clone();
+
+    lock.lock_owned().await
+}
+
+/// The next nonce for `from`: the node's count (mempool included), unless ethui is tracking
+/// transactions past it
+///
+/// Dev nodes are trusted over the tracked transactions, since a reset (e.g. anvil restarted)
+/// takes them all away
+pub(crate) async fn next(network: &Network, from: Address) -> color_eyre::Result<u64> {
+    let provider = network.get_alloy_provider().await?;
+    let pending = if network.is_dev().await? {
+        vec![]
+    } else {
+        ethui_db::get().get_pending_txs(network.id(), from).await?
+    };
+
+    next_with(&provider, from, &pending).await
+}
+
+async fn next_with(
+    provider: &impl Provider,
+    from: Address,
+    pending: &[PendingTx],
+) -> color_eyre::Result<u64> {
+    let onchain = provider.get_transaction_count(from).pending().await?;
+    let local = pending
+        .iter()
+        .map(|tx| tx.nonce + 1)
+        .max()
+        .unwrap_or_default();
+
+    Ok(onchain.max(local))
+}
+
+/// Starts tracking a transaction that was just submitted
+pub(crate) async fn track(
+    network: &Network,
+    from: Address,
+    request: &TransactionRequest,
+    hash: B256,
+    replaces: Option<B256>,
+) -> color_eyre::Result<()> {
+    let tx = PendingTx {
+        hash,
+        chain_id: network.chain_id(),
+        dedup_id: network.id().dedup_id(),
+        from,
+        nonce: request.nonce.unwrap_or_default(),
+        request: serde_json::to_value(request)?,
+        status: PendingTxStatus::Pending,
+        replaces,
+    };
+
+    ethui_db::get().save_pending_tx(&tx).await?;
+    ethui_broadcast::ui_notify(UINotify::TxsUpdated).await;
+
+    tokio::spawn(watch(tx));
+    Ok(())
+}
+
+/// Resumes watching the transactions that were still pending when ethui was last closed
+pub async fn init() {
+    match ethui_db::get().get_all_pending_txs().await {
+        Ok(txs) => {
+            for tx in txs {
+                tokio::spawn(watch(tx));
+            }
+        }
+        Err(e) => warn!("failed to load pending transactions: {e}"),
+    }
+}
+
+/// Builds a request replacing `tx`, with the same nonce and bumped fees
+pub(crate) async fn replacement(
+    tx: &PendingTx,
+    kind: Replacement,
+) -> color_eyre::Result<(Network, TransactionRequest)> {
+    if tx.status != PendingTxStatus::Pending {
+        return Err(eyre!("transaction {} is no longer pending", tx.hash));
+    }
+
+    let network = get_network(tx.network_id()).await?;
+    let provider = network.get_alloy_provider().await?;
+
+    let original: TransactionRequest = serde_json::from_value(tx.request.clone())?;
+    let mut request = match kind {
+        Replacement::SpeedUp => original.clone(),
+        Replacement::Cancel => TransactionRequest::default()
+            .from(tx.from)
+            .to(tx.from)
+            .value(U256::ZERO)
+            .gas_limit(21_000),
+    };
+
+    // fees were filled in at submission, so the node is the one that knows them
+    let (max_fee, priority_fee) = match provider.get_transaction_by_hash(tx.hash).await? {
+        Some(sent) => (
+            sent.max_fee_per_gas(),
+            sent.max_priority_fee_per_gas()
+                .unwrap_or_else(|| sent.max_fee_per_gas()),
+        ),
+        None => (
+            original.max_fee_per_gas.unwrap_or_default(),
+            original.max_priority_fee_per_gas.unwrap_or_default(),
+        ),
+    };
+    let market = provider.estimate_eip1559_fees().await?;
+
+    request.gas_price = None;
+    request.set_nonce(tx.nonce);
+    request.set_max_fee_per_gas(bump(max_fee).max(market.max_fee_per_gas));
+    request.set_max_priority_fee_per_gas(bump(priority_fee).max(market.max_priority_fee_per_gas));
+
+    Ok((network, request))
+}
+
+fn bump(fee: u128) -> u128 {
+    fee * FEE_BUMP_PERCENT / 100 + 1
+}
+
+async fn watch(tx: PendingTx) {
+    let mut misses = 0;
+    let mut network_failures = 0;
+
+    loop {
+        let result = match get_network(tx.network_id()).await {
+            Ok(network) => {
+                network_failures = 0;
+                poll(&network, &tx, &mut misses).await
+            }
+            Err(_) if network_failures + 1 >= MAX_NETWORK_FAILURES => {
+                Ok(Some(PendingTxStatus::Dropped))
+            }
+            Err(e) => {
+                network_failures += 1;
+                Err(e)
+            }
+        };
+
+        match result {
+            Ok(Some(status)) => {
+                if let Err(e) = ethui_db::get().set_pending_tx_status(tx.hash, status).await {
+                    warn!("failed to update transaction {}: {e}", tx.hash);
+                }
+                ethui_broadcast::ui_notify(UINotify::TxsUpdated).await;
+                return;
+            }
+            Ok(None) => {}
+            Err(e) => trace!("failed to check transaction {}: {e}", tx.hash),
+        }
+
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+async fn get_network(id: NetworkId) -> color_eyre::Result<Network> {
+    ethui_networks::networks()
+        .get(id)
+        .await?
+        .ok_or_else(|| eyre!("network {} not found", id.chain_id()))
+}
+
+async fn poll(
+    network: &Network,
+    tx: &PendingTx,
+    misses: &mut u32,
+) -> color_eyre::Result<Option<PendingTxStatus>> {
+    let provider = network.get_alloy_provider().await?;
+
+    // nodes evict a transaction from their mempool once a replacement for it comes in
+    let replaced = ethui_db::get()
+        .get_pending_txs(tx.network_id(), tx.from)
+        .await?
+        .iter()
+        .any(|other| other.replaces == Some(tx.hash));
+
+    check(&provider, tx, replaced, misses).await
+}
+
+/// The transaction's new status, once its nonce is used up or the node forgets about it
+///
+/// `misses` counts the polls in a row the node didn't know about the transaction
+async fn check(
+    provider: &impl Provider,
+    tx: &PendingTx,
+    replaced: bool,
+    misses: &mut u32,
+) -> color_eyre::Result<Option<PendingTxStatus>> {
+    // the nonce is checked first, so that a transaction mined in between the two calls isn't
+    // mistaken for a replaced one
+    let onchain_nonce = provider.get_transaction_count(tx.from).latest().await?;
+    if onchain_nonce <= tx.nonce {
+        if provider.get_transaction_by_hash(tx.hash).await?.is_some() {
+            *misses = 0;
+            return Ok(None);
+        }
+
+        if replaced {
+            return Ok(Some(PendingTxStatus::Replaced));
+        }
+
+        // e.g. a dev node that was restarted
+        *misses += 1;
+        if *misses >= MAX_MISSES {
+            return Ok(Some(PendingTxStatus::Dropped));
+        }
+
+        return Ok(None);
+    }
+
+    match provider.get_transaction_receipt(tx.hash).await? {
+        Some(_) => Ok(Some(PendingTxStatus::Mined)),
+        None => Ok(Some(PendingTxStatus::Replaced)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{providers::ProviderBuilder, transports::mock::Asserter};
+
+    use super::*;
+
+    fn pending_tx(nonce: u64) -> PendingTx {
+        PendingTx {
+            hash: B256::repeat_byte(nonce as u8),
+            chain_id: 31337,
+            dedup_id: 0,
+            from: Address::ZERO,
+            nonce,
+            request: json!({}),
+            status: PendingTxStatus::Pending,
+            replaces: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn next_uses_the_node_count_without_local_txs() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        asserter.push_success(&U64::from(5));
+
+        assert_eq!(next_with(&provider, Address::ZERO, &[]).await.unwrap(), 5);
+    }
+
+    #[tokio::test]
+    async fn next_goes_past_tracked_txs() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        asserter.push_success(&U64::from(5));
+
+        let pending = [pending_tx(5), pending_tx(7)];
+
+        assert_eq!(
+            next_with(&provider, Address::ZERO, &pending).await.unwrap(),
+            8
+        );
+    }
+
+    #[tokio::test]
+    async fn next_ignores_tracked_txs_behind_the_node() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        asserter.push_success(&U64::from(9));
+
+        assert_eq!(
+            next_with(&provider, Address::ZERO, &[pending_tx(3)])
+                .await
+                .unwrap(),
+            9
+        );
+    }
+
+    #[test]
+    fn bump_clears_the_replacement_threshold() {
+        assert_eq!(bump(0), 1);
+        assert_eq!(bump(100), 116);
+        assert!(bump(1_000_000_000) * 100 >= 1_000_000_000 * 110);
+    }
+
+    #[tokio::test]
+    async fn check_waits_for_several_misses_before_dropping() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        let tx = pending_tx(5);
+        let mut misses = 0;
+
+        for _ in 1..MAX_MISSES {
+            asserter.push_success(&U64::from(5));
+            asserter.push_success(&Json::Null);
+            assert_eq!(
+                check(&provider, &tx, false, &mut misses).await.unwrap(),
+                None
+            );
+        }
+
+        asserter.push_success(&U64::from(5));
+        asserter.push_success(&Json::Null);
+        assert_eq!(
+            check(&provider, &tx, false, &mut misses).await.unwrap(),
+            Some(PendingTxStatus::Dropped)
+        );
+    }
+
+    #[tokio::test]
+    async fn check_marks_missing_txs_with_a_replacement_as_replaced() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        let mut misses = 0;
+
+        asserter.push_success(&U64::from(5));
+        asserter.push_success(&Json::Null);
+
+        assert_eq!(
+            check(&provider, &pending_tx(5), true, &mut misses)
+                .await
+                .unwrap(),
+            Some(PendingTxStatus::Replaced)
+        );
+    }
+
+    #[tokio::test]
+    async fn check_marks_used_nonces_without_a_receipt_as_replaced() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        let mut misses = 0;
+
+        asserter.push_success(&U64::from(6));
+        asserter.push_success(&Json::Null);
+
+        assert_eq!(
+            check(&provider, &pending_tx(5), false, &mut misses)
+                .await
+                .unwrap(),
+            Some(PendingTxStatus::Replaced)
+        );
+    }
+}
@@ -1,5 +1,6 @@
 //! RPC parameter types with automatic conversion from jsonrpc_core::Params
 
+use alloy::primitives::Bytes;
 use ethui_connections::permissions::PermissionRequest;
 use ethui_types::Json;
 use jsonrpc_core::Params;
@@ -83,3 +84,20 @@ impl SwitchChainParams {
         u64::from_str_radix(hex_str, 16).map_err(|_| Error::InvalidParams)
     }
 }
+
+/// Params for web3_sha3
+#[derive(Debug)]
+pub struct Sha3Params(pub Bytes);
+
+impl TryFrom<Params> for Sha3Params {
+    type Error = Error;
+
+    fn try_from(params: Params) -> Result<Self> {
+        let params: Vec<Bytes> = params.parse()?;
+        params
+            .into_iter()
+            .next()
+            .map(Self)
+            .ok_or(Error::InvalidParams)
+    }
+}
@@ -0,0 +1,319 @@
+//! Safe multisig support
+//!
+//! Transactions sent from a Safe wallet are proposed as SafeTxs, persisted in the database, and
+//! signed by whichever of the Safe's owners are also ethui wallets. Once enough owners have signed,
+//! `execTransaction` is submitted by one of them
+
+use alloy::{
+    dyn_abi::TypedData,
+    network::TransactionBuilder as _,
+    primitives::{Signature, TxKind},
+    providers::{PendingTransactionBuilder, Provider as _, ProviderBuilder},
+    rpc::types::TransactionRequest,
+    signers::Signer as _,
+    sol,
+    sol_types::{Eip712Domain, SolStruct as _, eip712_domain},
+};
+use ethui_abis::ISafe;
+use ethui_proxy_detect::ProxyType;
+use ethui_types::{SafeProposal, prelude::*};
+use ethui_wallets::WalletControl as _;
+
+/// Storage slot holding the threshold, in every Safe version to date
+const THRESHOLD_SLOT: U256 = U256::from_limbs([4, 0, 0, 0]);
+
+sol! {
+    /// The EIP-712 struct Safe owners sign
+    #[derive(Debug, Serialize, Deserialize)]
+    struct SafeTx {
+        address to;
+        uint256 value;
+        bytes data;
+        uint8 operation;
+        uint256 safeTxGas;
+        uint256 baseGas;
+        uint256 gasPrice;
+        address gasToken;
+        address refundReceiver;
+        uint256 nonce;
+    }
+}
+
+/// Turns `request` into a SafeTx, and collects signatures for it from local owners
+pub(crate) async fn propose(
+    network: &Network,
+    safe: Address,
+    request: &TransactionRequest,
+) -> Result<SafeProposal> {
+    let provider = network.get_alloy_provider().await?;
+
+    match ethui_proxy_detect::detect_proxy(safe, &provider).await? {
+        Some(ProxyType::Safe(_)) => {}
+        _ => return Err(eyre!("{safe} is not a Safe")),
+    }
+
+    let Some(TxKind::Call(to)) = request.to else {
+        return Err(eyre!("Safes cannot deploy contracts directly"));
+    };
+
+    let onchain_nonce: u64 = ISafe::new(safe, &provider)
+        .nonce()
+        .call()
+        .await?
+        .saturating_to();
+
+    // proposals that are still waiting for signatures take up the next nonces
+    let nonce = ethui_db::get()
+        .get_safe_proposals(network.chain_id(), safe)
+        .await?
+        .iter()
+        .filter(|p| p.executed_in.is_none() && p.nonce >= onchain_nonce)
+        .map(|p| p.nonce + 1)
+        .max()
+        .unwrap_or(onchain_nonce);
+
+    let tx = SafeTx {
+        to,
+        value: request.value.unwrap_or_default(),
+        data: request.input().cloned().unwrap_or_default(),
+        operation: 0,
+        safeTxGas: U256::ZERO,
+        baseGas: U256::ZERO,
+        gasPrice: U256::ZERO,
+        gasToken: Address::ZERO,
+        refundReceiver: Address::ZERO,
+        nonce: U256::from(nonce),
+    };
+
+    let proposal = SafeProposal {
+        safe_tx_hash: tx.eip712_signing_hash(&domain(network, safe).await?),
+        chain_id: network.chain_id(),
+        safe,
+        nonce,
+        tx: serde_json::to_value(&tx)?,
+        signatures: Default::default(),
+        executed_in: None,
+    };
+
+    sign(network, proposal).await
+}
+
+/// Signs a proposal with every local owner that hasn't signed it yet, until the threshold is met
+pub(crate) async fn sign(network: &Network, mut proposal: SafeProposal) -> Result<SafeProposal> {
+    let provider = network.get_alloy_provider().await?;
+    let contract = ISafe::new(proposal.safe, &provider);
+    let threshold = threshold(network, proposal.safe).await?;
+
+    // signed as typed data rather than by hash, which remote signers can't do
+    let tx: SafeTx = serde_json::from_value(proposal.tx.clone())?;
+    let typed_data = TypedData::from_struct(&tx, Some(domain(network, proposal.safe).await?));
+
+    for owner in contract.getOwners().call().await? {
+        if proposal.signatures.len() >= threshold {
+            break;
+        }
+
+        if proposal.signatures.contains_key(&owner) {
+            continue;
+        }
+
+        let Some((wallet, path)) = ethui_wallets::find_wallet(owner).await else {
+            continue;
+        };
+
+        // not every wallet can sign (e.g. impersonators), or sign arbitrary typed data (e.g. ledgers)
+        let signature = match wallet.build_signer(network.chain_id(), &path).await {
+            Ok(signer) => signer
+                .sign_dynamic_typed_data(&typed_data)
+                .await
+                .map_err(Into::into),
+            Err(e) => Err(e),
+        };
+
+        match signature {
+            Ok(signature) => {
+                proposal
+                    .signatures
+                    .insert(owner, signature.as_bytes().into());
+            }
+            Err(e) => warn!(
+                "owner {owner} could not sign {}: {e}",
+                proposal.safe_tx_hash
+            ),
+        }
+    }
+
+    ethui_db::get().save_safe_proposal(&proposal).await?;
+    Ok(proposal)
+}
+
+/// The domain SafeTxs are signed in, which depends on the Safe's version
+async fn domain(network: &Network, safe: Address) -> Result<Eip712Domain> {
+    let provider = network.get_alloy_provider().await?;
+    let version = ISafe::new(safe, &provider).VERSION().call().await?;
+
+    Ok(versioned_domain(&version, network.chain_id(), safe))
+}
+
+/// Safes before 1.3.0 leave the chain id out of the domain
+fn versioned_domain(version: &str, chain_id: u64, safe: Address) -> Eip712Domain {
+    let mut parts = version.split(['.', '+', '-']).map(str::parse::<u64>);
+
+    match (parts.next(), parts.next()) {
+        (Some(Ok(major)), Some(Ok(minor))) if (major, minor) < (1, 3) => eip712_domain! {
+            verifying_contract: safe,
+        },
+        _ => eip712_domain! {
+            chain_id: chain_id,
+            verifying_contract: safe,
+        },
+    }
+}
+
+/// Adds a signature produced outside of ethui (e.g. by a co-owner)
+pub(crate) async fn add_signature(
+    mut proposal: SafeProposal,
+    owner: Address,
+    signature: Bytes,
+) -> Result<SafeProposal> {
+    let recovered = Signature::try_from(signature.as_ref())?
+        .recover_address_from_prehash(&proposal.safe_tx_hash)?;
+
+    if recovered != owner {
+        return Err(eyre!("signature was not produced by {owner}"));
+    }
+
+    proposal.signatures.insert(owner, signature);
+    ethui_db::get().save_safe_proposal(&proposal).await?;
+    Ok(proposal)
+}
+
+pub(crate) async fn threshold(network: &Network, safe: Address) -> Result<usize> {
+    let provider = network.get_alloy_provider().await?;
+    let threshold = ISafe::new(safe, &provider).getThreshold().call().await?;
+
+    Ok(threshold.saturating_to())
+}
+
+/// Submits `execTransaction`, paid for by one of the owners that signed the proposal
+pub(crate) async fn execute(
+    network: &Network,
+    mut proposal: SafeProposal,
+) -> Result<PendingTransactionBuilder<Ethereum>> {
+    let threshold = threshold(network, proposal.safe).await?;
+    if proposal.signatures.len() < threshold {
+        return Err(eyre!(
+            "{} of {threshold} signatures collected",
+            proposal.signatures.len()
+        ));
+    }
+
+    let mut signer = None;
+    for owner in proposal.signatures.keys() {
+        if let Some((wallet, path)) = ethui_wallets::find_wallet(*owner).await
+            && let Ok(s) = wallet.build_signer(network.chain_id(), &path).await
+        {
+            signer = Some(s);
+            break;
+        }
+    }
+    let signer = signer.ok_or_else(|| eyre!("no local owner can submit the transaction"))?;
+
+    // Safe expects signatures sorted by owner
+    let mut owners: Vec<_> = proposal.signatures.keys().copied().collect();
+    owners.sort();
+    let signatures: Vec<u8> = owners
+        .iter()
+        .flat_map(|owner| proposal.signatures[owner].to_vec())
+        .collect();
+
+    let tx: SafeTx = serde_json::from_value(proposal.tx.clone())?;
+    let provider = ProviderBuilder::new()
+        .wallet(signer.to_wallet())
+        .connect(network.http_url.as_str())
+        .await?;
+
+    let pending = ISafe::new(proposal.safe, &provider)
+        .execTransaction(
+            tx.to,
+            tx.value,
+            tx.data,
+            tx.operation,
+            tx.safeTxGas,
+            tx.baseGas,
+            tx.gasPrice,
+            tx.gasToken,
+            tx.refundReceiver,
+            signatures.into(),
+        )
+        .send()
+        .await?;
+
+    proposal.executed_in = Some(*pending.tx_hash());
+    ethui_db::get().save_safe_proposal(&proposal).await?;
+
+    Ok(pending)
+}
+
+/// Overwrites the threshold of a Safe on a dev network, so that a single local owner is enough to
+/// test with it
+pub(crate) async fn override_threshold(
+    network: &Network,
+    safe: Address,
+    threshold: u64,
+) -> Result<()> {
+    if !network.is_dev().await? {
+        return Err(eyre!(
+            "Safe thresholds can only be overridden on dev networks"
+        ));
+    }
+
+    let provider = network.get_alloy_provider().await?;
+
+    let owners = ISafe::new(safe, &provider).getOwners().call().await?;
+    if !(1..=owners.len() as u64).contains(&threshold) {
+        return Err(eyre!(
+            "threshold must be between 1 and {}, the number of owners",
+            owners.len()
+        ));
+    }
+
+    provider
+        .client()
+        .request::<_, Json>(
+            "anvil_setStorageAt",
+            (safe, THRESHOLD_SLOT, B256::from(U256::from(threshold))),
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::address;
+
+    use super::*;
+
+    const SAFE: Address = address!("0x000000000000000000000000000000000000dEaD");
+
+    #[test]
+    fn domain_includes_chain_id_from_1_3_0() {
+        for version in ["1.3.0", "1.3.0+L2", "1.4.1", "unknown"] {
+            let domain = versioned_domain(version, 1, SAFE);
+
+            assert_eq!(domain.chain_id, Some(U256::from(1)), "{version}");
+            assert_eq!(domain.verifying_contract, Some(SAFE));
+        }
+    }
+
+    #[test]
+    fn domain_excludes_chain_id_before_1_3_0() {
+        for version in ["1.0.0", "1.1.1", "1.2.0"] {
+            let domain = versioned_domain(version, 1, SAFE);
+
+            assert_eq!(domain.chain_id, None, "{version}");
+            assert_eq!(domain.verifying_contract, Some(SAFE));
+        }
+    }
+}
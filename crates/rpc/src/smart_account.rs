@@ -0,0 +1,342 @@
+//! ERC-4337 smart accounts
+//!
+//! Transactions sent from a smart account are turned into (v0.7) user operations, signed by the
+//! account's owner, and submitted to the wallet's bundler
+
+use alloy::{
+    network::TransactionBuilder as _,
+    primitives::{TxKind, aliases::U192, bytes},
+    providers::{Provider as _, ProviderBuilder},
+    rpc::types::TransactionRequest,
+    signers::Signer as _,
+    sol_types::SolCall as _,
+};
+use ethui_abis::{IEntryPoint, ISimpleAccount, ISimpleAccountFactory, PackedUserOperation};
+use ethui_types::prelude::*;
+use ethui_wallets::{SmartAccountWallet, WalletControl as _};
+
+/// How long to wait for the bundler to include a user operation, before giving up on a transaction
+/// hash and handing out the operation's hash instead
+const RECEIPT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Stand-in signature for gas estimation. It must still be a well-formed ECDSA signature, since
+/// accounts recover it during validation
+const DUMMY_SIGNATURE: Bytes = bytes!(
+    "fffffffffffffffffffffffffffffff0000000000000000000000000000000007aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa1c"
+);
+
+/// A user operation, in the unpacked form bundlers expect over RPC
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UserOperation {
+    pub sender: Address,
+    pub nonce: U256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub factory: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub factory_data: Option<Bytes>,
+    pub call_data: Bytes,
+    pub call_gas_limit: U256,
+    pub verification_gas_limit: U256,
+    pub pre_verification_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paymaster: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paymaster_verification_gas_limit: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paymaster_post_op_gas_limit: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paymaster_data: Option<Bytes>,
+    pub signature: Bytes,
+}
+
+/// Gas limits, as returned by `eth_estimateUserOperationGas`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GasEstimate {
+    pre_verification_gas: U256,
+    verification_gas_limit: U256,
+    call_gas_limit: U256,
+    paymaster_verification_gas_limit: Option<U256>,
+    paymaster_post_op_gas_limit: Option<U256>,
+}
+
+impl UserOperation {
+    /// Packs the operation the way the EntryPoint hashes it
+    fn pack(&self) -> PackedUserOperation {
+        let init_code = match (self.factory, &self.factory_data) {
+            (Some(factory), Some(data)) => [factory.as_slice(), data].concat().into(),
+            _ => Bytes::new(),
+        };
+
+        let paymaster_and_data = match self.paymaster {
+            Some(paymaster) => [
+                paymaster.as_slice(),
+                &u128_bytes(self.paymaster_verification_gas_limit.unwrap_or_default()),
+                &u128_bytes(self.paymaster_post_op_gas_limit.unwrap_or_default()),
+                self.paymaster_data.as_deref().unwrap_or_default(),
+            ]
+            .concat()
+            .into(),
+            None => Bytes::new(),
+        };
+
+        PackedUserOperation {
+            sender: self.sender,
+            nonce: self.nonce,
+            initCode: init_code,
+            callData: self.call_data.clone(),
+            accountGasLimits: pack_u128s(self.verification_gas_limit, self.call_gas_limit),
+            preVerificationGas: self.pre_verification_gas,
+            gasFees: pack_u128s(self.max_priority_fee_per_gas, self.max_fee_per_gas),
+            paymasterAndData: paymaster_and_data,
+            signature: self.signature.clone(),
+        }
+    }
+}
+
+/// Builds an (unsigned) user operation for `request`, with gas limits estimated by the bundler
+pub(crate) async fn prepare(
+    network: &Network,
+    wallet: &SmartAccountWallet,
+    request: &TransactionRequest,
+) -> Result<UserOperation> {
+    let Some(TxKind::Call(to)) = request.to else {
+        return Err(eyre!("Smart accounts cannot deploy contracts directly"));
+    };
+
+    let provider = network.get_alloy_provider().await?;
+    let sender = wallet.address;
+
+    // the first operation deploys the account
+    let (factory, factory_data) = if provider.get_code_at(sender).await?.is_empty() {
+        let data = ISimpleAccountFactory::createAccountCall {
+            owner: wallet.owner,
+            salt: wallet.salt,
+        }
+        .abi_encode();
+        (Some(wallet.factory), Some(data.into()))
+    } else {
+        (None, None)
+    };
+
+    let nonce = IEntryPoint::new(wallet.entry_point, &provider)
+        .getNonce(sender, U192::ZERO)
+        .call()
+        .await?;
+
+    let call_data = ISimpleAccount::executeCall {
+        dest: to,
+        value: request.value.unwrap_or_default(),
+        func: request.input().cloned().unwrap_or_default(),
+    }
+    .abi_encode();
+
+    let fees = provider.estimate_eip1559_fees().await?;
+
+    let mut op = UserOperation {
+        sender,
+        nonce,
+        factory,
+        factory_data,
+        call_data: call_data.into(),
+        max_fee_per_gas: U256::from(fees.max_fee_per_gas),
+        max_priority_fee_per_gas: U256::from(fees.max_priority_fee_per_gas),
+        paymaster: wallet.paymaster,
+        paymaster_data: wallet.paymaster.map(|_| wallet.paymaster_data.clone()),
+        signature: DUMMY_SIGNATURE,
+        ..Default::default()
+    };
+
+    let estimate: GasEstimate = bundler(wallet)
+        .await?
+        .client()
+        .request("eth_estimateUserOperationGas", (&op, wallet.entry_point))
+        .await?;
+
+    op.pre_verification_gas = estimate.pre_verification_gas;
+    op.verification_gas_limit = estimate.verification_gas_limit;
+    op.call_gas_limit = estimate.call_gas_limit;
+    if op.paymaster.is_some() {
+        op.paymaster_verification_gas_limit = estimate.paymaster_verification_gas_limit;
+        op.paymaster_post_op_gas_limit = estimate.paymaster_post_op_gas_limit;
+    }
+
+    Ok(op)
+}
+
+/// Signs the operation with the account's owner
+pub(crate) async fn sign(
+    network: &Network,
+    wallet: &SmartAccountWallet,
+    mut op: UserOperation,
+) -> Result<UserOperation> {
+    let provider = network.get_alloy_provider().await?;
+    let hash = IEntryPoint::new(wallet.entry_point, &provider)
+        .getUserOpHash(op.pack())
+        .call()
+        .await?;
+
+    let (owner, path) = ethui_wallets::find_wallet(wallet.owner)
+        .await
+        .with_context(|| format!("owner {} is not an ethui account", wallet.owner))?;
+    let signer = owner.build_signer(network.chain_id(), &path).await?;
+
+    // SimpleAccount expects an EIP-191 signature of the hash
+    op.signature = signer
+        .sign_message(hash.as_slice())
+        .await?
+        .as_bytes()
+        .into();
+
+    Ok(op)
+}
+
+/// Submits the operation to the bundler, returning its hash
+pub(crate) async fn submit(wallet: &SmartAccountWallet, op: UserOperation) -> Result<B256> {
+    Ok(bundler(wallet)
+        .await?
+        .client()
+        .request("eth_sendUserOperation", (op, wallet.entry_point))
+        .await?)
+}
+
+/// Waits for the transaction that includes the operation. `None` if it isn't included in time
+pub(crate) async fn wait(wallet: &SmartAccountWallet, op_hash: B256) -> Result<Option<B256>> {
+    let bundler = bundler(wallet).await?;
+
+    let receipt = tokio::time::timeout(RECEIPT_TIMEOUT, async {
+        loop {
+            let receipt: Option<Json> = bundler
+                .client()
+                .request("eth_getUserOperationReceipt", (op_hash,))
+                .await?;
+
+            match receipt {
+                Some(receipt) => return Ok::<_, color_eyre::Report>(receipt),
+                None => tokio::time::sleep(Duration::from_secs(1)).await,
+            }
+        }
+    })
+    .await;
+
+    let Ok(receipt) = receipt else {
+        return Ok(None);
+    };
+
+    Ok(Some(serde_json::from_value(
+        receipt?["receipt"]["transactionHash"].clone(),
+    )?))
+}
+
+async fn bundler(wallet: &SmartAccountWallet) -> Result<RootProvider<Ethereum>> {
+    Ok(ProviderBuilder::new()
+        .disable_recommended_fillers()
+        .connect(wallet.bundler_url.as_str())
+        .await?)
+}
+
+/// Two 128-bit values packed into a single word, as the EntryPoint does with gas params
+fn pack_u128s(high: U256, low: U256) -> B256 {
+    let mut word = [0u8; 32];
+    word[..16].copy_from_slice(&u128_bytes(high));
+    word[16..].copy_from_slice(&u128_bytes(low));
+    word.into()
+}
+
+fn u128_bytes(value: U256) -> [u8; 16] {
+    value.saturating_to::<u128>().to_be_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::b256;
+
+    use super::*;
+
+    #[test]
+    fn pack_u128s_puts_high_first() {
+        let word = pack_u128s(U256::from(1), U256::from(2));
+
+        assert_eq!(
+            word,
+            b256!("0x0000000000000000000000000000000100000000000000000000000000000002")
+        );
+    }
+
+    #[test]
+    fn pack_u128s_saturates() {
+        let word = pack_u128s(U256::MAX, U256::ZERO);
+
+        assert_eq!(
+            word,
+            b256!("0xffffffffffffffffffffffffffffffff00000000000000000000000000000000")
+        );
+    }
+
+    #[test]
+    fn pack_without_factory_or_paymaster() {
+        let op = UserOperation {
+            sender: Address::repeat_byte(1),
+            nonce: U256::from(7),
+            call_data: bytes!("0xdeadbeef"),
+            call_gas_limit: U256::from(100),
+            verification_gas_limit: U256::from(200),
+            pre_verification_gas: U256::from(300),
+            max_fee_per_gas: U256::from(10),
+            max_priority_fee_per_gas: U256::from(1),
+            signature: DUMMY_SIGNATURE,
+            ..Default::default()
+        };
+
+        let packed = op.pack();
+
+        assert_eq!(packed.sender, op.sender);
+        assert_eq!(packed.nonce, op.nonce);
+        assert!(packed.initCode.is_empty());
+        assert_eq!(packed.callData, op.call_data);
+        assert_eq!(
+            packed.accountGasLimits,
+            pack_u128s(U256::from(200), U256::from(100))
+        );
+        assert_eq!(packed.preVerificationGas, U256::from(300));
+        assert_eq!(packed.gasFees, pack_u128s(U256::from(1), U256::from(10)));
+        assert!(packed.paymasterAndData.is_empty());
+        assert_eq!(packed.signature, DUMMY_SIGNATURE);
+    }
+
+    #[test]
+    fn pack_with_factory_and_paymaster() {
+        let factory = Address::repeat_byte(2);
+        let paymaster = Address::repeat_byte(3);
+
+        let op = UserOperation {
+            factory: Some(factory),
+            factory_data: Some(bytes!("0x1234")),
+            paymaster: Some(paymaster),
+            paymaster_verification_gas_limit: Some(U256::from(5)),
+            paymaster_post_op_gas_limit: Some(U256::from(6)),
+            paymaster_data: Some(bytes!("0xabcd")),
+            ..Default::default()
+        };
+
+        let packed = op.pack();
+
+        assert_eq!(
+            packed.initCode,
+            Bytes::from([factory.as_slice(), &[0x12, 0x34]].concat())
+        );
+
+        // paymaster, then both of its gas limits as 16 bytes each, then its data
+        let expected = [
+            paymaster.as_slice(),
+            &5u128.to_be_bytes(),
+            &6u128.to_be_bytes(),
+            &[0xab, 0xcd],
+        ]
+        .concat();
+        assert_eq!(packed.paymasterAndData, Bytes::from(expected));
+    }
+}
@@ -0,0 +1,430 @@
+//! Decoding of well-known EIP-712 structures, for review before signing
+//!
+//! Permits and orders are the usual way a single signature drains a wallet, so they're summarized
+//! in terms of which tokens can be moved, by whom, and until when, along with warnings for the
+//! riskier cases
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use alloy::dyn_abi::TypedData;
+use ethui_types::prelude::*;
+
+/// Permit2, deployed at the same address on every chain
+const PERMIT2: Address = address!("0x000000000022D473030F116dDEE9F6B43aC78BA3");
+
+/// Deadlines further out than this are flagged
+const FAR_FUTURE: Duration = Duration::from_secs(90 * 24 * 60 * 60);
+
+/// `type(uint160).max`, the largest Permit2 amount. Anything above it is effectively unlimited too
+const UNLIMITED: U256 = U256::from_limbs([u64::MAX, u64::MAX, u32::MAX as u64, 0]);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum TypedDataKind {
+    /// ERC-2612 `Permit`, or DAI's older variant of it
+    Permit,
+    Permit2Single,
+    Permit2Batch,
+    Permit2TransferFrom,
+    SeaportOrder,
+    SafeTx,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TypedDataSummary {
+    pub kind: TypedDataKind,
+    /// who gets to move the tokens, or the Safe transaction's target
+    pub spender: Option<Address>,
+    /// tokens put at stake by the signature
+    pub tokens: Vec<TokenAmount>,
+    /// tokens received in return, for orders
+    pub receives: Vec<TokenAmount>,
+    /// unix timestamp until which the signature, or the allowance it grants, is valid
+    pub deadline: Option<u64>,
+    pub warnings: Vec<Warning>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TokenAmount {
+    pub token: Address,
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+    pub amount: U256,
+    /// for NFTs
+    pub token_id: Option<U256>,
+    pub unlimited: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub(crate) enum Warning {
+    UnlimitedAmount {
+        token: Address,
+    },
+    FarFutureDeadline {
+        deadline: u64,
+    },
+    /// an order where the signer gets nothing back
+    NothingInReturn,
+    /// a Safe transaction that runs another contract's code in the Safe's context
+    DelegateCall {
+        to: Address,
+    },
+}
+
+/// Summarizes `data`, if it's one of the known structures
+pub(crate) async fn summarize(data: &TypedData, chain_id: u64) -> Option<TypedDataSummary> {
+    let mut summary = decode(data)?;
+
+    for token in summary.tokens.iter_mut().chain(summary.receives.iter_mut()) {
+        if let Ok(metadata) = ethui_db::get()
+            .get_erc20_metadata(token.token, chain_id)
+            .await
+        {
+            token.symbol = metadata.symbol;
+            token.decimals = metadata.decimals;
+        }
+    }
+
+    summary.warnings.extend(
+        summary
+            .tokens
+            .iter()
+            .filter(|t| t.unlimited)
+            .map(|t| Warning::UnlimitedAmount { token: t.token }),
+    );
+
+    if let Some(deadline) = summary.deadline
+        && is_far_future(deadline)
+    {
+        summary
+            .warnings
+            .push(Warning::FarFutureDeadline { deadline });
+    }
+
+    Some(summary)
+}
+
+fn decode(data: &TypedData) -> Option<TypedDataSummary> {
+    let msg = &data.message;
+    let domain = &data.domain;
+    let primary_type = data.primary_type.as_str();
+
+    if domain.verifying_contract == Some(PERMIT2) {
+        return decode_permit2(primary_type, msg);
+    }
+
+    if domain.name.as_deref() == Some("Seaport") && primary_type == "OrderComponents" {
+        return decode_seaport(msg);
+    }
+
+    match primary_type {
+        "Permit" => decode_permit(domain.verifying_contract?, msg),
+        "SafeTx" => decode_safe_tx(msg),
+        _ => None,
+    }
+}
+
+/// ERC-2612, and DAI's `allowed` flavour
+fn decode_permit(token: Address, msg: &Json) -> Option<TypedDataSummary> {
+    let (amount, deadline) = match msg.get("allowed") {
+        Some(allowed) => {
+            let amount = if allowed.as_bool()? {
+                U256::MAX
+            } else {
+                U256::ZERO
+            };
+            (amount, uint(&msg["expiry"]))
+        }
+        None => (uint(&msg["value"])?, uint(&msg["deadline"])),
+    };
+
+    Some(TypedDataSummary {
+        kind: TypedDataKind::Permit,
+        spender: addr(&msg["spender"]),
+        tokens: vec![TokenAmount::new(token, amount)],
+        receives: vec![],
+        deadline: deadline.map(timestamp),
+        warnings: vec![],
+    })
+}
+
+fn decode_permit2(primary_type: &str, msg: &Json) -> Option<TypedDataSummary> {
+    let spender = addr(&msg["spender"]);
+
+    let (kind, tokens, deadline) = match primary_type {
+        // allowances, which can outlive the signature itself, so the latest expiration counts
+        "PermitSingle" | "PermitBatch" => {
+            let details = match &msg["details"] {
+                Json::Array(details) => details.clone(),
+                details => vec![details.clone()],
+            };
+
+            let tokens = details
+                .iter()
+                .map(|d| Some(TokenAmount::new(addr(&d["token"])?, uint(&d["amount"])?)))
+                .collect::<Option<Vec<_>>>()?;
+
+            let deadline = details
+                .iter()
+                .filter_map(|d| uint(&d["expiration"]))
+                .chain(uint(&msg["sigDeadline"]))
+                .max();
+
+            let kind = if primary_type == "PermitSingle" {
+                TypedDataKind::Permit2Single
+            } else {
+                TypedDataKind::Permit2Batch
+            };
+
+            (kind, tokens, deadline)
+        }
+
+        // one-off transfers, including the witness variants
+        t if t.contains("TransferFrom") => {
+            let permitted = match &msg["permitted"] {
+                Json::Array(permitted) => permitted.clone(),
+                permitted => vec![permitted.clone()],
+            };
+
+            let tokens = permitted
+                .iter()
+                .map(|p| Some(TokenAmount::new(addr(&p["token"])?, uint(&p["amount"])?)))
+                .collect::<Option<Vec<_>>>()?;
+
+            (
+                TypedDataKind::Permit2TransferFrom,
+                tokens,
+                uint(&msg["deadline"]),
+            )
+        }
+
+        _ => return None,
+    };
+
+    Some(TypedDataSummary {
+        kind,
+        spender,
+        tokens,
+        receives: vec![],
+        deadline: deadline.map(timestamp),
+        warnings: vec![],
+    })
+}
+
+fn decode_seaport(msg: &Json) -> Option<TypedDataSummary> {
+    let offerer = addr(&msg["offerer"])?;
+
+    let tokens = msg["offer"]
+        .as_array()?
+        .iter()
+        .filter_map(seaport_item)
+        .collect();
+
+    // consideration items going elsewhere are fees and royalties
+    let receives: Vec<_> = msg["consideration"]
+        .as_array()?
+        .iter()
+        .filter(|item| addr(&item["recipient"]) == Some(offerer))
+        .filter_map(seaport_item)
+        .collect();
+
+    let mut warnings = vec![];
+    if receives.iter().all(|t| t.amount.is_zero()) {
+        warnings.push(Warning::NothingInReturn);
+    }
+
+    Some(TypedDataSummary {
+        kind: TypedDataKind::SeaportOrder,
+        spender: addr(&msg["zone"]).filter(|zone| !zone.is_zero()),
+        tokens,
+        receives,
+        deadline: uint(&msg["endTime"]).map(timestamp),
+        warnings,
+    })
+}
+
+fn seaport_item(item: &Json) -> Option<TokenAmount> {
+    let item_type = uint(&item["itemType"])?;
+    let mut token = TokenAmount::new(addr(&item["token"])?, uint(&item["startAmount"])?);
+
+    // ERC721 & ERC1155, with or without criteria
+    if item_type >= U256::from(2) {
+        token.token_id = uint(&item["identifierOrCriteria"]);
+        token.unlimited = false;
+    }
+
+    Some(token)
+}
+
+fn decode_safe_tx(msg: &Json) -> Option<TypedDataSummary> {
+    let to = addr(&msg["to"])?;
+
+    let mut warnings = vec![];
+    if uint(&msg["operation"]) == Some(U256::from(1)) {
+        warnings.push(Warning::DelegateCall { to });
+    }
+
+    Some(TypedDataSummary {
+        kind: TypedDataKind::SafeTx,
+        spender: Some(to),
+        tokens: vec![],
+        receives: vec![],
+        deadline: None,
+        warnings,
+    })
+}
+
+impl TokenAmount {
+    fn new(token: Address, amount: U256) -> Self {
+        Self {
+            token,
+            symbol: None,
+            decimals: None,
+            amount,
+            token_id: None,
+            unlimited: amount >= UNLIMITED,
+        }
+    }
+}
+
+/// Integers come as JSON numbers, or as decimal or hex strings
+fn uint(value: &Json) -> Option<U256> {
+    match value {
+        Json::Number(n) => n.as_u64().map(U256::from),
+        Json::String(s) => U256::from_str(s).ok(),
+        _ => None,
+    }
+}
+
+fn addr(value: &Json) -> Option<Address> {
+    value.as_str()?.parse().ok()
+}
+
+fn timestamp(value: U256) -> u64 {
+    value.saturating_to()
+}
+
+fn is_far_future(deadline: u64) -> bool {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+
+    Duration::from_secs(deadline) > now + FAR_FUTURE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USDC: Address = address!("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+    const DAI: Address = address!("0x6B175474E89094C44Da98b954EedeAC495271d0F");
+    const WETH: Address = address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+    const SPENDER: Address = address!("0x3fC91A3afd70395Cd496C647d5a6CC9D4B2b7FAD");
+
+    fn fixture(json: &str) -> TypedDataSummary {
+        let data: TypedData = serde_json::from_str(json).unwrap();
+        decode(&data).unwrap()
+    }
+
+    #[test]
+    fn permit() {
+        let summary = fixture(include_str!("../fixtures/typed_data/permit.json"));
+
+        assert_eq!(summary.kind, TypedDataKind::Permit);
+        assert_eq!(summary.spender, Some(SPENDER));
+        assert_eq!(summary.tokens.len(), 1);
+        assert_eq!(summary.tokens[0].token, USDC);
+        assert_eq!(summary.tokens[0].amount, U256::MAX);
+        assert!(summary.tokens[0].unlimited);
+        assert_eq!(summary.deadline, Some(1700000000));
+    }
+
+    #[test]
+    fn dai_permit() {
+        let summary = fixture(include_str!("../fixtures/typed_data/dai_permit.json"));
+
+        // `allowed: true` grants the whole balance
+        assert_eq!(summary.kind, TypedDataKind::Permit);
+        assert_eq!(summary.spender, Some(SPENDER));
+        assert_eq!(summary.tokens[0].token, DAI);
+        assert!(summary.tokens[0].unlimited);
+        assert_eq!(summary.deadline, Some(1700000000));
+    }
+
+    #[test]
+    fn permit2_single() {
+        let summary = fixture(include_str!("../fixtures/typed_data/permit2_single.json"));
+
+        assert_eq!(summary.kind, TypedDataKind::Permit2Single);
+        assert_eq!(summary.spender, Some(SPENDER));
+        assert_eq!(summary.tokens[0].token, USDC);
+        assert_eq!(summary.tokens[0].amount, UNLIMITED);
+        assert!(summary.tokens[0].unlimited);
+        // the allowance's expiration outlives the signature's deadline
+        assert_eq!(summary.deadline, Some(1700000000));
+    }
+
+    #[test]
+    fn permit2_batch() {
+        let summary = fixture(include_str!("../fixtures/typed_data/permit2_batch.json"));
+
+        assert_eq!(summary.kind, TypedDataKind::Permit2Batch);
+        assert_eq!(
+            summary.tokens.iter().map(|t| t.token).collect::<Vec<_>>(),
+            vec![USDC, WETH]
+        );
+        assert!(summary.tokens.iter().all(|t| !t.unlimited));
+        assert_eq!(summary.deadline, Some(1710000000));
+    }
+
+    #[test]
+    fn permit2_transfer_from() {
+        let summary = fixture(include_str!(
+            "../fixtures/typed_data/permit2_transfer_from.json"
+        ));
+
+        assert_eq!(summary.kind, TypedDataKind::Permit2TransferFrom);
+        assert_eq!(summary.spender, Some(SPENDER));
+        assert_eq!(summary.tokens[0].token, WETH);
+        assert_eq!(
+            summary.tokens[0].amount,
+            U256::from(1_000_000_000_000_000_000u64)
+        );
+        assert_eq!(summary.deadline, Some(1700000000));
+    }
+
+    #[test]
+    fn seaport_order_with_nothing_in_return() {
+        let summary = fixture(include_str!(
+            "../fixtures/typed_data/seaport_nothing_in_return.json"
+        ));
+
+        assert_eq!(summary.kind, TypedDataKind::SeaportOrder);
+        // no zone
+        assert_eq!(summary.spender, None);
+        assert_eq!(summary.tokens.len(), 1);
+        assert_eq!(summary.tokens[0].token_id, Some(U256::from(1234)));
+        // the only consideration item is a fee, going to someone else
+        assert!(summary.receives.is_empty());
+        assert_eq!(summary.warnings, vec![Warning::NothingInReturn]);
+        assert_eq!(summary.deadline, Some(1700000000));
+    }
+
+    #[test]
+    fn safe_tx_delegatecall() {
+        let summary = fixture(include_str!(
+            "../fixtures/typed_data/safe_tx_delegatecall.json"
+        ));
+        let multisend = address!("0x40A2aCCbd92BCA938b02010E17A5b8929b49130D");
+
+        assert_eq!(summary.kind, TypedDataKind::SafeTx);
+        assert_eq!(summary.spender, Some(multisend));
+        assert_eq!(
+            summary.warnings,
+            vec![Warning::DelegateCall { to: multisend }]
+        );
+    }
+}
@@ -1,6 +1,10 @@
 use alloy::providers::Provider as _;
+use ethui_connections::Ctx;
 use ethui_networks::NetworksActorExt as _;
 use ethui_types::prelude::*;
+use ethui_wallets::WalletControl as _;
+
+use crate::{Error, Result};
 
 /// Get the current network from the networks actor
 pub(crate) async fn get_current_network() -> Network {
@@ -20,3 +24,44 @@ pub(crate) async fn get_code(address: Address, chain_id: u64) -> color_eyre::Res
         Ok(Some(code))
     }
 }
+
+/// Accounts the connection was granted access to, with its selected one first. Empty if no access
+/// was ever granted
+///
+/// The selected account is the one pinned to the domain, or the current wallet's otherwise
+pub(crate) async fn exposed_accounts(ctx: &Ctx) -> Vec<Address> {
+    let mut accounts = ctx.exposed_accounts().await.unwrap_or_default();
+
+    let selected = match ctx.account().await {
+        Some(account) => account,
+        None => current_address().await,
+    };
+
+    if let Some(i) = accounts.iter().position(|a| *a == selected) {
+        accounts[..=i].rotate_right(1);
+    }
+
+    accounts
+}
+
+/// Account to act on behalf of when a request doesn't specify one. `None` if the connection was
+/// never granted access to any
+pub(crate) async fn default_account(ctx: &Ctx) -> Option<Address> {
+    exposed_accounts(ctx).await.first().copied()
+}
+
+/// Fails unless the connection was granted access to `account`
+pub(crate) async fn authorize(ctx: &Ctx, account: Address) -> Result<()> {
+    if exposed_accounts(ctx).await.contains(&account) {
+        Ok(())
+    } else {
+        Err(Error::Unauthorized)
+    }
+}
+
+async fn current_address() -> Address {
+    ethui_wallets::get_current_wallet()
+        .await
+        .get_current_address()
+        .await
+}
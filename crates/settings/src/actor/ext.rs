@@ -2,7 +2,7 @@ use ethui_types::prelude::*;
 use kameo::actor::ActorRef;
 
 use super::{Set, SetValue, SettingsActor};
-use crate::{DarkMode, Settings, onboarding::OnboardingStep};
+use crate::{DarkMode, LockPolicy, Settings, onboarding::OnboardingStep};
 
 #[allow(async_fn_in_trait)]
 pub trait SettingsActorExt {
@@ -15,6 +15,7 @@ pub trait SettingsActorExt {
     async fn finish_onboarding_step(&self, step: OnboardingStep) -> Result<()>;
     async fn set_alias(&self, address: Address, alias: Option<String>) -> Result<()>;
     async fn set_run_local_stacks(&self, enabled: bool) -> Result<()>;
+    async fn set_lock_policy(&self, wallet: String, policy: Option<LockPolicy>) -> Result<()>;
 }
 
 impl SettingsActorExt for ActorRef<SettingsActor> {
@@ -81,4 +82,12 @@ impl SettingsActorExt for ActorRef<SettingsActor> {
         .await?;
         Ok(())
     }
+
+    async fn set_lock_policy(&self, wallet: String, policy: Option<LockPolicy>) -> Result<()> {
+        self.ask(Set {
+            value: SetValue::LockPolicy(wallet, policy),
+        })
+        .await?;
+        Ok(())
+    }
 }
@@ -11,7 +11,7 @@ pub use ext::SettingsActorExt;
 use kameo::prelude::*;
 
 use crate::{
-    DarkMode, Settings, migrations::load_and_migrate, onboarding::OnboardingStep,
+    DarkMode, LockPolicy, Settings, migrations::load_and_migrate, onboarding::OnboardingStep,
     test_alchemy_api_key, utils::test_etherscan_api_key,
 };
 
@@ -73,6 +73,7 @@ pub(crate) enum SetValue {
     FinishOnboarding,
     Alias(ethui_types::Address, Option<String>),
     RunLocalStacks(bool),
+    LockPolicy(String, Option<LockPolicy>),
 }
 
 #[messages]
@@ -185,6 +186,12 @@ impl SettingsActor {
                 if let Some(v) = map.get("runLocalStacks") {
                     self.inner.run_local_stacks = serde_json::from_value(v.clone()).unwrap();
                 }
+                if let Some(v) = map.get("persistRpcHistory") {
+                    self.inner.persist_rpc_history = serde_json::from_value(v.clone()).unwrap();
+                }
+                if let Some(v) = map.get("redactRpcHistory") {
+                    self.inner.redact_rpc_history = serde_json::from_value(v.clone()).unwrap();
+                }
             }
             SetValue::DarkMode(mode) => {
                 self.inner.dark_mode = mode;
@@ -210,6 +217,13 @@ impl SettingsActor {
             SetValue::RunLocalStacks(mode) => {
                 self.inner.run_local_stacks = mode;
             }
+            SetValue::LockPolicy(wallet, policy) => {
+                if let Some(policy) = policy {
+                    self.inner.lock_policies.insert(wallet, policy);
+                } else {
+                    self.inner.lock_policies.remove(&wallet);
+                }
+            }
         }
 
         self.save().await?;
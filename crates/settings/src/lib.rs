@@ -2,6 +2,7 @@ pub mod actor;
 mod autostart;
 pub mod commands;
 mod init;
+mod lock_policy;
 mod migrations;
 pub mod onboarding;
 mod utils;
@@ -9,6 +10,7 @@ mod utils;
 pub use actor::*;
 use ethui_types::prelude::*;
 pub use init::init;
+pub use lock_policy::LockPolicy;
 use migrations::LatestVersion;
 use onboarding::Onboarding;
 pub use onboarding::OnboardingStep;
@@ -63,6 +65,19 @@ pub struct Settings {
     #[serde(default)]
     pub onboarding: Onboarding,
 
+    /// Also persist inspected RPC calls to the database, rather than only keeping the most recent
+    /// ones in memory
+    #[serde(default)]
+    pub persist_rpc_history: bool,
+
+    /// Redact sensitive payloads (signatures, signed transactions) from inspected RPC calls
+    #[serde(default = "default_true")]
+    pub redact_rpc_history: bool,
+
+    /// Lock policy of each wallet, by name. Wallets not listed here use the default one
+    #[serde(default)]
+    pub lock_policies: HashMap<String, LockPolicy>,
+
     version: LatestVersion,
 }
 
@@ -84,10 +99,19 @@ impl Default for Settings {
             onboarding: Onboarding::default(),
             run_local_stacks: false,
             check_for_updates: true,
+            persist_rpc_history: false,
+            redact_rpc_history: true,
+            lock_policies: HashMap::new(),
         }
     }
 }
 
+impl Settings {
+    pub fn lock_policy(&self, wallet: &str) -> LockPolicy {
+        self.lock_policies.get(wallet).cloned().unwrap_or_default()
+    }
+}
+
 const fn default_true() -> bool {
     true
 }
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// When an unlocked wallet forgets its secret, and has to ask for the password again
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum LockPolicy {
+    /// a fixed time after being unlocked
+    Timeout { secs: u64 },
+
+    /// after not being used for a while. every signature resets the timer
+    Idle { secs: u64 },
+
+    /// when the system goes to sleep. only detected on resume, since there's no portable way to be
+    /// notified of it (or of screen locks)
+    OnSleep,
+
+    /// never. meant for wallets holding throwaway keys for dev networks
+    Never,
+}
+
+impl Default for LockPolicy {
+    fn default() -> Self {
+        Self::Timeout { secs: 60 }
+    }
+}
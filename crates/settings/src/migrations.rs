@@ -206,6 +206,9 @@ fn run_migrations(settings: Versions) -> Settings {
                     run_local_stacks: false,
                     check_for_updates: true,
                     walletconnect_project_id: None,
+                    persist_rpc_history: false,
+                    redact_rpc_history: true,
+                    lock_policies: Default::default(),
                 });
             }
 
@@ -2,7 +2,7 @@ use ethui_db::Db;
 use ethui_networks::{NetworksActorExt as _, networks};
 use ethui_types::prelude::*;
 
-use crate::types::{Request, SimResult};
+use crate::types::{BundleResult, Request, SimResult};
 
 #[tauri::command]
 pub async fn simulator_run(chain_id: u64, request: Request) -> TauriResult<SimResult> {
@@ -14,6 +14,19 @@ pub async fn simulator_run(chain_id: u64, request: Request) -> TauriResult<SimRe
     Ok(crate::simulate_once(request, network.http_url.to_string(), None).await?)
 }
 
+#[tauri::command]
+pub async fn simulator_run_bundle(
+    chain_id: u64,
+    requests: Vec<Request>,
+) -> TauriResult<BundleResult> {
+    let network = networks()
+        .get(chain_id)
+        .await?
+        .with_context(|| "Network not found")?;
+
+    Ok(crate::simulate_bundle(requests, network.http_url.to_string(), None).await?)
+}
+
 #[tauri::command]
 pub async fn simulator_get_call_count(
     chain_id: u64,
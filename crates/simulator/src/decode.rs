@@ -0,0 +1,192 @@
+use alloy::{
+    dyn_abi::{DynSolValue, EventExt as _, JsonAbiExt as _},
+    hex,
+    json_abi::{JsonAbi, Param},
+    primitives::LogData,
+};
+use ethui_sol_artifacts::{SolArtifactsActorExt as _, actor::try_sol_artifacts};
+use ethui_types::prelude::*;
+use revm::state::EvmState;
+
+use crate::{
+    revert,
+    types::{CallFrame, DecodedCall, DecodedEvent, DecodedParam},
+};
+
+pub(crate) struct KnownAbi {
+    /// contract name, when matched against a local artifact
+    pub name: Option<String>,
+    pub abi: JsonAbi,
+}
+
+/// Looks up the ABIs of every contract whose code ran in `trace`
+pub(crate) async fn load_abis(
+    trace: &CallFrame,
+    chain_id: u64,
+    state: &EvmState,
+) -> HashMap<Address, KnownAbi> {
+    let mut addresses = HashSet::new();
+    collect_addresses(trace, &mut addresses);
+
+    let mut abis = HashMap::new();
+    for address in addresses {
+        let code = state
+            .get(&address)
+            .and_then(|a| a.info.code.as_ref())
+            .map(|c| c.original_bytes());
+
+        if let Some(abi) = lookup(address, chain_id, code).await {
+            abis.insert(address, abi);
+        }
+    }
+
+    abis
+}
+
+fn collect_addresses(frame: &CallFrame, addresses: &mut HashSet<Address>) {
+    addresses.insert(frame.to);
+    for call in frame.calls.iter() {
+        collect_addresses(call, addresses);
+    }
+}
+
+/// Known contracts come first, then local artifacts matching the contract's code
+pub(crate) async fn lookup(
+    address: Address,
+    chain_id: u64,
+    code: Option<Bytes>,
+) -> Option<KnownAbi> {
+    if let Ok(abi) = ethui_db::get()
+        .get_contract_impl_abi(chain_id, address)
+        .await
+    {
+        return Some(KnownAbi { name: None, abi });
+    }
+
+    let artifact = try_sol_artifacts()
+        .ok()?
+        .get_abi_for(code.filter(|c| !c.is_empty())?)
+        .await
+        .ok()??;
+
+    Some(KnownAbi {
+        name: Some(artifact.name),
+        abi: serde_json::from_value(artifact.abi).ok()?,
+    })
+}
+
+/// Decodes the calls, logs and reverts in `trace` with the given ABIs
+pub(crate) fn decode_trace(frame: &mut CallFrame, abis: &HashMap<Address, KnownAbi>) {
+    // delegate calls run the callee's code, so its events come from the callee's ABI too
+    let known = abis.get(&frame.to);
+
+    if let Some(known) = known {
+        frame.contract = known.name.clone();
+        frame.function = decode_call(&known.abi, &frame.input, &frame.output);
+
+        for log in frame.logs.iter_mut() {
+            log.event = decode_event(&known.abi, &log.log.data);
+        }
+    }
+
+    // errors bubble up from inner calls, so any of the ABIs may be the one declaring it
+    if !frame.success {
+        frame.revert_reason =
+            revert::decode_with(&frame.output, known.into_iter().chain(abis.values()))
+                .map(|r| r.to_string());
+    }
+
+    for call in frame.calls.iter_mut() {
+        decode_trace(call, abis);
+    }
+}
+
+fn decode_call(abi: &JsonAbi, input: &Bytes, output: &Bytes) -> Option<DecodedCall> {
+    let selector = input.get(..4)?;
+    let function = abi
+        .functions()
+        .find(|f| f.selector().as_slice() == selector)?;
+    let inputs = function.abi_decode_input(&input[4..]).ok()?;
+
+    // reverted calls have no output to decode
+    let outputs = function.abi_decode_output(output).unwrap_or_default();
+
+    Some(DecodedCall {
+        signature: function.signature(),
+        inputs: params(&function.inputs, &inputs),
+        outputs: params(&function.outputs, &outputs),
+    })
+}
+
+fn decode_event(abi: &JsonAbi, log: &LogData) -> Option<DecodedEvent> {
+    let topic = log.topics().first()?;
+    let event = abi.events().find(|e| e.selector() == *topic)?;
+    let decoded = event.decode_log(log).ok()?;
+
+    // indexed and non-indexed values are decoded separately, but are shown in declaration order
+    let mut indexed = decoded.indexed.into_iter();
+    let mut body = decoded.body.into_iter();
+    let params = event
+        .inputs
+        .iter()
+        .filter_map(|input| {
+            let value = if input.indexed {
+                indexed.next()?
+            } else {
+                body.next()?
+            };
+
+            Some(DecodedParam {
+                name: input.name.clone(),
+                ty: input.ty.clone(),
+                value: format_value(&value),
+            })
+        })
+        .collect();
+
+    Some(DecodedEvent {
+        signature: event.signature(),
+        params,
+    })
+}
+
+pub(crate) fn params(params: &[Param], values: &[DynSolValue]) -> Vec<DecodedParam> {
+    params
+        .iter()
+        .zip(values.iter())
+        .map(|(param, value)| DecodedParam {
+            name: param.name.clone(),
+            ty: param.ty.clone(),
+            value: format_value(value),
+        })
+        .collect()
+}
+
+pub(crate) fn format_value(value: &DynSolValue) -> String {
+    if let Some(values) = value.as_tuple() {
+        return format!("({})", join(values));
+    }
+
+    if let Some(values) = value.as_array().or_else(|| value.as_fixed_array()) {
+        return format!("[{}]", join(values));
+    }
+
+    match value {
+        DynSolValue::Address(a) => a.to_checksum(None),
+        DynSolValue::Bool(b) => b.to_string(),
+        DynSolValue::Int(i, _) => i.to_string(),
+        DynSolValue::Uint(u, _) => u.to_string(),
+        DynSolValue::FixedBytes(word, size) => hex::encode_prefixed(&word[..*size]),
+        DynSolValue::Bytes(b) => hex::encode_prefixed(b),
+        DynSolValue::String(s) => format!("{s:?}"),
+        other => format!("{other:?}"),
+    }
+}
+
+fn join(values: &[DynSolValue]) -> String {
+    values
+        .iter()
+        .map(format_value)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -1,19 +1,33 @@
 pub mod commands;
+mod decode;
+mod overrides;
+pub mod revert;
+mod state_diff;
+mod tracer;
 pub mod types;
 
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use alloy::{
     eips::BlockId,
-    providers::{Provider as _, ProviderBuilder},
+    network::Ethereum,
+    primitives::TxKind,
+    providers::{DynProvider, Provider as _, ProviderBuilder},
+    rpc::types::BlockOverrides,
 };
 use ethui_types::prelude::*;
+pub use revert::{RevertReason, decode_revert};
 use revm::{
-    ExecuteEvm as _, MainBuilder as _, MainContext as _,
-    context::{TxEnv, result::ExecResultAndState},
+    ExecuteCommitEvm as _, ExecuteEvm as _, InspectEvm as _, MainBuilder as _, MainContext as _,
+    context::{BlockEnv, ContextTr as _, TxEnv, result::ExecResultAndState},
     database::{AlloyDB, CacheDB, WrapDatabaseAsync},
 };
-pub use types::{Request, SimResult};
+pub use types::{BundleResult, CallFrame, CallResult, Request, SimResult, StateDiff, TxOutcome};
+
+use crate::tracer::CallTracer;
+
+/// Time between the simulated blocks of a bundle, one per transaction
+const BLOCK_TIME: u64 = 12;
 
 /// Simulates a transaction on a given network's latest state.
 pub async fn simulate_once(
@@ -21,54 +35,273 @@ pub async fn simulate_once(
     fork_url: String,
     fork_block_number: Option<u64>,
 ) -> Result<SimResult> {
-    let provider = ProviderBuilder::new().connect(&fork_url).await?;
-    let block_id: BlockId = fork_block_number.map(Into::into).unwrap_or_default();
-
-    let nonce = provider
-        .get_transaction_count(tx.from)
-        .block_id(block_id)
-        .await?;
-
-    let block_number = provider.get_block_number().await?;
-    let block_time = provider
-        .get_block(block_number.into())
-        .await?
-        .map(|b| b.header.timestamp)
-        .unwrap_or_default();
-    let system_time = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
-    let time = std::cmp::max(block_time, system_time);
-
-    let db = WrapDatabaseAsync::new(AlloyDB::new(provider, block_id)).unwrap();
-    let cache_db = CacheDB::new(db);
+    let mut bundle = simulate_bundle(vec![tx], fork_url, fork_block_number).await?;
+    match bundle.results.pop() {
+        Some(TxOutcome::Simulated(result)) => Ok(result),
+        Some(TxOutcome::Invalid { error }) => Err(eyre!("Failed to transact: {error}")),
+        None => Err(eyre!("Failed to transact")),
+    }
+}
+
+/// Runs a call on a given network's state, the way `eth_call` does: neither the sender's nonce nor
+/// the block's base fee are checked.
+///
+/// Unlike a simulation, nothing is traced, so reverts are decoded against the called contract only
+pub async fn call(
+    tx: Request,
+    fork_url: String,
+    fork_block_number: Option<u64>,
+) -> Result<CallResult> {
+    let fork = Fork::new(&fork_url, fork_block_number).await?;
 
     let mut evm = revm::Context::mainnet()
-        .with_db(cache_db)
-        .modify_block_chained(|b| {
-            b.timestamp = U256::from(time + 1);
-            b.number = U256::from(block_number + 1);
+        .with_db(fork.db())
+        .modify_cfg_chained(|c| {
+            c.disable_base_fee = true;
+            c.disable_nonce_check = true;
         })
         .build_mainnet();
 
-    let tx = TxEnv::builder()
-        .caller(tx.from)
-        .to(tx.to.unwrap_or_default())
-        .data(tx.data.unwrap_or_default())
-        .value(tx.value.unwrap_or_default())
-        .nonce(nonce)
-        .build()
-        .unwrap();
+    if let Some(ref state_overrides) = tx.state_overrides {
+        overrides::apply_state(evm.ctx.db_mut(), state_overrides)?;
+    }
+
+    evm.ctx
+        .modify_block(|b| fork.prepare_block(b, 0, tx.block_overrides.as_ref()));
+
+    let result = evm
+        .transact_one(tx_env(&tx, 0))
+        .map_err(|e| eyre!("Failed to transact: {e}"))?;
+
+    let revert_reason = match result.output() {
+        Some(output) if !result.is_success() => {
+            let chain_id = fork.provider.get_chain_id().await?;
+            // the called contract's code, as loaded or overridden
+            let contract = tx.to.map(|to| {
+                let code = evm
+                    .ctx
+                    .db()
+                    .cache
+                    .accounts
+                    .get(&to)
+                    .and_then(|account| account.info.code.as_ref())
+                    .map(|code| code.original_bytes());
+                (to, code)
+            });
+
+            decode_revert(output, chain_id, contract).await
+        }
+        _ => None,
+    };
+
+    Ok(CallResult {
+        success: result.is_success(),
+        gas_used: result.gas_used(),
+        return_data: result.output().cloned(),
+        revert_reason,
+    })
+}
+
+/// Simulates a sequence of transactions on a given network's latest state, each one seeing the
+/// effects of the previous ones. Each transaction goes in its own block.
+///
+/// Transactions that can't run don't stop the bundle, and get an error of their own instead
+pub async fn simulate_bundle(
+    txs: Vec<Request>,
+    fork_url: String,
+    fork_block_number: Option<u64>,
+) -> Result<BundleResult> {
+    let fork = Fork::new(&fork_url, fork_block_number).await?;
+    let chain_id = fork.provider.get_chain_id().await?;
+
+    let mut evm = revm::Context::mainnet()
+        .with_db(fork.db())
+        .build_mainnet_with_inspector(CallTracer::default());
+
+    // only needs the node for accounts not yet touched by the bundle
+    let mut nonces: HashMap<Address, u64> = HashMap::new();
+
+    let mut results = Vec::with_capacity(txs.len());
+    for (i, tx) in txs.into_iter().enumerate() {
+        if let Some(ref state_overrides) = tx.state_overrides {
+            if let Err(e) = overrides::apply_state(evm.ctx.db_mut(), state_overrides) {
+                results.push(TxOutcome::Invalid {
+                    error: format!("invalid state overrides: {e}"),
+                });
+                continue;
+            }
+
+            for (address, account) in state_overrides.iter() {
+                if let Some(nonce) = account.nonce {
+                    nonces.insert(*address, nonce);
+                }
+            }
+        }
+
+        let nonce = match nonces.get(&tx.from) {
+            Some(nonce) => *nonce,
+            None => match fork
+                .provider
+                .get_transaction_count(tx.from)
+                .block_id(fork.block_id)
+                .await
+            {
+                Ok(nonce) => nonce,
+                Err(e) => {
+                    results.push(TxOutcome::Invalid {
+                        error: format!("failed to fetch the nonce of {}: {e}", tx.from),
+                    });
+                    continue;
+                }
+            },
+        };
+
+        evm.ctx
+            .modify_block(|b| fork.prepare_block(b, i as u64, tx.block_overrides.as_ref()));
+
+        // a base fee override shouldn't make the sender pay for gas, as in geth's eth_call
+        evm.ctx
+            .modify_cfg(|c| c.disable_base_fee = tx.block_overrides.is_some());
+
+        let (from, to) = (tx.from, tx.to);
+        let ExecResultAndState { result, state } = match evm.inspect_tx(tx_env(&tx, nonce)) {
+            Ok(result) => result,
+            Err(e) => {
+                // whatever the tracer saw belongs to no transaction
+                std::mem::take(&mut evm.inspector);
+                results.push(TxOutcome::Invalid {
+                    error: e.to_string(),
+                });
+                continue;
+            }
+        };
+        nonces.insert(from, nonce + 1);
 
-    if let Ok(ExecResultAndState { result, .. }) = evm.transact(tx) {
-        Ok(SimResult {
+        // the transaction isn't committed yet, so the cache still holds the balances from before it
+        let cache = &evm.ctx.db().cache.accounts;
+        let balances: HashMap<Address, U256> = state
+            .keys()
+            .filter_map(|address| Some((*address, cache.get(address)?.info.balance)))
+            .collect();
+
+        let state_diff = state_diff::build(chain_id, &state, result.logs(), &balances).await;
+
+        // the tracer is reused across transactions, so its trace is taken out every time
+        let mut trace = std::mem::take(&mut evm.inspector).into_trace();
+        let abis = match trace {
+            Some(ref trace) => decode::load_abis(trace, chain_id, &state).await,
+            None => Default::default(),
+        };
+
+        if let Some(ref mut trace) = trace {
+            decode::decode_trace(trace, &abis);
+        }
+
+        // the called contract is the likeliest to declare the error, though it may have bubbled
+        // up from any of the inner calls
+        let revert_reason = match result.output() {
+            Some(output) if !result.is_success() => revert::decode_with(
+                output,
+                to.and_then(|to| abis.get(&to))
+                    .into_iter()
+                    .chain(abis.values()),
+            ),
+            _ => None,
+        };
+
+        results.push(TxOutcome::Simulated(SimResult {
             success: result.is_success(),
             gas_used: result.gas_used(),
             logs: result.logs().to_vec(),
             return_data: result.output().cloned(),
+            revert_reason,
+            state_diff,
+            trace,
+        }));
+
+        evm.commit(state);
+    }
+
+    let state_diff = StateDiff::merge(results.iter().filter_map(|r| match r {
+        TxOutcome::Simulated(result) => Some(&result.state_diff),
+        TxOutcome::Invalid { .. } => None,
+    }));
+
+    Ok(BundleResult {
+        results,
+        state_diff,
+    })
+}
+
+fn tx_env(tx: &Request, nonce: u64) -> TxEnv {
+    let mut builder = TxEnv::builder()
+        .caller(tx.from)
+        .kind(tx.to.map(TxKind::Call).unwrap_or(TxKind::Create))
+        .data(tx.data.clone().unwrap_or_default())
+        .value(tx.value.unwrap_or_default())
+        .nonce(nonce);
+
+    // a zero limit means none was given, so revm's default applies
+    if tx.gas_limit > 0 {
+        builder = builder.gas_limit(tx.gas_limit);
+    }
+
+    builder.build().unwrap()
+}
+
+/// The block transactions are simulated on top of
+struct Fork {
+    provider: DynProvider,
+    block_id: BlockId,
+    number: u64,
+    timestamp: u64,
+}
+
+impl Fork {
+    async fn new(fork_url: &str, fork_block_number: Option<u64>) -> Result<Self> {
+        let provider = ProviderBuilder::new().connect(fork_url).await?.erased();
+        let block_id: BlockId = fork_block_number.map(Into::into).unwrap_or_default();
+
+        let header = provider
+            .get_block(block_id)
+            .await?
+            .ok_or_else(|| eyre!("Block {block_id} not found"))?
+            .header;
+
+        // the latest block may lag behind the wall clock, while a past one is taken as it was
+        let timestamp = match fork_block_number {
+            Some(_) => header.timestamp,
+            None => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                std::cmp::max(header.timestamp, now)
+            }
+        };
+
+        Ok(Self {
+            provider,
+            // pinned, so that every account is read from the same block even if a new one comes in
+            block_id: header.number.into(),
+            number: header.number,
+            timestamp,
         })
-    } else {
-        Err(eyre!("Failed to transact"))
+    }
+
+    fn db(&self) -> CacheDB<WrapDatabaseAsync<AlloyDB<Ethereum, DynProvider>>> {
+        CacheDB::new(
+            WrapDatabaseAsync::new(AlloyDB::new(self.provider.clone(), self.block_id)).unwrap(),
+        )
+    }
+
+    /// Sets up the `i`th block simulated on top of the fork
+    fn prepare_block(&self, block: &mut BlockEnv, i: u64, overrides: Option<&BlockOverrides>) {
+        block.number = U256::from(self.number + 1 + i);
+        block.timestamp = U256::from(self.timestamp + 1 + i * BLOCK_TIME);
+
+        if let Some(overrides) = overrides {
+            overrides::apply_block(block, overrides);
+        }
     }
 }
@@ -0,0 +1,76 @@
+use alloy::rpc::types::{BlockOverrides, state::StateOverride};
+use ethui_types::prelude::*;
+use revm::{Database as _, DatabaseRef, bytecode::Bytecode, context::BlockEnv, database::CacheDB};
+
+/// Applies geth-style state overrides on top of the forked state
+///
+/// `state` replaces an account's whole storage, while `stateDiff` only replaces the given slots
+pub(crate) fn apply_state<DB>(db: &mut CacheDB<DB>, overrides: &StateOverride) -> Result<()>
+where
+    DB: DatabaseRef,
+    DB::Error: std::error::Error + Send + Sync + 'static,
+{
+    for (address, account) in overrides.iter() {
+        let mut info = db.basic(*address)?.unwrap_or_default();
+
+        if let Some(balance) = account.balance {
+            info.balance = balance;
+        }
+
+        if let Some(nonce) = account.nonce {
+            info.nonce = nonce;
+        }
+
+        if let Some(ref code) = account.code {
+            let code = Bytecode::new_raw(code.clone());
+            info.code_hash = code.hash_slow();
+            info.code = Some(code);
+        }
+
+        db.insert_account_info(*address, info);
+
+        if let Some(ref state) = account.state {
+            let storage = state
+                .iter()
+                .map(|(slot, value)| (U256::from_be_bytes(slot.0), U256::from_be_bytes(value.0)))
+                .collect();
+
+            db.replace_account_storage(*address, storage)?;
+        }
+
+        if let Some(ref diff) = account.state_diff {
+            for (slot, value) in diff.iter() {
+                db.insert_account_storage(
+                    *address,
+                    U256::from_be_bytes(slot.0),
+                    U256::from_be_bytes(value.0),
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Applies block overrides to the simulated block
+pub(crate) fn apply_block(block: &mut BlockEnv, overrides: &BlockOverrides) {
+    if let Some(number) = overrides.number {
+        block.number = number;
+    }
+
+    if let Some(time) = overrides.time {
+        block.timestamp = U256::from(time);
+    }
+
+    if let Some(base_fee) = overrides.base_fee {
+        block.basefee = base_fee.saturating_to();
+    }
+
+    if let Some(coinbase) = overrides.coinbase {
+        block.beneficiary = coinbase;
+    }
+
+    if let Some(gas_limit) = overrides.gas_limit {
+        block.gas_limit = gas_limit;
+    }
+}
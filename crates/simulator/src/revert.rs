@@ -0,0 +1,275 @@
+//! Decoding of revert data into something a human can read
+//!
+//! Besides the built-in `Error(string)` and `Panic(uint256)`, custom errors are looked up in the
+//! ABIs of known contracts, then in local forge artifacts, and finally in a bundled list of
+//! common error signatures
+
+use std::{fmt, sync::LazyLock};
+
+use alloy::{
+    dyn_abi::JsonAbiExt as _,
+    json_abi::Error as AbiError,
+    primitives::Selector,
+    sol_types::{Panic, Revert, SolError as _},
+};
+use ethui_types::prelude::*;
+
+use crate::{
+    decode::{self, KnownAbi},
+    types::DecodedParam,
+};
+
+/// Signatures of widely used custom errors (OpenZeppelin, Solady, Permit2, Uniswap, ERC-4337)
+static BUNDLED: LazyLock<HashMap<Selector, AbiError>> = LazyLock::new(|| {
+    include_str!("errors.txt")
+        .lines()
+        .filter_map(|line| AbiError::parse(line).ok())
+        .map(|error| (error.selector(), error))
+        .collect()
+});
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum RevertReason {
+    /// `require(cond, "message")` or `revert("message")`
+    Error { message: String },
+
+    /// failed assertions, overflows, out-of-bounds accesses and such
+    Panic { code: U256, description: String },
+
+    Custom {
+        signature: String,
+        params: Vec<DecodedParam>,
+        /// the contract whose ABI declares the error, if it came from a local artifact
+        contract: Option<String>,
+    },
+}
+
+impl fmt::Display for RevertReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error { message } => write!(f, "{message}"),
+            Self::Panic { code, description } => write!(f, "panic: {description} ({code:#x})"),
+            Self::Custom {
+                signature, params, ..
+            } => {
+                let name = signature.split('(').next().unwrap_or(signature);
+                let params = params
+                    .iter()
+                    .map(|p| match p.name.as_str() {
+                        "" => p.value.clone(),
+                        name => format!("{name}: {}", p.value),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                write!(f, "{name}({params})")
+            }
+        }
+    }
+}
+
+/// Decodes revert data returned by a call to `contract`, if known. Its deployed code, when given,
+/// is used to match against local artifacts
+pub async fn decode_revert(
+    data: &[u8],
+    chain_id: u64,
+    contract: Option<(Address, Option<Bytes>)>,
+) -> Option<RevertReason> {
+    let known = match contract {
+        Some((address, code)) => decode::lookup(address, chain_id, code).await,
+        None => None,
+    };
+
+    decode_with(data, known.iter())
+}
+
+/// Decodes revert data, looking up custom errors in the given ABIs before the bundled ones
+pub(crate) fn decode_with<'a>(
+    data: &[u8],
+    abis: impl IntoIterator<Item = &'a KnownAbi>,
+) -> Option<RevertReason> {
+    let selector = Selector::try_from(data.get(..4)?).ok()?;
+
+    if selector == Revert::SELECTOR {
+        let Revert { reason } = Revert::abi_decode(data).ok()?;
+        return Some(RevertReason::Error { message: reason });
+    }
+
+    if selector == Panic::SELECTOR {
+        let Panic { code } = Panic::abi_decode(data).ok()?;
+        return Some(RevertReason::Panic {
+            code,
+            description: panic_description(code).into(),
+        });
+    }
+
+    let declared = abis.into_iter().find_map(|known| {
+        known
+            .abi
+            .errors()
+            .find(|e| e.selector() == selector)
+            .map(|e| (e, known.name.clone()))
+    });
+
+    let (error, contract) = match declared {
+        Some((error, contract)) => (error, contract),
+        None => (BUNDLED.get(&selector)?, None),
+    };
+
+    let values = error.abi_decode_input(&data[4..]).ok()?;
+
+    Some(RevertReason::Custom {
+        signature: error.signature(),
+        params: decode::params(&error.inputs, &values),
+        contract,
+    })
+}
+
+/// https://docs.soliditylang.org/en/latest/control-structures.html#panic-via-assert-and-error-via-require
+fn panic_description(code: U256) -> &'static str {
+    match code.saturating_to::<u64>() {
+        0x00 => "generic compiler panic",
+        0x01 => "assertion failed",
+        0x11 => "arithmetic overflow or underflow",
+        0x12 => "division or modulo by zero",
+        0x21 => "invalid enum value",
+        0x22 => "invalid storage byte array encoding",
+        0x31 => "pop on empty array",
+        0x32 => "array index out of bounds",
+        0x41 => "out of memory",
+        0x51 => "call to uninitialized function",
+        _ => "unknown panic code",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{json_abi::JsonAbi, sol, sol_types::SolError};
+
+    use super::*;
+
+    sol! {
+        error InsufficientBalance(uint256 available, uint256 required);
+        error ERC1967InvalidImplementation(address implementation);
+    }
+
+    fn known(name: &str, signature: &str) -> KnownAbi {
+        let error = AbiError::parse(signature).unwrap();
+        let mut abi = JsonAbi::new();
+        abi.errors.insert(error.name.clone(), vec![error]);
+
+        KnownAbi {
+            name: Some(name.into()),
+            abi,
+        }
+    }
+
+    #[test]
+    fn error_string() {
+        let data = Revert {
+            reason: "not enough".into(),
+        }
+        .abi_encode();
+
+        let reason = decode_with(&data, []).unwrap();
+
+        assert_eq!(
+            reason,
+            RevertReason::Error {
+                message: "not enough".into()
+            }
+        );
+        assert_eq!(reason.to_string(), "not enough");
+    }
+
+    #[test]
+    fn panic() {
+        let data = Panic {
+            code: U256::from(0x11),
+        }
+        .abi_encode();
+
+        let reason = decode_with(&data, []).unwrap();
+
+        assert_eq!(
+            reason.to_string(),
+            "panic: arithmetic overflow or underflow (0x11)"
+        );
+    }
+
+    #[test]
+    fn custom_error_from_a_known_abi() {
+        let data = InsufficientBalance {
+            available: U256::from(1),
+            required: U256::from(2),
+        }
+        .abi_encode();
+        let vault = known(
+            "Vault",
+            "InsufficientBalance(uint256 available, uint256 required)",
+        );
+
+        let reason = decode_with(&data, [&vault]).unwrap();
+
+        let RevertReason::Custom { ref contract, .. } = reason else {
+            panic!("expected a custom error, got {reason:?}");
+        };
+        assert_eq!(contract.as_deref(), Some("Vault"));
+        assert_eq!(
+            reason.to_string(),
+            "InsufficientBalance(available: 1, required: 2)"
+        );
+    }
+
+    #[test]
+    fn custom_error_from_the_first_abi_declaring_it() {
+        let data = InsufficientBalance {
+            available: U256::from(1),
+            required: U256::from(2),
+        }
+        .abi_encode();
+        let signature = "InsufficientBalance(uint256 available, uint256 required)";
+        let (vault, token) = (known("Vault", signature), known("Token", signature));
+
+        let reason = decode_with(&data, [&vault, &token]).unwrap();
+
+        assert!(
+            matches!(reason, RevertReason::Custom { contract: Some(ref c), .. } if c == "Vault")
+        );
+    }
+
+    #[test]
+    fn bundled_error() {
+        let data = ERC1967InvalidImplementation {
+            implementation: Address::ZERO,
+        }
+        .abi_encode();
+
+        let reason = decode_with(&data, []).unwrap();
+
+        assert_eq!(
+            reason,
+            RevertReason::Custom {
+                signature: "ERC1967InvalidImplementation(address)".into(),
+                params: vec![DecodedParam {
+                    name: "implementation".into(),
+                    ty: "address".into(),
+                    value: Address::ZERO.to_checksum(None),
+                }],
+                contract: None,
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_error() {
+        let data = InsufficientBalance {
+            available: U256::from(1),
+            required: U256::from(2),
+        }
+        .abi_encode();
+
+        assert_eq!(decode_with(&data, []), None);
+    }
+}
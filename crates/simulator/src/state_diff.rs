@@ -0,0 +1,370 @@
+use std::collections::BTreeMap;
+
+use alloy::{
+    primitives::{I256, Log},
+    sol_types::SolEvent as _,
+};
+use ethui_abis::{IERC20, IERC721, IERC1155};
+use ethui_types::prelude::*;
+use revm::state::EvmState;
+
+use crate::types::{
+    BalanceChange, DeployedCode, StateDiff, StorageChange, TokenChange, TokenStandard,
+};
+
+/// Builds the diff between the state before a simulated transaction and the state it left
+///
+/// `balances` holds the balances of the touched accounts before the transaction, as loaded into the
+/// fork's cache while it ran. Accounts missing from it are taken to have had none
+pub(crate) async fn build(
+    chain_id: u64,
+    state: &EvmState,
+    logs: &[Log],
+    balances: &HashMap<Address, U256>,
+) -> StateDiff {
+    let mut diff = StateDiff::default();
+
+    for (address, account) in state.iter().filter(|(_, a)| a.is_touched()) {
+        let before = balances.get(address).copied().unwrap_or_default();
+
+        if before != account.info.balance {
+            diff.balances.push(BalanceChange {
+                address: *address,
+                before,
+                after: account.info.balance,
+            });
+        }
+
+        diff.storage.extend(
+            account
+                .changed_storage_slots()
+                .map(|(slot, value)| StorageChange {
+                    address: *address,
+                    slot: (*slot).into(),
+                    before: value.original_value.into(),
+                    after: value.present_value.into(),
+                }),
+        );
+
+        if account.is_created()
+            && let Some(code) = account.info.code.as_ref()
+            && !code.is_empty()
+        {
+            diff.deployed.push(DeployedCode {
+                address: *address,
+                code: code.original_bytes(),
+            });
+        }
+    }
+
+    diff.balances.sort_by_key(|c| c.address);
+    diff.storage.sort_by_key(|c| (c.address, c.slot));
+    diff.deployed.sort_by_key(|c| c.address);
+
+    diff.tokens = token_changes(logs, chain_id).await;
+
+    diff
+}
+
+/// Per-owner balance changes from every token transfer, along with the token's metadata
+async fn token_changes(logs: &[Log], chain_id: u64) -> Vec<TokenChange> {
+    let mut changes = Vec::new();
+    for ((token, standard, owner, token_id), delta) in net_transfers(logs) {
+        let metadata = ethui_db::get()
+            .get_erc20_metadata(token, chain_id)
+            .await
+            .ok();
+
+        changes.push(TokenChange {
+            token,
+            standard,
+            owner,
+            token_id,
+            delta,
+            symbol: metadata.as_ref().and_then(|m| m.symbol.clone()),
+            decimals: metadata.and_then(|m| m.decimals),
+        });
+    }
+
+    changes
+}
+
+/// Nets out every token transfer into per-owner balance changes, by token, owner and token id
+fn net_transfers(logs: &[Log]) -> Vec<((Address, TokenStandard, Address, Option<U256>), I256)> {
+    let mut deltas: BTreeMap<(Address, TokenStandard, Address, Option<U256>), I256> =
+        BTreeMap::new();
+
+    let mut transfer = |token, standard, from, to, id, value: U256| {
+        let value = I256::from_raw(value);
+        *deltas.entry((token, standard, from, id)).or_default() -= value;
+        *deltas.entry((token, standard, to, id)).or_default() += value;
+    };
+
+    for log in logs {
+        let token = log.address;
+
+        // ERC-20 and ERC-721 transfers share a signature, and differ on whether the amount is
+        // indexed
+        if let Ok(Log { data, .. }) = IERC20::Transfer::decode_log(log) {
+            transfer(
+                token,
+                TokenStandard::Erc20,
+                data.from,
+                data.to,
+                None,
+                data.value,
+            );
+        } else if let Ok(Log { data, .. }) = IERC721::Transfer::decode_log(log) {
+            transfer(
+                token,
+                TokenStandard::Erc721,
+                data.from,
+                data.to,
+                Some(data.tokenId),
+                U256::from(1),
+            );
+        } else if let Ok(Log { data, .. }) = IERC1155::TransferSingle::decode_log(log) {
+            transfer(
+                token,
+                TokenStandard::Erc1155,
+                data.from,
+                data.to,
+                Some(data.id),
+                data.value,
+            );
+        } else if let Ok(Log { data, .. }) = IERC1155::TransferBatch::decode_log(log) {
+            for (id, value) in data.ids.iter().zip(data.values.iter()) {
+                transfer(
+                    token,
+                    TokenStandard::Erc1155,
+                    data.from,
+                    data.to,
+                    Some(*id),
+                    *value,
+                );
+            }
+        }
+    }
+
+    // mints and burns show up as transfers from and to the zero address
+    deltas
+        .into_iter()
+        .filter(|((_, _, owner, _), delta)| !owner.is_zero() && !delta.is_zero())
+        .collect()
+}
+
+impl StateDiff {
+    /// Combines the diffs of consecutive transactions into the diff of all of them
+    pub(crate) fn merge<'a>(diffs: impl IntoIterator<Item = &'a StateDiff>) -> Self {
+        let mut balances: BTreeMap<Address, BalanceChange> = BTreeMap::new();
+        let mut storage: BTreeMap<(Address, B256), StorageChange> = BTreeMap::new();
+        let mut tokens: BTreeMap<(Address, TokenStandard, Address, Option<U256>), TokenChange> =
+            BTreeMap::new();
+        let mut deployed: BTreeMap<Address, DeployedCode> = BTreeMap::new();
+
+        // the earliest `before` is kept, and the latest `after`
+        for diff in diffs {
+            for change in diff.balances.iter() {
+                balances
+                    .entry(change.address)
+                    .and_modify(|c| c.after = change.after)
+                    .or_insert_with(|| change.clone());
+            }
+
+            for change in diff.storage.iter() {
+                storage
+                    .entry((change.address, change.slot))
+                    .and_modify(|c| c.after = change.after)
+                    .or_insert_with(|| change.clone());
+            }
+
+            for change in diff.tokens.iter() {
+                tokens
+                    .entry((change.token, change.standard, change.owner, change.token_id))
+                    .and_modify(|c| c.delta += change.delta)
+                    .or_insert_with(|| change.clone());
+            }
+
+            for code in diff.deployed.iter() {
+                deployed.insert(code.address, code.clone());
+            }
+        }
+
+        Self {
+            balances: balances
+                .into_values()
+                .filter(|c| c.before != c.after)
+                .collect(),
+            tokens: tokens
+                .into_values()
+                .filter(|c| !c.delta.is_zero())
+                .collect(),
+            storage: storage
+                .into_values()
+                .filter(|c| c.before != c.after)
+                .collect(),
+            deployed: deployed.into_values().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::sol_types::SolEvent;
+
+    use super::*;
+
+    const TOKEN: Address = Address::repeat_byte(0xaa);
+    const ALICE: Address = Address::repeat_byte(0x01);
+    const BOB: Address = Address::repeat_byte(0x02);
+
+    fn log(event: &impl SolEvent) -> Log {
+        Log {
+            address: TOKEN,
+            data: event.encode_log_data(),
+        }
+    }
+
+    fn erc20(from: Address, to: Address, value: u64) -> Log {
+        log(&IERC20::Transfer {
+            from,
+            to,
+            value: U256::from(value),
+        })
+    }
+
+    fn delta(value: i64) -> I256 {
+        I256::try_from(value).unwrap()
+    }
+
+    #[test]
+    fn nets_transfers_between_owners() {
+        let logs = [erc20(ALICE, BOB, 100), erc20(BOB, ALICE, 30)];
+
+        assert_eq!(
+            net_transfers(&logs),
+            vec![
+                ((TOKEN, TokenStandard::Erc20, ALICE, None), delta(-70)),
+                ((TOKEN, TokenStandard::Erc20, BOB, None), delta(70)),
+            ]
+        );
+    }
+
+    #[test]
+    fn leaves_out_the_zero_address_on_mints_and_burns() {
+        let logs = [
+            erc20(Address::ZERO, ALICE, 100),
+            erc20(ALICE, Address::ZERO, 40),
+        ];
+
+        assert_eq!(
+            net_transfers(&logs),
+            vec![((TOKEN, TokenStandard::Erc20, ALICE, None), delta(60))]
+        );
+    }
+
+    #[test]
+    fn leaves_out_owners_that_net_to_zero() {
+        let logs = [erc20(ALICE, BOB, 100), erc20(BOB, ALICE, 100)];
+
+        assert!(net_transfers(&logs).is_empty());
+    }
+
+    #[test]
+    fn splits_erc1155_batches_by_id() {
+        let logs = [log(&IERC1155::TransferBatch {
+            operator: ALICE,
+            from: ALICE,
+            to: BOB,
+            ids: vec![U256::from(1), U256::from(2)],
+            values: vec![U256::from(5), U256::from(7)],
+        })];
+
+        assert_eq!(
+            net_transfers(&logs),
+            vec![
+                (
+                    (TOKEN, TokenStandard::Erc1155, ALICE, Some(U256::from(1))),
+                    delta(-5)
+                ),
+                (
+                    (TOKEN, TokenStandard::Erc1155, ALICE, Some(U256::from(2))),
+                    delta(-7)
+                ),
+                (
+                    (TOKEN, TokenStandard::Erc1155, BOB, Some(U256::from(1))),
+                    delta(5)
+                ),
+                (
+                    (TOKEN, TokenStandard::Erc1155, BOB, Some(U256::from(2))),
+                    delta(7)
+                ),
+            ]
+        );
+    }
+
+    fn token_change(owner: Address, value: i64) -> TokenChange {
+        TokenChange {
+            token: TOKEN,
+            standard: TokenStandard::Erc20,
+            owner,
+            token_id: None,
+            delta: delta(value),
+            symbol: None,
+            decimals: None,
+        }
+    }
+
+    #[test]
+    fn merges_consecutive_diffs() {
+        let slot = B256::with_last_byte(1);
+        let first = StateDiff {
+            balances: vec![BalanceChange {
+                address: ALICE,
+                before: U256::from(10),
+                after: U256::from(20),
+            }],
+            tokens: vec![token_change(ALICE, -5), token_change(BOB, 5)],
+            storage: vec![StorageChange {
+                address: TOKEN,
+                slot,
+                before: B256::ZERO,
+                after: B256::with_last_byte(7),
+            }],
+            deployed: vec![DeployedCode {
+                address: TOKEN,
+                code: Bytes::from_static(&[0x60, 0x00]),
+            }],
+        };
+        let second = StateDiff {
+            balances: vec![BalanceChange {
+                address: ALICE,
+                before: U256::from(20),
+                after: U256::from(30),
+            }],
+            tokens: vec![token_change(ALICE, 5), token_change(BOB, -2)],
+            storage: vec![StorageChange {
+                address: TOKEN,
+                slot,
+                before: B256::with_last_byte(7),
+                after: B256::ZERO,
+            }],
+            deployed: vec![],
+        };
+
+        let merged = StateDiff::merge([&first, &second]);
+
+        assert_eq!(
+            merged.balances,
+            vec![BalanceChange {
+                address: ALICE,
+                before: U256::from(10),
+                after: U256::from(30),
+            }]
+        );
+        // ALICE got back what was sent, and the slot went back to its original value
+        assert_eq!(merged.tokens, vec![token_change(BOB, 3)]);
+        assert!(merged.storage.is_empty());
+        assert_eq!(merged.deployed, first.deployed);
+    }
+}
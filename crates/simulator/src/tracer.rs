@@ -0,0 +1,126 @@
+use alloy::primitives::Log;
+use ethui_types::prelude::*;
+use revm::{
+    Inspector,
+    context::ContextTr,
+    interpreter::{
+        CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome, InterpreterResult,
+    },
+};
+
+use crate::types::{CallFrame, CallKind, TraceLog};
+
+/// Records the tree of calls made during execution
+#[derive(Debug, Default)]
+pub(crate) struct CallTracer {
+    /// frames that haven't returned yet, innermost last
+    stack: Vec<CallFrame>,
+    root: Option<CallFrame>,
+}
+
+impl CallTracer {
+    pub(crate) fn into_trace(self) -> Option<CallFrame> {
+        self.root
+    }
+
+    fn enter(
+        &mut self,
+        kind: CallKind,
+        from: Address,
+        to: Address,
+        value: U256,
+        input: Bytes,
+        gas_limit: u64,
+    ) {
+        self.stack.push(CallFrame {
+            kind,
+            from,
+            to,
+            value,
+            input,
+            output: Default::default(),
+            gas_limit,
+            gas_used: 0,
+            success: false,
+            revert_reason: None,
+            contract: None,
+            function: None,
+            logs: vec![],
+            calls: vec![],
+        });
+    }
+
+    fn exit(&mut self, result: &InterpreterResult, created: Option<Address>) {
+        let Some(mut frame) = self.stack.pop() else {
+            return;
+        };
+
+        if let Some(address) = created {
+            frame.to = address;
+        }
+
+        frame.output = result.output.clone();
+        frame.gas_used = result.gas.spent();
+        frame.success = result.result.is_ok();
+
+        match self.stack.last_mut() {
+            Some(parent) => parent.calls.push(frame),
+            None => self.root = Some(frame),
+        }
+    }
+}
+
+impl<CTX: ContextTr> Inspector<CTX> for CallTracer {
+    fn call(&mut self, context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        let kind = match inputs.scheme {
+            CallScheme::Call => CallKind::Call,
+            CallScheme::StaticCall => CallKind::StaticCall,
+            CallScheme::DelegateCall => CallKind::DelegateCall,
+            CallScheme::CallCode => CallKind::CallCode,
+        };
+
+        self.enter(
+            kind,
+            inputs.caller,
+            inputs.bytecode_address,
+            inputs.call_value(),
+            inputs.input.bytes(context),
+            inputs.gas_limit,
+        );
+
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, outcome: &mut CallOutcome) {
+        self.exit(&outcome.result, None);
+    }
+
+    fn create(&mut self, _context: &mut CTX, inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        // the address is only known once the contract is created
+        self.enter(
+            CallKind::Create,
+            inputs.caller(),
+            Address::ZERO,
+            inputs.value(),
+            inputs.init_code().clone(),
+            inputs.gas_limit(),
+        );
+
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.exit(&outcome.result, outcome.address);
+    }
+
+    fn log(&mut self, _context: &mut CTX, log: Log) {
+        if let Some(frame) = self.stack.last_mut() {
+            frame.logs.push(TraceLog { log, event: None });
+        }
+    }
+}
@@ -1,6 +1,11 @@
-use alloy::primitives::Log;
+use alloy::{
+    primitives::{I256, Log},
+    rpc::types::{BlockOverrides, state::StateOverride},
+};
 use ethui_types::prelude::*;
 
+use crate::revert::RevertReason;
+
 /// Simulation request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -10,6 +15,10 @@ pub struct Request {
     pub data: Option<Bytes>,
     pub gas_limit: u64,
     pub value: Option<U256>,
+    /// geth-style overrides of balance, nonce, code and storage, applied before execution
+    pub state_overrides: Option<StateOverride>,
+    /// overrides of the simulated block's number, timestamp and base fee
+    pub block_overrides: Option<BlockOverrides>,
 }
 
 /// Simulation result
@@ -20,4 +29,162 @@ pub struct SimResult {
     pub success: bool,
     pub logs: Vec<Log>,
     pub return_data: Option<Bytes>,
+    /// decoded from `return_data`, when the transaction reverted
+    pub revert_reason: Option<RevertReason>,
+    pub state_diff: StateDiff,
+    pub trace: Option<CallFrame>,
+}
+
+/// Results of simulating a sequence of transactions on top of each other
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleResult {
+    /// one per transaction, in order
+    pub results: Vec<TxOutcome>,
+    /// combined effects of the whole bundle
+    pub state_diff: StateDiff,
+}
+
+/// Outcome of a plain call, without a simulation's state diff or trace
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CallResult {
+    pub gas_used: u64,
+    pub success: bool,
+    pub return_data: Option<Bytes>,
+    pub revert_reason: Option<RevertReason>,
+}
+
+/// Outcome of one of a bundle's transactions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum TxOutcome {
+    Simulated(SimResult),
+    /// the transaction couldn't run at all (e.g. its sender can't afford its value). The rest of
+    /// the bundle still runs, without its effects
+    Invalid {
+        error: String,
+    },
+}
+
+/// Effects of a simulated transaction on chain state
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StateDiff {
+    pub balances: Vec<BalanceChange>,
+    pub tokens: Vec<TokenChange>,
+    pub storage: Vec<StorageChange>,
+    pub deployed: Vec<DeployedCode>,
+}
+
+/// Native balance of an account, before and after
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChange {
+    pub address: Address,
+    pub before: U256,
+    pub after: U256,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum TokenStandard {
+    Erc20,
+    Erc721,
+    Erc1155,
+}
+
+/// Net token balance change of an owner, decoded from transfer logs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenChange {
+    pub token: Address,
+    pub standard: TokenStandard,
+    pub owner: Address,
+    pub token_id: Option<U256>,
+    pub delta: I256,
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+}
+
+/// A storage slot written to during the simulation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageChange {
+    pub address: Address,
+    pub slot: B256,
+    pub before: B256,
+    pub after: B256,
+}
+
+/// A contract created during the simulation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeployedCode {
+    pub address: Address,
+    pub code: Bytes,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CallKind {
+    Call,
+    StaticCall,
+    DelegateCall,
+    CallCode,
+    Create,
+}
+
+/// A single call in the trace of a simulation, along with the calls it made
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    pub kind: CallKind,
+    pub from: Address,
+    /// the address whose code ran, which for delegate calls is the implementation
+    pub to: Address,
+    pub value: U256,
+    pub input: Bytes,
+    pub output: Bytes,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub success: bool,
+    pub revert_reason: Option<String>,
+    /// name of the contract, when matched against a local artifact
+    pub contract: Option<String>,
+    pub function: Option<DecodedCall>,
+    pub logs: Vec<TraceLog>,
+    pub calls: Vec<CallFrame>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedCall {
+    pub signature: String,
+    pub inputs: Vec<DecodedParam>,
+    pub outputs: Vec<DecodedParam>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedParam {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub value: String,
+}
+
+/// A log emitted by a call frame
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceLog {
+    pub log: Log,
+    pub event: Option<DecodedEvent>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedEvent {
+    pub signature: String,
+    pub params: Vec<DecodedParam>,
 }
@@ -32,6 +32,7 @@ pub async fn stacks_create(slug: String) -> TauriResult<()> {
         currency: "ETH".to_string(),
         decimals: 18,
         is_stack: true,
+        rpc_cache: false,
     };
 
     stack_network_add(network_params).await;
@@ -78,6 +78,7 @@ async fn main() -> Result<()> {
         decimals: 18,
         status: NetworkStatus::Unknown,
         is_stack: false,
+        rpc_cache: false,
     };
 
     // Create worker
@@ -46,6 +46,7 @@ async fn test_http_worker_lifecycle() {
         decimals: 18,
         status: NetworkStatus::Unknown,
         is_stack: true,
+        rpc_cache: false,
     };
     let worker = AnvilHttp::new(network);
     let consumer = TestConsumer;
@@ -81,6 +82,7 @@ async fn test_ws_worker_lifecycle() {
         decimals: 18,
         status: NetworkStatus::Unknown,
         is_stack: true,
+        rpc_cache: false,
     };
     let worker = AnvilWs::new(network);
     let consumer = TestConsumer;
@@ -116,6 +118,7 @@ async fn test_message_processing_lifecycle() {
         decimals: 18,
         status: NetworkStatus::Unknown,
         is_stack: false,
+        rpc_cache: false,
     };
     let worker = AnvilHttp::new(network);
     let message_count = Arc::new(AtomicU32::new(0));
@@ -161,6 +164,7 @@ async fn test_wait_behavior() {
         decimals: 18,
         status: NetworkStatus::Unknown,
         is_stack: true,
+        rpc_cache: false,
     };
 
     // Test HTTP worker - should timeout or error without anvil
@@ -205,6 +209,7 @@ async fn test_wait_unavailable_node() {
         decimals: 18,
         status: NetworkStatus::Unknown,
         is_stack: true,
+        rpc_cache: false,
     };
 
     // Test HTTP worker failure
@@ -245,6 +250,7 @@ async fn test_block_subscription_behavior() {
         decimals: 18,
         status: NetworkStatus::Unknown,
         is_stack: true,
+        rpc_cache: false,
     };
 
     // Test HTTP block subscription - should fail gracefully without anvil
@@ -281,6 +287,7 @@ async fn test_worker_subscription_lifecycle() {
         decimals: 18,
         status: NetworkStatus::Unknown,
         is_stack: true,
+        rpc_cache: false,
     };
     let worker = Worker::new(AnvilHttp::new(network.clone()));
     let consumer = TestConsumer;
@@ -322,6 +329,7 @@ async fn test_historical_blocks_stream_interface() {
         decimals: 18,
         status: NetworkStatus::Unknown,
         is_stack: true,
+        rpc_cache: false,
     };
 
     // Test that backfill_blocks interface works without requiring actual anvil
@@ -368,6 +376,7 @@ async fn test_worker_streaming_lifecycle() {
         decimals: 18,
         status: NetworkStatus::Unknown,
         is_stack: true,
+        rpc_cache: false,
     };
 
     let worker = Worker::new(AnvilHttp::new(network.clone()));
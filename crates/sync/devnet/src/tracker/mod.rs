@@ -83,6 +83,7 @@ mod tests {
             decimals: 18,
             status: NetworkStatus::Unknown,
             is_stack: true,
+            rpc_cache: false,
         }
     }
 
@@ -260,6 +261,7 @@ mod tests {
             decimals: 18,
             status: NetworkStatus::Unknown,
             is_stack: true,
+            rpc_cache: false,
         };
 
         let worker = create_worker(ws_network.clone());
@@ -282,6 +284,7 @@ mod tests {
             decimals: 18,
             status: NetworkStatus::Unknown,
             is_stack: true,
+            rpc_cache: false,
         };
 
         let worker = create_worker(http_network.clone());
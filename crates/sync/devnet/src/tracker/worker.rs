@@ -304,6 +304,7 @@ mod tests {
             decimals: 18,
             status: NetworkStatus::Unknown,
             is_stack: false,
+            rpc_cache: false,
         };
 
         let worker = super::create_worker(network_with_ws.clone());
@@ -326,6 +327,7 @@ mod tests {
             decimals: 18,
             status: NetworkStatus::Unknown,
             is_stack: false,
+            rpc_cache: false,
         };
 
         let worker = super::create_worker(network_without_ws);
@@ -377,6 +379,7 @@ mod tests {
             decimals: 18,
             status: NetworkStatus::Unknown,
             is_stack: false,
+            rpc_cache: false,
         };
 
         let mut worker = Worker::new(AnvilHttp::new(network));
@@ -437,6 +440,7 @@ mod tests {
             decimals: 18,
             status: ethui_types::NetworkStatus::Unknown,
             is_stack: false,
+            rpc_cache: false,
         };
 
         let provider = AnvilWs::new(network);
@@ -5,7 +5,10 @@ pub mod events;
 mod global_state;
 mod network;
 mod new_network_params;
+mod pending_tx;
 pub mod prelude;
+mod rpc_record;
+mod safe_proposal;
 mod tokens;
 pub mod transactions;
 pub mod ui_events;
@@ -18,6 +21,9 @@ pub use events::Event;
 pub use global_state::GlobalState;
 pub use network::{Network, NetworkId, NetworkStatus};
 pub use new_network_params::NewNetworkParams;
+pub use pending_tx::{PendingTx, PendingTxStatus};
+pub use rpc_record::RpcRecord;
+pub use safe_proposal::SafeProposal;
 pub use tokens::{
     Erc721Collection, Erc721Token, Erc721TokenData, Erc721TokenDetails, Erc1155Token,
     Erc1155TokenData, TokenBalance, TokenMetadata,
@@ -34,6 +34,11 @@ pub struct Network {
 
     #[serde(default)]
     pub is_stack: bool,
+
+    /// Opt-in cache of immutable RPC responses (e.g. receipts of mined txs). Never used for dev
+    /// networks
+    #[serde(default)]
+    pub rpc_cache: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
@@ -57,6 +62,7 @@ impl Network {
             decimals: 18,
             status: Default::default(),
             is_stack: false,
+            rpc_cache: false,
         }
     }
 
@@ -71,6 +77,7 @@ impl Network {
             decimals: 18,
             status: Default::default(),
             is_stack: false,
+            rpc_cache: false,
         }
     }
 
@@ -85,6 +92,7 @@ impl Network {
             decimals: 18,
             status: Default::default(),
             is_stack: false,
+            rpc_cache: false,
         }
     }
 
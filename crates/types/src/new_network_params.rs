@@ -13,6 +13,8 @@ pub struct NewNetworkParams {
     pub currency: String,
     pub decimals: u32,
     pub is_stack: bool,
+    #[serde(default)]
+    pub rpc_cache: bool,
 }
 
 impl NewNetworkParams {
@@ -27,6 +29,7 @@ impl NewNetworkParams {
             decimals: self.decimals,
             status: NetworkStatus::Unknown,
             is_stack: self.is_stack,
+            rpc_cache: self.rpc_cache,
         }
     }
 }
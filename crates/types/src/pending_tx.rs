@@ -0,0 +1,85 @@
+use std::str::FromStr;
+
+use sqlx::{Row, sqlite::SqliteRow};
+
+use crate::{NetworkId, prelude::*};
+
+/// A transaction submitted by ethui, tracked until its nonce is used up on-chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingTx {
+    pub hash: B256,
+    pub chain_id: u64,
+    /// tells apart networks sharing a chain id (e.g. several anvil nodes)
+    pub dedup_id: u64,
+    pub from: Address,
+    pub nonce: u64,
+    /// the request as it was submitted, used to build replacements
+    pub request: Json,
+    pub status: PendingTxStatus,
+    /// the transaction this one was sent to speed up or cancel
+    pub replaces: Option<B256>,
+}
+
+impl PendingTx {
+    pub fn network_id(&self) -> NetworkId {
+        NetworkId::from(self.chain_id, self.dedup_id)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PendingTxStatus {
+    /// not mined yet
+    Pending,
+    Mined,
+    /// another transaction with the same nonce was mined instead
+    Replaced,
+    /// the node no longer knows about it, and its nonce is still unused
+    Dropped,
+}
+
+impl PendingTxStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Mined => "mined",
+            Self::Replaced => "replaced",
+            Self::Dropped => "dropped",
+        }
+    }
+}
+
+impl FromStr for PendingTxStatus {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "mined" => Ok(Self::Mined),
+            "replaced" => Ok(Self::Replaced),
+            "dropped" => Ok(Self::Dropped),
+            _ => Err(eyre!("invalid pending tx status: {s}")),
+        }
+    }
+}
+
+impl TryFrom<SqliteRow> for PendingTx {
+    type Error = color_eyre::Report;
+
+    fn try_from(row: SqliteRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            hash: B256::from_str(row.get("hash"))?,
+            chain_id: row.get::<i64, _>("chain_id") as u64,
+            dedup_id: row.get::<i64, _>("dedup_id") as u64,
+            from: Address::from_str(row.get("from_address"))?,
+            nonce: row.get::<i64, _>("nonce") as u64,
+            request: serde_json::from_str(row.get("request"))?,
+            status: row.get::<&str, _>("status").parse()?,
+            replaces: row
+                .get::<Option<&str>, _>("replaces")
+                .map(B256::from_str)
+                .transpose()?,
+        })
+    }
+}
@@ -0,0 +1,44 @@
+use sqlx::{Row, sqlite::SqliteRow};
+
+use crate::prelude::*;
+
+/// A single RPC call handled on behalf of a peer, as captured by the request inspector
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcRecord {
+    /// unix timestamp, in milliseconds
+    pub timestamp: u64,
+    pub domain: Option<String>,
+    /// network the call was resolved to, at the time it was received
+    pub network_id: NetworkId,
+    pub method: String,
+    pub params: Json,
+    pub result: Option<Json>,
+    pub error: Option<Json>,
+    pub latency_ms: u64,
+}
+
+impl TryFrom<SqliteRow> for RpcRecord {
+    type Error = serde_json::Error;
+
+    fn try_from(row: SqliteRow) -> Result<Self, Self::Error> {
+        let json = |col: &str| -> Result<Option<Json>, Self::Error> {
+            row.get::<Option<String>, _>(col)
+                .map(|s| serde_json::from_str(&s))
+                .transpose()
+        };
+
+        Ok(Self {
+            timestamp: row.get::<i64, _>("timestamp") as u64,
+            domain: row.get("domain"),
+            network_id: NetworkId::from(
+                row.get::<i64, _>("chain_id") as u64,
+                row.get::<i64, _>("dedup_id") as u64,
+            ),
+            method: row.get("method"),
+            params: json("params")?.unwrap_or_default(),
+            result: json("result")?,
+            error: json("error")?,
+            latency_ms: row.get::<i64, _>("latency_ms") as u64,
+        })
+    }
+}
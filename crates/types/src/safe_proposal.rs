@@ -0,0 +1,36 @@
+use sqlx::{Row, sqlite::SqliteRow};
+
+use crate::prelude::*;
+
+/// A transaction proposed to a Safe, along with the owner signatures collected for it so far
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SafeProposal {
+    pub safe_tx_hash: B256,
+    pub chain_id: u64,
+    pub safe: Address,
+    pub nonce: u64,
+    /// the SafeTx fields, as they were hashed
+    pub tx: Json,
+    pub signatures: HashMap<Address, Bytes>,
+    /// hash of the `execTransaction` call, once submitted
+    pub executed_in: Option<B256>,
+}
+
+impl TryFrom<SqliteRow> for SafeProposal {
+    type Error = color_eyre::Report;
+
+    fn try_from(row: SqliteRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            safe_tx_hash: B256::from_str(row.get("safe_tx_hash"))?,
+            chain_id: row.get::<i64, _>("chain_id") as u64,
+            safe: Address::from_str(row.get("safe"))?,
+            nonce: row.get::<i64, _>("nonce") as u64,
+            tx: serde_json::from_str(row.get("tx"))?,
+            signatures: serde_json::from_str(row.get("signatures"))?,
+            executed_in: row
+                .get::<Option<&str>, _>("executed_in")
+                .map(B256::from_str)
+                .transpose()?,
+        })
+    }
+}
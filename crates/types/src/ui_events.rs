@@ -29,6 +29,7 @@ pub struct DialogSend {
 pub enum UINotify {
     #[allow(unused)]
     WalletsChanged,
+    WalletsLockChanged,
     NetworksChanged,
     CurrentNetworkChanged,
     TxsUpdated,
@@ -46,6 +47,7 @@ impl UINotify {
     pub fn label(&self) -> &str {
         match self {
             Self::WalletsChanged => "wallets-changed",
+            Self::WalletsLockChanged => "wallets-lock-changed",
             Self::NetworksChanged => "networks-changed",
             Self::CurrentNetworkChanged => "current-network-changed",
             Self::TxsUpdated => "txs-updated",
@@ -11,6 +11,7 @@ use base64::{
     Engine as _,
     engine::general_purpose::{STANDARD as BASE64, URL_SAFE_NO_PAD},
 };
+use ethui_connections::{Ctx, permissions::Permission};
 use ethui_dialogs::{Dialog, DialogMsg};
 use ethui_types::{Address, ui_events::UINotify};
 use ethui_wallets::WalletControl;
@@ -503,10 +504,11 @@ async fn settle_session(
     // WS connection — not per request, which would panic below whenever
     // `peer.url` is empty or fails to parse (both real for dApps that omit
     // proposal metadata), since `Ctx::request_permissions` used to unwrap it.
-    let domain = url::Url::parse(&session.peer.url)
-        .ok()
-        .and_then(|u| u.host_str().map(|h| h.to_owned()));
+    let domain = session_domain(&session.peer.url);
     let handler = Arc::new(ethui_rpc::Handler::new(domain));
+    // approving the proposal is what exposes the account, so `eth_accounts` over the session must
+    // agree with it
+    expose_account(handler.ctx(), address).await?;
 
     // Insert the handler before the session: `handle_request` reads SESSIONS
     // then SESSION_HANDLERS, so a reader that observes the session here is
@@ -532,6 +534,26 @@ async fn settle_session(
     Ok(())
 }
 
+/// Domain the session's RPC handler is keyed on, if the dApp's metadata has a usable url
+fn session_domain(peer_url: &str) -> Option<String> {
+    url::Url::parse(peer_url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_owned()))
+}
+
+/// Grants `address` as the only account exposed to the session
+///
+/// Sessions from a known domain share that domain's persisted permissions. Those without one keep
+/// the grant to themselves
+async fn expose_account(ctx: &Ctx, address: Address) -> WcResult<()> {
+    let invoker = ctx.domain.clone().unwrap_or_default();
+
+    ctx.grant_permissions(vec![Permission::eth_accounts(invoker, vec![address])])
+        .await?;
+
+    Ok(())
+}
+
 // ── session request forwarding ────────────────────────────────────────────────
 
 async fn handle_request(
@@ -683,7 +705,7 @@ pub async fn list_sessions() -> Vec<Session> {
 /// change (`wc_sessionEvent` "accountsChanged"). No re-approval is needed on
 /// the dApp side — same as switching accounts in a browser extension wallet.
 pub async fn switch_account(topic: Topic, new_address: Address) -> WcResult<()> {
-    let (sym_key, chains, allowed_methods) = {
+    let (sym_key, chains, allowed_methods, domain) = {
         let sessions = SESSIONS.read().await;
         let session = sessions
             .get(topic.as_ref())
@@ -692,9 +714,12 @@ pub async fn switch_account(topic: Topic, new_address: Address) -> WcResult<()>
             session.sym_key,
             session.chains.clone(),
             session.allowed_methods.clone(),
+            session_domain(&session.peer.url),
         )
     };
 
+    expose_account(domain.as_deref(), new_address).await?;
+
     let accounts = build_eip155_accounts(&chains, new_address);
 
     let update = serde_json::json!({
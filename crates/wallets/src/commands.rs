@@ -1,4 +1,10 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use alloy::eips::eip7702::SignedAuthorization;
+use color_eyre::eyre::{ContextCompat as _, eyre};
+use ethui_settings::{LockPolicy, SettingsActorExt as _, settings};
 use ethui_types::{Address, GlobalState, Json, SerializableError, TauriResult};
+use zeroize::Zeroizing;
 
 use super::{Wallet, WalletControl, Wallets, utils};
 
@@ -99,3 +105,121 @@ pub async fn wallets_ledger_derive(paths: Vec<String>) -> TauriResult<Vec<(Strin
         .await
         .map_err(SerializableError::from)
 }
+
+/// Re-encrypts an HD or private key wallet under a new password
+#[tauri::command]
+pub async fn wallets_change_password(
+    name: String,
+    old_password: String,
+    new_password: String,
+) -> TauriResult<()> {
+    let old_password = Zeroizing::new(old_password);
+    let new_password = Zeroizing::new(new_password);
+
+    Wallets::write()
+        .await
+        .change_password(&name, &old_password, &new_password)
+        .await
+        .map_err(SerializableError::from)
+}
+
+/// Exports one of a wallet's keys as a V3 keystore file in `dir`, readable by other tools (e.g.
+/// `cast wallet` or `forge script --keystore`), after asking for the wallet's password again
+#[tauri::command]
+pub async fn wallets_export_keystore(
+    name: String,
+    path: String,
+    dir: PathBuf,
+    password: String,
+) -> TauriResult<PathBuf> {
+    let password = Zeroizing::new(password);
+    let wallet = Wallets::read()
+        .await
+        .get(&name)
+        .cloned()
+        .with_context(|| format!("invalid wallet name `{name}`"))?;
+
+    utils::export_keystore(&wallet, &path, &dir, &password)
+        .await
+        .map_err(SerializableError::from)
+}
+
+/// Reveals the mnemonic of an HD wallet, after asking for its password
+#[tauri::command]
+pub async fn wallets_reveal_mnemonic(name: String) -> TauriResult<String> {
+    let wallet = Wallets::read()
+        .await
+        .get(&name)
+        .cloned()
+        .with_context(|| format!("invalid wallet name `{name}`"))?;
+
+    let Wallet::HDWallet(wallet) = wallet else {
+        return Err(eyre!("only HD wallets have a mnemonic").into());
+    };
+
+    Ok(wallet.reveal_mnemonic().await?.to_string())
+}
+
+/// Unlocks a keystore of a keystore dir wallet whose address is still unknown
+#[tauri::command]
+pub async fn wallets_unlock_keystore(name: String, key: String) -> TauriResult<Address> {
+    crate::wallets::unlock_keystore(name, key)
+        .await
+        .map_err(SerializableError::from)
+}
+
+/// Sets the lock policy of a wallet. `None` goes back to the default one
+///
+/// Only dev wallets may stay unlocked for good, since their keys are public anyway
+#[tauri::command]
+pub async fn wallets_set_lock_policy(name: String, policy: Option<LockPolicy>) -> TauriResult<()> {
+    let is_dev = Wallets::read()
+        .await
+        .get(&name)
+        .with_context(|| format!("invalid wallet name `{name}`"))?
+        .is_dev();
+
+    if policy == Some(LockPolicy::Never) && !is_dev {
+        return Err(eyre!("only dev wallets can be left unlocked").into());
+    }
+
+    settings().set_lock_policy(name, policy).await?;
+    Ok(())
+}
+
+/// Signs an EIP-7702 authorization, delegating `address` to the code at `delegate`
+#[tauri::command]
+pub async fn wallets_sign_authorization(
+    address: Address,
+    chain_id: u64,
+    delegate: Address,
+    nonce: Option<u64>,
+) -> TauriResult<SignedAuthorization> {
+    utils::sign_authorization(address, chain_id, delegate, nonce)
+        .await
+        .map_err(SerializableError::from)
+}
+
+/// The contracts the given addresses currently delegate to via EIP-7702. Addresses without a
+/// delegation are left out
+#[tauri::command]
+pub async fn wallets_get_delegations(
+    chain_id: u64,
+    addresses: Vec<Address>,
+) -> TauriResult<HashMap<Address, Address>> {
+    utils::get_delegations(chain_id, addresses)
+        .await
+        .map_err(SerializableError::from)
+}
+
+/// Locks every wallet right away, regardless of its lock policy
+#[tauri::command]
+pub async fn wallets_lock_all() {
+    crate::lock_all().await
+}
+
+/// Whether each password-protected wallet is currently unlocked, by name
+#[tauri::command]
+pub async fn wallets_get_lock_states() -> HashMap<String, bool> {
+    crate::get_lock_states().await
+}
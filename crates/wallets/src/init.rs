@@ -47,6 +47,9 @@ pub async fn init(pathbuf: PathBuf) {
     res.init_broadcast().await;
 
     WALLETS.set(RwLock::new(res)).unwrap();
+
+    crate::wallets::watch_all(&Wallets::read().await.wallets);
+    tauri::async_runtime::spawn(crate::secret_cache::watch_sleep());
 }
 
 #[async_trait]
@@ -1,5 +1,6 @@
 pub mod commands;
 mod init;
+mod remote_signer;
 pub(crate) mod secret_cache;
 mod signer;
 mod utils;
@@ -7,7 +8,7 @@ mod wallet;
 mod wallets;
 
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     fs::File,
     path::{Path, PathBuf},
 };
@@ -15,6 +16,8 @@ use std::{
 use color_eyre::eyre::{ContextCompat as _, eyre};
 use ethui_types::{Address, GlobalState, Json, UINotify};
 pub use init::init;
+pub use remote_signer::{RemoteApi, RemoteAuth, RemoteSigner};
+pub use secret_cache::lock_all;
 use serde::Serialize;
 pub use signer::Signer;
 
@@ -32,6 +35,19 @@ pub async fn get_current_wallet() -> Wallet {
     Wallets::read().await.get_current_wallet().clone()
 }
 
+/// Whether each password-protected wallet is currently unlocked, by name
+pub async fn get_lock_states() -> HashMap<String, bool> {
+    let wallets = Wallets::read().await.wallets.clone();
+
+    let mut res = HashMap::new();
+    for wallet in wallets {
+        if let Some(unlocked) = wallet.is_unlocked().await {
+            res.insert(wallet.name(), unlocked);
+        }
+    }
+    res
+}
+
 /// Maintains a list of Ethereum wallets, including keeping track of the global current wallet &
 /// address
 #[derive(Debug, Serialize)]
@@ -116,12 +132,19 @@ impl Wallets {
 
         self.ensure_no_duplicates_of(&wallet.name())?;
 
+        // keystores without an address can only be unlocked once the wallet is saved
+        let offer_unlock = matches!(wallet, Wallet::KeystoreDir(_)).then(|| wallet.name());
+
         // TODO: ensure no duplicates
         self.wallets.push(wallet);
 
         self.on_wallet_changed().await?;
         self.save()?;
 
+        if let Some(name) = offer_unlock {
+            tauri::async_runtime::spawn(wallets::offer_unlock(name));
+        }
+
         ethui_broadcast::wallet_created().await;
 
         for (_, a) in addresses {
@@ -142,6 +165,11 @@ impl Wallets {
         self.wallets[i] = self.wallets[i].clone().update(params).await?;
         let after = self.wallets[i].get_all_addresses().await;
 
+        let renamed = self.wallets[i].name();
+        if renamed != name {
+            secret_cache::move_lock_policy(&name, Some(renamed)).await?;
+        }
+
         tokio::spawn(async move {
             let before: HashSet<_> = before.into_iter().collect();
             let after: HashSet<_> = after.into_iter().collect();
@@ -169,6 +197,11 @@ impl Wallets {
 
         if let Some((i, _)) = found {
             let removed = self.wallets.remove(i);
+            secret_cache::move_lock_policy(&name, None).await?;
+
+            if let Wallet::KeystoreDir(_) = removed {
+                wallets::unwatch(&name);
+            }
 
             for (_, a) in removed.get_all_addresses().await {
                 ethui_broadcast::address_removed(a).await;
@@ -182,6 +215,29 @@ impl Wallets {
         Ok(())
     }
 
+    /// Re-encrypts a wallet's secret under a new password
+    async fn change_password(
+        &mut self,
+        name: &str,
+        old_password: &str,
+        new_password: &str,
+    ) -> color_eyre::Result<()> {
+        let wallet = self
+            .wallets
+            .iter_mut()
+            .find(|w| w.name() == name)
+            .with_context(|| format!("invalid wallet name `{name}`"))?;
+
+        match wallet {
+            Wallet::HDWallet(w) => w.change_password(old_password, new_password)?,
+            Wallet::PrivateKey(w) => w.change_password(old_password, new_password)?,
+            _ => return Err(eyre!("{} wallets have no password", wallet.wallet_type())),
+        }
+
+        self.save()?;
+        Ok(())
+    }
+
     /// Get all addresses currently enabled in a given wallet
     async fn get_wallet_addresses(&self, name: String) -> Vec<(String, Address)> {
         let wallet = self.find_wallet(&name).unwrap();
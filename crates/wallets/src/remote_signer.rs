@@ -0,0 +1,550 @@
+use std::path::PathBuf;
+
+use alloy::{
+    consensus::{SignableTransaction, Transaction as _, TxEnvelope},
+    dyn_abi::TypedData,
+    eips::eip2718::Decodable2718 as _,
+    network::TxSigner,
+    primitives::{Signature, eip191_message},
+    rpc::types::{TransactionInput, TransactionRequest},
+    signers::Error as SignerError,
+};
+use async_trait::async_trait;
+use ethui_types::prelude::*;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+use url::Url;
+use zeroize::Zeroizing;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The API a remote signer speaks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RemoteApi {
+    /// Web3Signer's REST API (`/api/v1/eth1/...`). keys are identified by their public key
+    #[default]
+    Web3Signer,
+
+    /// plain `eth_sign` / `eth_signTypedData_v4` / `eth_signTransaction` JSON-RPC, as served by
+    /// clef, Web3Signer's JSON-RPC endpoint, or a node with unlocked accounts
+    JsonRpc,
+}
+
+/// How to authenticate with a remote signer
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RemoteAuth {
+    #[default]
+    None,
+
+    /// a file holding the token, sent as an `Authorization: Bearer` header. Only its path is kept,
+    /// so that the token itself doesn't end up in the wallets file
+    Bearer { path: PathBuf },
+
+    /// client certificate & key, both PEM encoded
+    Mtls { cert: PathBuf, key: PathBuf },
+}
+
+/// Connection settings for a remote signing service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteEndpoint {
+    pub url: Url,
+
+    #[serde(default)]
+    pub api: RemoteApi,
+
+    #[serde(default)]
+    pub auth: RemoteAuth,
+
+    /// PEM encoded CA certificate, for signers behind a private CA
+    #[serde(default)]
+    pub ca: Option<PathBuf>,
+}
+
+impl RemoteEndpoint {
+    fn client(&self) -> Result<reqwest::Client> {
+        let mut builder = reqwest::Client::builder().timeout(REQUEST_TIMEOUT);
+
+        if let Some(ca) = &self.ca {
+            let pem = std::fs::read(ca).with_context(|| format!("reading {}", ca.display()))?;
+            builder = builder.add_root_certificate(reqwest::Certificate::from_pem(&pem)?);
+        }
+
+        match &self.auth {
+            RemoteAuth::None => {}
+            RemoteAuth::Bearer { path } => {
+                let token = Zeroizing::new(
+                    std::fs::read_to_string(path)
+                        .with_context(|| format!("reading {}", path.display()))?,
+                );
+                let mut value = HeaderValue::from_str(&format!("Bearer {}", token.trim()))?;
+                value.set_sensitive(true);
+
+                let mut headers = HeaderMap::new();
+                headers.insert(AUTHORIZATION, value);
+                builder = builder.default_headers(headers);
+            }
+            RemoteAuth::Mtls { cert, key } => {
+                let mut pem =
+                    std::fs::read(cert).with_context(|| format!("reading {}", cert.display()))?;
+                pem.extend(
+                    std::fs::read(key).with_context(|| format!("reading {}", key.display()))?,
+                );
+                builder = builder.identity(reqwest::Identity::from_pem(&pem)?);
+            }
+        }
+
+        Ok(builder.build()?)
+    }
+
+    /// Keys the signer holds, as `(identifier, address)` pairs
+    ///
+    /// Web3Signer identifies keys by their public key, while JSON-RPC signers use the address
+    pub async fn list_keys(&self) -> Result<Vec<(String, Address)>> {
+        let client = self.client()?;
+
+        match self.api {
+            RemoteApi::Web3Signer => {
+                let url = self.url.join("api/v1/eth1/publicKeys")?;
+                let keys: Vec<String> = client
+                    .get(url)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+
+                keys.into_iter()
+                    .map(|key| Ok((key.clone(), public_key_address(&key)?)))
+                    .collect()
+            }
+            RemoteApi::JsonRpc => {
+                let accounts: Vec<Address> =
+                    rpc(&client, &self.url, "eth_accounts", json!([])).await?;
+                Ok(accounts.into_iter().map(|a| (a.to_string(), a)).collect())
+            }
+        }
+    }
+
+    /// A signer for one of the keys listed by `list_keys`
+    pub fn signer(&self, identifier: &str, address: Address) -> Result<RemoteSigner> {
+        Ok(RemoteSigner {
+            client: self.client()?,
+            url: self.url.clone(),
+            api: self.api,
+            identifier: identifier.into(),
+            address,
+            chain_id: None,
+        })
+    }
+}
+
+/// Forwards signing requests to a remote signing service
+///
+/// Neither API signs a bare hash, since Web3Signer always hashes what it's given, so `sign_hash`
+/// fails much like with ledgers. Messages, typed data and transactions are sent over in full
+/// instead, and other payloads (e.g. EIP-7702 authorizations) go through `sign_preimage`
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    client: reqwest::Client,
+    url: Url,
+    api: RemoteApi,
+    identifier: String,
+    address: Address,
+    chain_id: Option<u64>,
+}
+
+impl RemoteSigner {
+    pub async fn sign_message(&self, message: &[u8]) -> alloy::signers::Result<Signature> {
+        match self.api {
+            // Web3Signer hashes whatever it is given, so the EIP-191 prefix is added here
+            RemoteApi::Web3Signer => self.web3signer_sign(&eip191_message(message)).await,
+            RemoteApi::JsonRpc => {
+                self.rpc_signature(
+                    "eth_sign",
+                    json!([self.address, Bytes::copy_from_slice(message)]),
+                )
+                .await
+            }
+        }
+    }
+
+    pub async fn sign_typed_data(&self, data: &TypedData) -> alloy::signers::Result<Signature> {
+        match self.api {
+            RemoteApi::Web3Signer => {
+                let encoded = [
+                    &[0x19, 0x01][..],
+                    data.domain.separator().as_slice(),
+                    data.hash_struct().map_err(SignerError::other)?.as_slice(),
+                ]
+                .concat();
+
+                self.web3signer_sign(&encoded).await
+            }
+            RemoteApi::JsonRpc => {
+                self.rpc_signature("eth_signTypedData_v4", json!([self.address, data]))
+                    .await
+            }
+        }
+    }
+
+    /// Signs the keccak256 hash of `preimage`. Only Web3Signer signs arbitrary data
+    pub async fn sign_preimage(&self, preimage: &[u8]) -> alloy::signers::Result<Signature> {
+        match self.api {
+            RemoteApi::Web3Signer => self.web3signer_sign(preimage).await,
+            RemoteApi::JsonRpc => Err(self.unsupported("arbitrary data")),
+        }
+    }
+
+    fn unsupported(&self, what: &str) -> SignerError {
+        SignerError::other(format!(
+            "the remote signer at {} can't sign {what}",
+            self.url
+        ))
+    }
+
+    /// `POST /api/v1/eth1/sign/{identifier}`, which signs the keccak256 hash of `data`
+    async fn web3signer_sign(&self, data: &[u8]) -> alloy::signers::Result<Signature> {
+        let url = self
+            .url
+            .join(&format!("api/v1/eth1/sign/{}", self.identifier))
+            .map_err(SignerError::other)?;
+
+        let signature = self
+            .client
+            .post(url)
+            .json(&json!({ "data": Bytes::copy_from_slice(data) }))
+            .send()
+            .await
+            .and_then(|res| res.error_for_status())
+            .map_err(SignerError::other)?
+            .text()
+            .await
+            .map_err(SignerError::other)?;
+
+        parse_signature(signature.trim())
+    }
+
+    async fn rpc_signature(&self, method: &str, params: Json) -> alloy::signers::Result<Signature> {
+        let signature: Bytes = rpc(&self.client, &self.url, method, params)
+            .await
+            .map_err(SignerError::other)?;
+
+        Signature::try_from(signature.as_ref()).map_err(SignerError::other)
+    }
+}
+
+#[async_trait]
+impl alloy::signers::Signer<Signature> for RemoteSigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn chain_id(&self) -> Option<u64> {
+        self.chain_id
+    }
+
+    fn set_chain_id(&mut self, chain_id: Option<u64>) {
+        self.chain_id = chain_id;
+    }
+
+    async fn sign_hash(&self, _hash: &B256) -> alloy::signers::Result<Signature> {
+        Err(self.unsupported("a bare hash"))
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> alloy::signers::Result<Signature> {
+        RemoteSigner::sign_message(self, message).await
+    }
+
+    async fn sign_dynamic_typed_data(&self, data: &TypedData) -> alloy::signers::Result<Signature> {
+        RemoteSigner::sign_typed_data(self, data).await
+    }
+}
+
+#[async_trait]
+impl TxSigner<Signature> for RemoteSigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<Signature>,
+    ) -> alloy::signers::Result<Signature> {
+        if let Some(chain_id) = self.chain_id
+            && !tx.set_chain_id_checked(chain_id)
+        {
+            return Err(SignerError::TransactionChainIdMismatch {
+                signer: chain_id,
+                tx: tx.chain_id().unwrap_or_default(),
+            });
+        }
+
+        match self.api {
+            RemoteApi::Web3Signer => self.web3signer_sign(&tx.encoded_for_signing()).await,
+
+            // the signer returns the whole signed transaction, from which only the signature is
+            // kept
+            RemoteApi::JsonRpc => {
+                let request = TransactionRequest {
+                    from: Some(self.address),
+                    to: Some(tx.kind()),
+                    value: Some(tx.value()),
+                    input: TransactionInput::new(tx.input().clone()),
+                    nonce: Some(tx.nonce()),
+                    chain_id: tx.chain_id(),
+                    gas: Some(tx.gas_limit()),
+                    gas_price: tx.gas_price(),
+                    max_fee_per_gas: tx.is_dynamic_fee().then(|| tx.max_fee_per_gas()),
+                    max_priority_fee_per_gas: tx.max_priority_fee_per_gas(),
+                    access_list: tx.access_list().cloned(),
+                    authorization_list: tx.authorization_list().map(<[_]>::to_vec),
+                    transaction_type: Some(tx.ty()),
+                    ..Default::default()
+                };
+
+                let raw: Bytes = rpc(
+                    &self.client,
+                    &self.url,
+                    "eth_signTransaction",
+                    json!([request]),
+                )
+                .await
+                .map_err(SignerError::other)?;
+                let signed =
+                    TxEnvelope::decode_2718(&mut raw.as_ref()).map_err(SignerError::other)?;
+
+                Ok(*signed.signature())
+            }
+        }
+    }
+}
+
+/// A single JSON-RPC request, returning its result
+async fn rpc<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    url: &Url,
+    method: &str,
+    params: Json,
+) -> Result<T> {
+    let res: Json = client
+        .post(url.clone())
+        .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    if let Some(error) = res.get("error") {
+        return Err(eyre!("{method} failed: {}", error["message"]));
+    }
+
+    Ok(serde_json::from_value(res["result"].clone())?)
+}
+
+fn parse_signature(hex: &str) -> alloy::signers::Result<Signature> {
+    let bytes: Bytes = hex.parse().map_err(SignerError::other)?;
+    Signature::try_from(bytes.as_ref()).map_err(SignerError::other)
+}
+
+/// The address of an uncompressed secp256k1 public key, with or without its `0x04` prefix
+fn public_key_address(key: &str) -> Result<Address> {
+    let bytes: Bytes = key.parse()?;
+    let raw = match bytes.len() {
+        65 if bytes[0] == 0x04 => &bytes[1..],
+        64 => &bytes[..],
+        _ => return Err(eyre!("unexpected public key format: {key}")),
+    };
+
+    Ok(Address::from_raw_public_key(raw))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        eips::{Encodable2718 as _, eip7702::Authorization},
+        network::{TransactionBuilder as _, TxSignerSync as _},
+        primitives::keccak256,
+        signers::{
+            Signer as _, SignerSync as _, k256::elliptic_curve::sec1::ToEncodedPoint as _,
+            local::PrivateKeySigner,
+        },
+    };
+    use axum::{
+        Json as AxumJson, Router,
+        extract::{Path, State},
+        routing::{get, post},
+    };
+
+    use super::*;
+    use crate::Signer;
+
+    /// A stand-in for Web3Signer's REST API, backed by a single local key
+    async fn web3signer(key: PrivateKeySigner) -> Url {
+        async fn public_keys(State(key): State<PrivateKeySigner>) -> AxumJson<Vec<String>> {
+            let public = key.credential().verifying_key().to_encoded_point(false);
+            AxumJson(vec![Bytes::copy_from_slice(public.as_bytes()).to_string()])
+        }
+
+        async fn sign(
+            State(key): State<PrivateKeySigner>,
+            Path(_identifier): Path<String>,
+            AxumJson(body): AxumJson<Json>,
+        ) -> String {
+            let data: Bytes = serde_json::from_value(body["data"].clone()).unwrap();
+            let signature = key.sign_hash_sync(&keccak256(&data)).unwrap();
+            Bytes::from(signature.as_bytes()).to_string()
+        }
+
+        let app = Router::new()
+            .route("/api/v1/eth1/publicKeys", get(public_keys))
+            .route("/api/v1/eth1/sign/{identifier}", post(sign))
+            .with_state(key);
+
+        serve(app).await
+    }
+
+    /// A stand-in for a JSON-RPC signer, backed by a single local key
+    async fn json_rpc(key: PrivateKeySigner) -> Url {
+        async fn handle(
+            State(key): State<PrivateKeySigner>,
+            AxumJson(req): AxumJson<Json>,
+        ) -> AxumJson<Json> {
+            let params = &req["params"];
+            let result = match req["method"].as_str().unwrap() {
+                "eth_accounts" => json!([key.address()]),
+                "eth_sign" => {
+                    let message: Bytes = serde_json::from_value(params[1].clone()).unwrap();
+                    let signature = key.sign_message_sync(&message).unwrap();
+                    json!(Bytes::from(signature.as_bytes()))
+                }
+                "eth_signTypedData_v4" => {
+                    let data: TypedData = serde_json::from_value(params[1].clone()).unwrap();
+                    let signature = key.sign_dynamic_typed_data_sync(&data).unwrap();
+                    json!(Bytes::from(signature.as_bytes()))
+                }
+                "eth_signTransaction" => {
+                    let request: TransactionRequest =
+                        serde_json::from_value(params[0].clone()).unwrap();
+                    let mut tx = request.build_typed_tx().unwrap();
+                    let signature = key.sign_transaction_sync(&mut tx).unwrap();
+                    let signed: TxEnvelope = tx.into_signed(signature).into();
+                    json!(Bytes::from(signed.encoded_2718()))
+                }
+                _ => Json::Null,
+            };
+
+            AxumJson(json!({ "jsonrpc": "2.0", "id": req["id"], "result": result }))
+        }
+
+        serve(Router::new().route("/", post(handle)).with_state(key)).await
+    }
+
+    async fn serve(app: Router) -> Url {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        format!("http://{addr}/").parse().unwrap()
+    }
+
+    async fn remote_signer(api: RemoteApi, url: Url) -> RemoteSigner {
+        let endpoint = RemoteEndpoint {
+            url,
+            api,
+            auth: Default::default(),
+            ca: None,
+        };
+
+        let keys = endpoint.list_keys().await.unwrap();
+        let (identifier, address) = &keys[0];
+        let mut signer = endpoint.signer(identifier, *address).unwrap();
+        signer.set_chain_id(Some(1));
+        signer
+    }
+
+    fn typed_data() -> TypedData {
+        serde_json::from_value(json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "chainId", "type": "uint256" }
+                ],
+                "Mail": [{ "name": "contents", "type": "string" }]
+            },
+            "primaryType": "Mail",
+            "domain": { "name": "ethui", "chainId": 1 },
+            "message": { "contents": "hello" }
+        }))
+        .unwrap()
+    }
+
+    fn transaction() -> TransactionRequest {
+        TransactionRequest::default()
+            .to(Address::repeat_byte(1))
+            .value(U256::from(1))
+            .nonce(0)
+            .gas_limit(21000)
+            .max_fee_per_gas(2_000_000_000)
+            .max_priority_fee_per_gas(1_000_000_000)
+            .with_chain_id(1)
+    }
+
+    #[rstest::rstest]
+    #[case::web3signer(RemoteApi::Web3Signer)]
+    #[case::json_rpc(RemoteApi::JsonRpc)]
+    #[tokio::test]
+    async fn signs_remotely(#[case] api: RemoteApi) {
+        let key = PrivateKeySigner::random();
+        let url = match api {
+            RemoteApi::Web3Signer => web3signer(key.clone()).await,
+            RemoteApi::JsonRpc => json_rpc(key.clone()).await,
+        };
+        let signer = remote_signer(api, url).await;
+
+        assert_eq!(alloy::signers::Signer::address(&signer), key.address());
+
+        let signature = signer.sign_message(b"hello").await.unwrap();
+        assert_eq!(
+            signature.recover_address_from_msg(b"hello").unwrap(),
+            key.address()
+        );
+
+        let data = typed_data();
+        let signature = signer.sign_typed_data(&data).await.unwrap();
+        assert_eq!(
+            signature
+                .recover_address_from_prehash(&data.eip712_signing_hash().unwrap())
+                .unwrap(),
+            key.address()
+        );
+
+        let mut tx = transaction().build_typed_tx().unwrap();
+        let signature = signer.sign_transaction(&mut tx).await.unwrap();
+        assert_eq!(
+            signature
+                .recover_address_from_prehash(&tx.signature_hash())
+                .unwrap(),
+            key.address()
+        );
+
+        let authorization = Authorization {
+            chain_id: U256::from(1),
+            address: Address::repeat_byte(2),
+            nonce: 0,
+        };
+        let signed = Signer::Remote(signer.clone())
+            .sign_authorization(authorization)
+            .await;
+        match api {
+            RemoteApi::Web3Signer => {
+                assert_eq!(signed.unwrap().recover_authority().unwrap(), key.address())
+            }
+            RemoteApi::JsonRpc => assert!(signed.is_err()),
+        }
+
+        assert!(signer.sign_hash(&B256::ZERO).await.is_err());
+    }
+}
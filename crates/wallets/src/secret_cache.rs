@@ -1,36 +1,49 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::{Arc, LazyLock, Weak},
+    time::{Duration, SystemTime},
+};
 
 use color_eyre::eyre::eyre;
 use ethui_dialogs::{Dialog, DialogMsg};
+use ethui_settings::{LockPolicy, SettingsActorExt as _, try_settings};
 use ethui_types::prelude::*;
 use secrets::SecretVec;
 use tokio::{
     sync::{Mutex, MutexGuard, RwLock, RwLockReadGuard},
     task::JoinHandle,
+    time::Instant,
 };
 use zeroize::Zeroizing;
 
-/// A cache for secret material (private keys, mnemonics) that automatically expires after a
-/// timeout. Wraps the secret in `SecretVec` for memory safety (mlock'd pages).
+/// Every cache that has held a secret, so that they can all be locked at once
+static CACHES: LazyLock<std::sync::Mutex<Vec<Weak<Inner>>>> = LazyLock::new(Default::default);
+
+/// A cache for secret material (private keys, mnemonics) that expires according to the wallet's
+/// `LockPolicy`. Wraps the secret in `SecretVec` for memory safety (mlock'd pages).
 ///
 /// The additional `Mutex` within is there because `SecretVec` is not `Send`.
 #[derive(Debug, Clone, Default)]
-pub struct SecretCache {
-    secret: Arc<RwLock<Option<Mutex<SecretVec<u8>>>>>,
-    expirer: Arc<RwLock<Option<JoinHandle<()>>>>,
+pub struct SecretCache(Arc<Inner>);
+
+#[derive(Debug, Default)]
+struct Inner {
+    secret: RwLock<Option<Mutex<SecretVec<u8>>>>,
+    expirer: RwLock<Option<JoinHandle<()>>>,
+    policy: std::sync::Mutex<LockPolicy>,
+    last_used: std::sync::Mutex<Option<Instant>>,
 }
 
 impl SecretCache {
     /// Returns `true` if a secret is currently cached (not yet expired).
     pub async fn is_unlocked(&self) -> bool {
-        self.secret.read().await.is_some()
+        self.0.secret.read().await.is_some()
     }
 
-    /// Stores a `SecretVec` in the cache and sets a 60-second expiration timer.
+    /// Stores a `SecretVec` in the cache, to be dropped according to `policy`.
     /// Aborts any existing expirer task before replacing.
-    pub async fn store(&self, secret: SecretVec<u8>) {
-        let mut expirer_handle = self.expirer.write().await;
-        let mut secret_handle = self.secret.write().await;
+    pub async fn store(&self, secret: SecretVec<u8>, policy: LockPolicy) {
+        let mut expirer_handle = self.0.expirer.write().await;
+        let mut secret_handle = self.0.secret.write().await;
 
         // abort the previous expirer so it doesn't clear the new secret
         if let Some(handle) = expirer_handle.take() {
@@ -38,28 +51,171 @@ impl SecretCache {
         }
 
         *secret_handle = Some(Mutex::new(secret));
+        *self.0.last_used.lock().unwrap() = Some(Instant::now());
+        *self.0.policy.lock().unwrap() = policy.clone();
+
+        let inner = Arc::clone(&self.0);
+        *expirer_handle = match policy {
+            LockPolicy::Timeout { secs } => Some(tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_secs(secs)).await;
+                inner.forget().await;
+            })),
+            LockPolicy::Idle { secs } => Some(tokio::spawn(async move {
+                let timeout = Duration::from_secs(secs);
+                loop {
+                    let idle = inner.idle();
+                    if idle >= timeout {
+                        break;
+                    }
+                    tokio::time::sleep(timeout - idle).await;
+                }
+                inner.forget().await;
+            })),
+            LockPolicy::OnSleep | LockPolicy::Never => None,
+        };
+
+        drop(secret_handle);
+        drop(expirer_handle);
 
-        // set up cache expiration for 1 minute
-        let clone = Arc::clone(&self.secret);
-        *expirer_handle = Some(tokio::spawn(async move {
-            tokio::time::sleep(Duration::from_secs(60)).await;
-            clone.write().await.take();
-        }));
+        register(&self.0);
+        notify_lock_changed().await;
+    }
+
+    /// Drops the cached secret right away, instead of waiting for it to expire
+    pub async fn clear(&self) {
+        if let Some(handle) = self.0.expirer.write().await.take() {
+            handle.abort();
+        }
+
+        self.0.forget().await;
     }
 
     /// Acquires a read lock on the cached secret, returning an error if the cache is empty.
+    /// Counts as a use of the secret, for idle timeouts
     ///
     /// Callers should ensure the cache is populated first (typically by calling `unlock`),
     /// but this method handles the race where the expirer task clears the cache in between.
     pub async fn read(&self) -> color_eyre::Result<SecretGuard<'_>> {
-        let guard = self.secret.read().await;
+        let guard = self.0.secret.read().await;
         if guard.is_none() {
             return Err(eyre!("secret cache is empty; wallet may be locked"));
         }
+
+        *self.0.last_used.lock().unwrap() = Some(Instant::now());
         Ok(SecretGuard(guard))
     }
 }
 
+impl Inner {
+    async fn forget(&self) {
+        if self.secret.write().await.take().is_some() {
+            notify_lock_changed().await;
+        }
+    }
+
+    fn idle(&self) -> Duration {
+        self.last_used
+            .lock()
+            .unwrap()
+            .map(|t| t.elapsed())
+            .unwrap_or_default()
+    }
+}
+
+fn register(inner: &Arc<Inner>) {
+    let mut caches = CACHES.lock().unwrap();
+    caches.retain(|c| c.strong_count() > 0);
+
+    if !caches.iter().any(|c| c.as_ptr() == Arc::as_ptr(inner)) {
+        caches.push(Arc::downgrade(inner));
+    }
+}
+
+/// Caches that are still alive
+fn all_caches() -> Vec<SecretCache> {
+    CACHES
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(Weak::upgrade)
+        .map(SecretCache)
+        .collect()
+}
+
+async fn notify_lock_changed() {
+    ethui_broadcast::wallets_lock_changed().await;
+    ethui_broadcast::ui_notify(UINotify::WalletsLockChanged).await;
+}
+
+/// Locks every wallet, regardless of its policy
+pub async fn lock_all() {
+    for cache in all_caches() {
+        cache.clear().await;
+    }
+}
+
+/// Locks wallets once the system resumes from sleep
+///
+/// There's no portable way to be told about sleep or screen locks, so a resume is detected as a
+/// short sleep taking far longer than it should by the wall clock. This doesn't rely on how the
+/// monotonic clock behaves during suspend, which differs across platforms: it stops on Linux and
+/// macOS, but keeps going on Windows. Time-based policies are locked as well, since their expiry
+/// timers run on the monotonic clock and so may not have fired
+pub(crate) async fn watch_sleep() {
+    const TICK: Duration = Duration::from_secs(10);
+    const TOLERANCE: Duration = Duration::from_secs(30);
+
+    loop {
+        let wall = SystemTime::now();
+
+        tokio::time::sleep(TICK).await;
+
+        let slept = wall.elapsed().unwrap_or_default() > TICK + TOLERANCE;
+        if slept {
+            lock_on_resume(all_caches()).await;
+        }
+    }
+}
+
+/// Locks every cache whose policy doesn't keep it unlocked across sleep
+async fn lock_on_resume(caches: Vec<SecretCache>) {
+    for cache in caches {
+        if *cache.0.policy.lock().unwrap() != LockPolicy::Never {
+            cache.clear().await;
+        }
+    }
+}
+
+/// Lock policies are keyed by wallet name, so they're moved along when a wallet is renamed, and
+/// dropped when it's removed
+pub(crate) async fn move_lock_policy(from: &str, to: Option<String>) -> Result<()> {
+    let Ok(settings) = try_settings() else {
+        return Ok(());
+    };
+
+    let Some(policy) = settings.get_all().await?.lock_policies.get(from).cloned() else {
+        return Ok(());
+    };
+
+    settings.set_lock_policy(from.into(), None).await?;
+    if let Some(to) = to {
+        settings.set_lock_policy(to, Some(policy)).await?;
+    }
+
+    Ok(())
+}
+
+async fn lock_policy(wallet_name: &str) -> LockPolicy {
+    match try_settings() {
+        Ok(settings) => settings
+            .get_all()
+            .await
+            .map(|s| s.lock_policy(wallet_name))
+            .unwrap_or_default(),
+        Err(_) => Default::default(),
+    }
+}
+
 /// RAII guard that holds a read lock on the secret cache and provides access to the inner
 /// `Mutex<SecretVec<u8>>`.
 pub struct SecretGuard<'a>(RwLockReadGuard<'a, Option<Mutex<SecretVec<u8>>>>);
@@ -90,10 +246,25 @@ pub fn string_from_secret(secret: &SecretVec<u8>) -> String {
 /// The `try_decrypt` closure is called with each password attempt and should return
 /// `Ok(secret_bytes)` on success or `Err(...)` on failure.
 ///
-/// On success, the decrypted secret is stored in the provided `SecretCache`.
+/// On success, the decrypted secret is stored in the provided `SecretCache`, under the wallet's
+/// lock policy.
 pub async fn unlock_with_dialog<F>(
     cache: &SecretCache,
     wallet_name: &str,
+    try_decrypt: F,
+) -> color_eyre::Result<()>
+where
+    F: FnMut(&str) -> color_eyre::Result<SecretVec<u8>>,
+{
+    unlock_key_with_dialog(cache, wallet_name, None, try_decrypt).await
+}
+
+/// Same as [`unlock_with_dialog`], for wallets holding several separately encrypted keys. The key
+/// is shown in the dialog, so that the user knows which password to enter
+pub async fn unlock_key_with_dialog<F>(
+    cache: &SecretCache,
+    wallet_name: &str,
+    key: Option<&str>,
     mut try_decrypt: F,
 ) -> color_eyre::Result<()>
 where
@@ -103,7 +274,10 @@ where
         return Ok(());
     }
 
-    let dialog = Dialog::new("wallet-unlock", serde_json::json!({ "name": wallet_name }));
+    let dialog = Dialog::new(
+        "wallet-unlock",
+        serde_json::json!({ "name": wallet_name, "key": key }),
+    );
     dialog.open().await?;
 
     for _ in 0..3 {
@@ -118,7 +292,7 @@ where
         };
 
         if let Ok(secret) = try_decrypt(&password) {
-            cache.store(secret).await;
+            cache.store(secret, lock_policy(wallet_name).await).await;
             return Ok(());
         }
 
@@ -127,3 +301,100 @@ where
 
     Err(eyre!("user failed to unlock the wallet"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn unlocked(policy: LockPolicy) -> SecretCache {
+        let cache = SecretCache::default();
+        cache
+            .store(string_into_secret("secret".into()), policy)
+            .await;
+        cache
+    }
+
+    async fn sleep(secs: u64) {
+        tokio::time::sleep(Duration::from_secs(secs)).await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn timeout_locks_a_fixed_time_after_unlocking() {
+        let cache = unlocked(LockPolicy::Timeout { secs: 60 }).await;
+
+        sleep(30).await;
+        cache.read().await.unwrap();
+        sleep(29).await;
+        assert!(cache.is_unlocked().await);
+
+        sleep(2).await;
+        assert!(!cache.is_unlocked().await);
+        assert!(cache.read().await.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn idle_locks_once_unused_for_a_while() {
+        let cache = unlocked(LockPolicy::Idle { secs: 60 }).await;
+
+        sleep(40).await;
+        cache.read().await.unwrap();
+        sleep(40).await;
+        assert!(cache.is_unlocked().await);
+
+        sleep(21).await;
+        assert!(!cache.is_unlocked().await);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn on_sleep_and_never_do_not_expire() {
+        let on_sleep = unlocked(LockPolicy::OnSleep).await;
+        let never = unlocked(LockPolicy::Never).await;
+
+        sleep(365 * 24 * 60 * 60).await;
+
+        assert!(on_sleep.is_unlocked().await);
+        assert!(never.is_unlocked().await);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn storing_again_replaces_the_previous_policy() {
+        let cache = unlocked(LockPolicy::Timeout { secs: 10 }).await;
+        cache
+            .store(string_into_secret("secret".into()), LockPolicy::Never)
+            .await;
+
+        sleep(20).await;
+
+        assert!(cache.is_unlocked().await);
+    }
+
+    #[tokio::test]
+    async fn clear_locks_regardless_of_policy() {
+        let cache = unlocked(LockPolicy::Never).await;
+
+        cache.clear().await;
+
+        assert!(!cache.is_unlocked().await);
+    }
+
+    #[tokio::test]
+    async fn resuming_locks_all_but_never() {
+        let timeout = unlocked(LockPolicy::Timeout { secs: 600 }).await;
+        let idle = unlocked(LockPolicy::Idle { secs: 600 }).await;
+        let on_sleep = unlocked(LockPolicy::OnSleep).await;
+        let never = unlocked(LockPolicy::Never).await;
+
+        lock_on_resume(vec![
+            timeout.clone(),
+            idle.clone(),
+            on_sleep.clone(),
+            never.clone(),
+        ])
+        .await;
+
+        assert!(!timeout.is_unlocked().await);
+        assert!(!idle.is_unlocked().await);
+        assert!(!on_sleep.is_unlocked().await);
+        assert!(never.is_unlocked().await);
+    }
+}
@@ -1,20 +1,26 @@
 use alloy::{
+    dyn_abi::TypedData,
+    eips::eip7702::{Authorization, SignedAuthorization, constants::MAGIC},
     network::EthereumWallet,
     primitives::{Address, B256, Signature},
+    rlp::Encodable as _,
     signers::{ledger::LedgerSigner, local::PrivateKeySigner},
 };
 use async_trait::async_trait;
 
+use crate::remote_signer::RemoteSigner;
+
 #[derive(Debug)]
 pub enum Signer {
     Local(PrivateKeySigner),
     Ledger(LedgerSigner),
+    Remote(RemoteSigner),
 }
 
 impl Signer {
     pub fn is_ledger(&self) -> bool {
         match self {
-            Self::Local(_) => false,
+            Self::Local(_) | Self::Remote(_) => false,
             Self::Ledger(_) => true,
         }
     }
@@ -26,6 +32,7 @@ impl alloy::signers::Signer<Signature> for Signer {
         match self {
             Self::Local(signer) => signer.address(),
             Self::Ledger(signer) => signer.address(),
+            Self::Remote(signer) => signer.address(),
         }
     }
 
@@ -33,6 +40,7 @@ impl alloy::signers::Signer<Signature> for Signer {
         match self {
             Self::Local(signer) => signer.chain_id(),
             Self::Ledger(signer) => signer.chain_id(),
+            Self::Remote(signer) => signer.chain_id(),
         }
     }
 
@@ -40,6 +48,7 @@ impl alloy::signers::Signer<Signature> for Signer {
         match self {
             Self::Local(signer) => signer.set_chain_id(chain_id),
             Self::Ledger(signer) => signer.set_chain_id(chain_id),
+            Self::Remote(signer) => signer.set_chain_id(chain_id),
         };
     }
 
@@ -47,15 +56,77 @@ impl alloy::signers::Signer<Signature> for Signer {
         match self {
             Self::Local(signer) => signer.sign_hash(hash).await,
             Self::Ledger(signer) => signer.sign_hash(hash).await,
+            Self::Remote(signer) => signer.sign_hash(hash).await,
+        }
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> alloy::signers::Result<Signature> {
+        match self {
+            Self::Local(signer) => signer.sign_message(message).await,
+            Self::Ledger(signer) => signer.sign_message(message).await,
+            Self::Remote(signer) => signer.sign_message(message).await,
+        }
+    }
+
+    async fn sign_dynamic_typed_data(&self, data: &TypedData) -> alloy::signers::Result<Signature> {
+        match self {
+            Self::Local(signer) => signer.sign_dynamic_typed_data(data).await,
+            Self::Ledger(signer) => signer.sign_dynamic_typed_data(data).await,
+            Self::Remote(signer) => signer.sign_typed_data(data).await,
         }
     }
 }
 
 impl Signer {
+    /// Signs an EIP-7702 authorization, delegating the signer's account to the authorized code
+    pub async fn sign_authorization(
+        &self,
+        authorization: Authorization,
+    ) -> alloy::signers::Result<SignedAuthorization> {
+        // remote signers hash what they're given themselves, so they get the preimage instead
+        let signature = match self {
+            Self::Remote(signer) => {
+                let mut preimage = vec![MAGIC];
+                authorization.encode(&mut preimage);
+                signer.sign_preimage(&preimage).await?
+            }
+            _ => {
+                let hash = authorization.signature_hash();
+                alloy::signers::Signer::sign_hash(self, &hash).await?
+            }
+        };
+
+        Ok(authorization.into_signed(signature))
+    }
+
     pub fn to_wallet(self) -> EthereumWallet {
         match self {
             Self::Local(signer) => EthereumWallet::from(signer),
             Self::Ledger(signer) => EthereumWallet::from(signer),
+            Self::Remote(signer) => EthereumWallet::from(signer),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn signs_authorizations_as_the_signer() {
+        let key = PrivateKeySigner::random();
+        let authorization = Authorization {
+            chain_id: alloy::primitives::U256::from(31337),
+            address: Address::repeat_byte(2),
+            nonce: 7,
+        };
+
+        let signed = Signer::Local(key.clone())
+            .sign_authorization(authorization.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(signed.recover_authority().unwrap(), key.address());
+        assert_eq!(*signed.inner(), authorization);
+    }
+}
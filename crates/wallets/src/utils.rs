@@ -1,10 +1,20 @@
-use alloy::signers::{
-    ledger::{HDPath, LedgerSigner},
-    local::{MnemonicBuilder, coins_bip39::English},
+use std::path::{Path, PathBuf};
+
+use alloy::{
+    eips::eip7702::{Authorization, SignedAuthorization, constants::EIP7702_DELEGATION_DESIGNATOR},
+    providers::Provider,
+    signers::{
+        ledger::{HDPath, LedgerSigner},
+        local::{LocalSigner, MnemonicBuilder, coins_bip39::English},
+    },
 };
 use ethui_types::prelude::*;
 use once_cell::sync::Lazy;
+use rand_core::OsRng;
 use tokio::sync::Mutex;
+use zeroize::Zeroizing;
+
+use crate::{Signer, Wallet, WalletControl as _};
 
 pub(crate) static HID_MUTEX: Lazy<Mutex<()>> = Lazy::new(Default::default);
 
@@ -63,6 +73,141 @@ pub(crate) async fn ledger_derive_multiple(paths: Vec<String>) -> Result<Vec<(St
     Ok(res)
 }
 
+/// Exports a key as a Web3 Secret Storage (V3) keystore, written to `dir` and named after its
+/// address
+///
+/// Like revealing a mnemonic, the wallet's password is always asked for, even if it's currently
+/// unlocked
+pub(crate) async fn export_keystore(
+    wallet: &Wallet,
+    path: &str,
+    dir: &Path,
+    password: &str,
+) -> Result<PathBuf> {
+    wallet.lock().await;
+
+    // the chain id plays no part in the key itself
+    let Signer::Local(signer) = wallet.build_signer(1, path).await? else {
+        return Err(eyre!("{} keys cannot be exported", wallet.wallet_type()));
+    };
+
+    let key = Zeroizing::new(signer.credential().to_bytes());
+
+    // an existing keystore may be encrypted with a different password, and would be lost
+    let name = signer.address().to_string();
+    if dir.join(&name).exists() {
+        return Err(eyre!("{} already exists", dir.join(&name).display()));
+    }
+
+    LocalSigner::encrypt_keystore(dir, &mut OsRng, key.as_slice(), password, Some(&name))?;
+
+    Ok(dir.join(name))
+}
+
+/// The contracts the given accounts delegate to via EIP-7702. Delegated accounts have their code
+/// set to the designator prefix followed by the delegate's address
+pub(crate) async fn get_delegations(
+    chain_id: u64,
+    addresses: Vec<Address>,
+) -> Result<HashMap<Address, Address>> {
+    let provider = ethui_networks::get_provider(chain_id).await?;
+
+    get_delegations_with(&provider, addresses).await
+}
+
+async fn get_delegations_with(
+    provider: &impl Provider,
+    addresses: Vec<Address>,
+) -> Result<HashMap<Address, Address>> {
+    let mut delegations = HashMap::new();
+    for address in addresses {
+        let code = provider.get_code_at(address).await?;
+
+        if let Some(delegate) = code
+            .strip_prefix(EIP7702_DELEGATION_DESIGNATOR.as_slice())
+            .filter(|delegate| delegate.len() == 20)
+        {
+            delegations.insert(address, Address::from_slice(delegate));
+        }
+    }
+
+    Ok(delegations)
+}
+
+/// Signs an EIP-7702 authorization with the wallet holding `address`
+///
+/// Without an explicit nonce the account's current one is used, which is only valid when the
+/// authorization is sent by a different account. A self-sponsored authorization needs the next one
+pub(crate) async fn sign_authorization(
+    address: Address,
+    chain_id: u64,
+    delegate: Address,
+    nonce: Option<u64>,
+) -> Result<SignedAuthorization> {
+    let (wallet, path) = crate::find_wallet(address)
+        .await
+        .with_context(|| format!("{address} is not an ethui account"))?;
+
+    let nonce = match nonce {
+        Some(nonce) => nonce,
+        None => {
+            ethui_networks::get_provider(chain_id)
+                .await?
+                .get_transaction_count(address)
+                .await?
+        }
+    };
+
+    let signer = wallet.build_signer(chain_id, &path).await?;
+    let authorization = Authorization {
+        chain_id: U256::from(chain_id),
+        address: delegate,
+        nonce,
+    };
+
+    Ok(signer.sign_authorization(authorization).await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{providers::ProviderBuilder, transports::mock::Asserter};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn finds_delegations_by_code() {
+        let delegate = Address::repeat_byte(9);
+        let (delegated, eoa, contract, truncated) = (
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            Address::repeat_byte(3),
+            Address::repeat_byte(4),
+        );
+
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        asserter.push_success(&Bytes::from(
+            [
+                EIP7702_DELEGATION_DESIGNATOR.as_slice(),
+                delegate.as_slice(),
+            ]
+            .concat(),
+        ));
+        asserter.push_success(&Bytes::new());
+        asserter.push_success(&Bytes::from_static(&[0x60, 0x80, 0x60, 0x40]));
+        asserter.push_success(&Bytes::from(
+            [EIP7702_DELEGATION_DESIGNATOR.as_slice(), &delegate[..19]].concat(),
+        ));
+
+        let delegations =
+            get_delegations_with(&provider, vec![delegated, eoa, contract, truncated])
+                .await
+                .unwrap();
+
+        assert_eq!(delegations, HashMap::from([(delegated, delegate)]));
+    }
+}
+
 // TODO: can I enable this test again in the future?
 //#[cfg(test)]
 //mod tests {
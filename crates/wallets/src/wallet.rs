@@ -3,7 +3,9 @@ use enum_dispatch::enum_dispatch;
 use ethui_types::prelude::*;
 
 use super::wallets::{HDWallet, Impersonator, JsonKeystoreWallet, LedgerWallet, PlaintextWallet};
-use crate::wallets::PrivateKeyWallet;
+use crate::wallets::{
+    KeystoreDirWallet, PrivateKeyWallet, RemoteWallet, SafeWallet, SmartAccountWallet,
+};
 
 #[async_trait]
 #[enum_dispatch(Wallet)]
@@ -34,6 +36,15 @@ pub trait WalletControl: Sync + Send + Deserialize<'static> + Serialize + std::f
     fn is_dev(&self) -> bool {
         false
     }
+
+    /// Whether the wallet's secret is currently held in memory. `None` for wallets that never ask
+    /// for a password
+    async fn is_unlocked(&self) -> Option<bool> {
+        None
+    }
+
+    /// Forgets the wallet's secret, so that its password is asked for again on next use
+    async fn lock(&self) {}
 }
 
 /// needs to be a separate trait, because enum_dispatch does not allow for static functions
@@ -57,6 +68,14 @@ pub enum Wallet {
     Ledger(LedgerWallet),
 
     PrivateKey(PrivateKeyWallet),
+
+    Safe(SafeWallet),
+
+    SmartAccount(SmartAccountWallet),
+
+    KeystoreDir(KeystoreDirWallet),
+
+    Remote(RemoteWallet),
 }
 
 impl Wallet {
@@ -68,6 +87,10 @@ impl Wallet {
             Wallet::Impersonator(_) => "Impersonator",
             Wallet::Ledger(_) => "Ledger",
             Wallet::PrivateKey(_) => "PrivateKey",
+            Wallet::Safe(_) => "Safe",
+            Wallet::SmartAccount(_) => "SmartAccount",
+            Wallet::KeystoreDir(_) => "KeystoreDir",
+            Wallet::Remote(_) => "Remote",
         }
     }
 
@@ -81,6 +104,10 @@ impl Wallet {
             "impersonator" => Impersonator::create(params).await?,
             "ledger" => LedgerWallet::create(params).await?,
             "privateKey" => PrivateKeyWallet::create(params).await?,
+            "safe" => SafeWallet::create(params).await?,
+            "smartAccount" => SmartAccountWallet::create(params).await?,
+            "keystoreDir" => KeystoreDirWallet::create(params).await?,
+            "remote" => RemoteWallet::create(params).await?,
             _ => return Err(eyre!("invalid wallet type: {}", wallet_type)),
         };
 
@@ -96,6 +123,10 @@ pub enum WalletType {
     Impersonator,
     Ledger,
     PrivateKey,
+    Safe,
+    SmartAccount,
+    KeystoreDir,
+    Remote,
 }
 
 impl std::fmt::Display for WalletType {
@@ -110,6 +141,10 @@ impl std::fmt::Display for WalletType {
                 WalletType::Impersonator => "impersonator",
                 WalletType::Ledger => "ledger",
                 WalletType::PrivateKey => "privateKey",
+                WalletType::Safe => "safe",
+                WalletType::SmartAccount => "smartAccount",
+                WalletType::KeystoreDir => "keystoreDir",
+                WalletType::Remote => "remote",
             }
         )
     }
@@ -124,6 +159,10 @@ impl From<&Wallet> for WalletType {
             Wallet::Impersonator(_) => Self::Impersonator,
             Wallet::Ledger(_) => Self::Ledger,
             Wallet::PrivateKey(_) => Self::PrivateKey,
+            Wallet::Safe(_) => Self::Safe,
+            Wallet::SmartAccount(_) => Self::SmartAccount,
+            Wallet::KeystoreDir(_) => Self::KeystoreDir,
+            Wallet::Remote(_) => Self::Remote,
         }
     }
 }
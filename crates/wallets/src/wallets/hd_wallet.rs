@@ -5,6 +5,7 @@ use alloy::signers::{
 use async_trait::async_trait;
 use ethui_crypto::{self, EncryptedData};
 use ethui_types::prelude::*;
+use zeroize::Zeroizing;
 
 use crate::{
     Signer, Wallet, WalletControl,
@@ -107,6 +108,14 @@ impl WalletControl for HDWallet {
 
         Ok(Signer::Local(signer))
     }
+
+    async fn is_unlocked(&self) -> Option<bool> {
+        Some(self.cache.is_unlocked().await)
+    }
+
+    async fn lock(&self) {
+        self.cache.clear().await;
+    }
 }
 
 impl HDWallet {
@@ -160,6 +169,35 @@ impl HDWallet {
         Ok(())
     }
 
+    /// Re-encrypts the mnemonic under a new password
+    pub(crate) fn change_password(&mut self, old: &str, new: &str) -> color_eyre::Result<()> {
+        let mnemonic = Zeroizing::new(ethui_crypto::decrypt(&self.ciphertext, old)?);
+        self.ciphertext = ethui_crypto::encrypt(&*mnemonic, new)?;
+
+        Ok(())
+    }
+
+    /// Decrypts the mnemonic for display. The password is always asked for, even if the wallet is
+    /// currently unlocked
+    pub(crate) async fn reveal_mnemonic(&self) -> color_eyre::Result<Zeroizing<String>> {
+        let cache = SecretCache::default();
+        let ciphertext = self.ciphertext.clone();
+        unlock_with_dialog(&cache, &self.name, |password| {
+            let mnemonic = ethui_crypto::decrypt(&ciphertext, password)?;
+            Ok(string_into_secret(mnemonic))
+        })
+        .await?;
+
+        let mnemonic = {
+            let guard = cache.read().await?;
+            let secret = guard.lock().await;
+            Zeroizing::new(string_from_secret(&secret))
+        };
+        cache.clear().await;
+
+        Ok(mnemonic)
+    }
+
     async fn unlock(&self) -> color_eyre::Result<()> {
         let ciphertext = self.ciphertext.clone();
         unlock_with_dialog(&self.cache, &self.name, |password| {
@@ -182,6 +220,7 @@ pub struct HDWalletParams {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::secret_cache::{string_from_secret, string_into_secret};
 
     #[test]
@@ -193,4 +232,27 @@ mod tests {
 
         assert_eq!(signer, recovered_signer);
     }
+
+    #[tokio::test]
+    async fn change_password() {
+        let mnemonic = "test test test test test test test test test test test junk".to_string();
+        let params = HDWalletParams {
+            mnemonic: mnemonic.clone(),
+            derivation_path: "m/44'/60'/0'/0".into(),
+            password: "old".into(),
+            name: "test".into(),
+            count: 1,
+        };
+
+        let mut wallet = HDWallet::from_params(params).await.unwrap();
+
+        assert!(wallet.change_password("wrong", "new").is_err());
+        wallet.change_password("old", "new").unwrap();
+
+        assert!(ethui_crypto::decrypt(&wallet.ciphertext, "old").is_err());
+        assert_eq!(
+            ethui_crypto::decrypt(&wallet.ciphertext, "new").unwrap(),
+            mnemonic
+        );
+    }
 }
@@ -3,6 +3,10 @@ use ethui_types::prelude::*;
 
 use crate::{Signer, Wallet, WalletControl, wallet::WalletCreate};
 
+/// Watch-only addresses, with no keys behind them
+///
+/// These can't sign anything, but dev nodes can be told to accept unsigned transactions from them
+/// (see `anvil_impersonateAccount`)
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Impersonator {
     pub name: String,
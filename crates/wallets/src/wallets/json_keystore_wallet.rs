@@ -57,7 +57,7 @@ impl<'de> serde::Deserialize<'de> for JsonKeystoreWallet {
     }
 }
 
-fn legacy_file_address(file: &std::path::Path) -> Option<Address> {
+pub(super) fn legacy_file_address(file: &std::path::Path) -> Option<Address> {
     let file = std::fs::File::open(file).ok()?;
     let json: serde_json::Value = serde_json::from_reader(std::io::BufReader::new(file)).ok()?;
     Address::from_str(json["address"].as_str()?).ok()
@@ -133,6 +133,14 @@ impl WalletControl for JsonKeystoreWallet {
         signer.set_chain_id(Some(chain_id));
         Ok(Signer::Local(signer))
     }
+
+    async fn is_unlocked(&self) -> Option<bool> {
+        Some(self.cache.is_unlocked().await)
+    }
+
+    async fn lock(&self) {
+        self.cache.clear().await;
+    }
 }
 
 impl JsonKeystoreWallet {
@@ -147,7 +155,7 @@ impl JsonKeystoreWallet {
 }
 
 /// Converts a signer into a SecretVec
-fn signer_into_secret(keystore: &LocalSigner<ecdsa::SigningKey>) -> SecretVec<u8> {
+pub(super) fn signer_into_secret(keystore: &LocalSigner<ecdsa::SigningKey>) -> SecretVec<u8> {
     // TODO: test this encoding
     let signer_bytes = keystore.credential().to_bytes();
     let bytes = signer_bytes.as_slice();
@@ -158,7 +166,7 @@ fn signer_into_secret(keystore: &LocalSigner<ecdsa::SigningKey>) -> SecretVec<u8
 }
 
 /// Converts a SecretVec into a signer
-fn signer_from_secret(secret: &SecretVec<u8>) -> LocalSigner<ecdsa::SigningKey> {
+pub(super) fn signer_from_secret(secret: &SecretVec<u8>) -> LocalSigner<ecdsa::SigningKey> {
     let signer_bytes = secret.borrow();
     let key = B256::from_slice(&signer_bytes);
     LocalSigner::from_bytes(&key).unwrap()
@@ -0,0 +1,440 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+    sync::{LazyLock, Mutex},
+};
+
+use alloy::signers::{Signer as _, local::LocalSigner};
+use async_trait::async_trait;
+use ethui_types::{GlobalState, prelude::*};
+use notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_full::{DebounceEventResult, Debouncer, RecommendedCache, new_debouncer};
+
+use super::json_keystore_wallet::{legacy_file_address, signer_from_secret, signer_into_secret};
+use crate::{
+    Signer, Wallet, WalletControl, Wallets,
+    secret_cache::{SecretCache, unlock_key_with_dialog},
+    wallet::WalletCreate,
+};
+
+/// One watcher per wallet, keyed by wallet name
+static WATCHERS: LazyLock<Mutex<HashMap<String, Debouncer<RecommendedWatcher, RecommendedCache>>>> =
+    LazyLock::new(Default::default);
+
+/// A directory of encrypted V3 keystores, such as the one `cast wallet import` writes to
+///
+/// Every keystore in it is an address path, named after its file. Keystores usually don't carry
+/// their address, so those stay locked until the user unlocks them once to learn it, which is
+/// offered when the wallet is created
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeystoreDirWallet {
+    name: String,
+
+    #[serde(default = "default_dir")]
+    pub dir: PathBuf,
+
+    /// addresses learned so far, by keystore name
+    #[serde(default)]
+    addresses: BTreeMap<String, Address>,
+
+    /// keystores whose address is still unknown
+    #[serde(default)]
+    locked: BTreeSet<String>,
+
+    #[serde(default)]
+    current: Option<String>,
+
+    /// each keystore has its own password, and so its own cache
+    #[serde(skip)]
+    caches: Arc<Mutex<HashMap<String, SecretCache>>>,
+}
+
+fn default_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_default()
+        .join(".foundry")
+        .join("keystores")
+}
+
+#[derive(Debug, Deserialize)]
+struct KeystoreDirWalletParams {
+    name: String,
+    #[serde(default = "default_dir")]
+    dir: PathBuf,
+}
+
+#[async_trait]
+impl WalletCreate for KeystoreDirWallet {
+    async fn create(params: Json) -> Result<Wallet> {
+        let params: KeystoreDirWalletParams = serde_json::from_value(params)?;
+
+        if !params.dir.is_dir() {
+            return Err(eyre!("{} is not a directory", params.dir.display()));
+        }
+
+        let mut wallet = Self {
+            name: params.name,
+            dir: params.dir,
+            addresses: Default::default(),
+            locked: Default::default(),
+            current: None,
+            caches: Default::default(),
+        };
+
+        for (key, file) in wallet.keystores()? {
+            match legacy_file_address(&file) {
+                Some(address) => wallet.addresses.insert(key, address),
+                None => wallet.locked.insert(key),
+            };
+        }
+
+        wallet.watch();
+
+        Ok(Wallet::KeystoreDir(wallet))
+    }
+}
+
+#[async_trait]
+impl WalletControl for KeystoreDirWallet {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn update(mut self, params: Json) -> Result<Wallet> {
+        if let Some(name) = params["name"].as_str()
+            && name != self.name
+        {
+            unwatch(&self.name);
+            self.name = name.into();
+            self.watch();
+        }
+
+        if !params["addresses"].is_null() {
+            let found: BTreeMap<String, Address> =
+                serde_json::from_value(params["addresses"].clone())?;
+            self.addresses.extend(found);
+        }
+
+        if !params["locked"].is_null() {
+            self.locked = serde_json::from_value(params["locked"].clone())?;
+        }
+
+        // forget keystores that were removed from the directory. the directory itself may be gone
+        // (e.g. an unmounted drive), which shouldn't get in the way of other updates
+        match self.keystores() {
+            Ok(keystores) => {
+                let keys: HashSet<_> = keystores.into_keys().collect();
+                self.addresses.retain(|key, _| keys.contains(key));
+                self.locked
+                    .retain(|key| keys.contains(key) && !self.addresses.contains_key(key));
+                if self.current.as_ref().is_some_and(|c| !keys.contains(c)) {
+                    self.current = None;
+                }
+            }
+            Err(e) => warn!("failed to read {}: {e}", self.dir.display()),
+        }
+
+        Ok(Wallet::KeystoreDir(self))
+    }
+
+    async fn get_current_address(&self) -> Address {
+        self.get_address(&self.get_current_path())
+            .await
+            .unwrap_or_default()
+    }
+
+    fn get_current_path(&self) -> String {
+        self.current
+            .clone()
+            .or_else(|| self.addresses.keys().next().cloned())
+            .unwrap_or_default()
+    }
+
+    async fn set_current_path(&mut self, path: String) -> Result<()> {
+        if !self.addresses.contains_key(&path) {
+            return Err(eyre!("unknown keystore `{path}`"));
+        }
+
+        self.current = Some(path);
+        Ok(())
+    }
+
+    async fn get_all_addresses(&self) -> Vec<(String, Address)> {
+        self.addresses
+            .iter()
+            .map(|(key, address)| (key.clone(), *address))
+            .collect()
+    }
+
+    async fn get_address(&self, path: &str) -> Result<Address> {
+        self.addresses
+            .get(path)
+            .copied()
+            .with_context(|| format!("unknown keystore `{path}`"))
+    }
+
+    async fn build_signer(&self, chain_id: u64, path: &str) -> Result<Signer> {
+        let cache = self.unlock(path).await?;
+
+        let guard = cache.read().await?;
+        let secret = guard.lock().await;
+
+        let mut signer = signer_from_secret(&secret);
+        signer.set_chain_id(Some(chain_id));
+        Ok(Signer::Local(signer))
+    }
+
+    async fn is_unlocked(&self) -> Option<bool> {
+        let caches: Vec<_> = self.caches.lock().unwrap().values().cloned().collect();
+        for cache in caches {
+            if cache.is_unlocked().await {
+                return Some(true);
+            }
+        }
+
+        Some(false)
+    }
+
+    async fn lock(&self) {
+        let caches: Vec<_> = self.caches.lock().unwrap().values().cloned().collect();
+        for cache in caches {
+            cache.clear().await;
+        }
+    }
+}
+
+impl KeystoreDirWallet {
+    /// Keystore files in the directory, by name
+    fn keystores(&self) -> Result<BTreeMap<String, PathBuf>> {
+        let mut res = BTreeMap::new();
+
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            let is_hidden = path
+                .file_name()
+                .is_some_and(|n| n.to_string_lossy().starts_with('.'));
+
+            if path.is_file()
+                && !is_hidden
+                && let Some(name) = path.file_name()
+            {
+                res.insert(name.to_string_lossy().to_string(), path);
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Unlocks a single keystore, returning the cache holding its key
+    async fn unlock(&self, key: &str) -> Result<SecretCache> {
+        let cache = self
+            .caches
+            .lock()
+            .unwrap()
+            .entry(key.into())
+            .or_default()
+            .clone();
+
+        let file = self.dir.join(key);
+        unlock_key_with_dialog(&cache, &self.name, Some(key), |password| {
+            let keystore = LocalSigner::decrypt_keystore(&file, password)?;
+            Ok(signer_into_secret(&keystore))
+        })
+        .await?;
+
+        Ok(cache)
+    }
+
+    /// Starts watching the directory for keystores being added or removed
+    fn watch(&self) {
+        let name = self.name.clone();
+        let watcher = new_debouncer(
+            Duration::from_millis(500),
+            None,
+            move |result: DebounceEventResult| match result {
+                Ok(_) => {
+                    tauri::async_runtime::spawn(discover(name.clone()));
+                }
+                Err(e) => warn!("keystore watch error: {:?}", e),
+            },
+        );
+
+        match watcher {
+            Ok(mut watcher) => {
+                if let Err(e) = watcher.watch(&self.dir, RecursiveMode::NonRecursive) {
+                    warn!("failed to watch {}: {e}", self.dir.display());
+                    return;
+                }
+                WATCHERS.lock().unwrap().insert(self.name.clone(), watcher);
+            }
+            Err(e) => warn!("failed to create keystore watcher: {e}"),
+        }
+    }
+}
+
+/// Starts watching the directories of all keystore dir wallets, and picks up keystores added or
+/// removed while ethui was not running
+pub(crate) fn watch_all(wallets: &[Wallet]) {
+    for wallet in wallets {
+        if let Wallet::KeystoreDir(wallet) = wallet {
+            wallet.watch();
+            tauri::async_runtime::spawn(discover(wallet.name.clone()));
+        }
+    }
+}
+
+/// Picks up keystores that are new to the wallet, and drops the ones that were removed
+///
+/// Only keystores that carry their address are added right away. The others are left locked,
+/// rather than prompting for a password whenever one shows up
+async fn discover(name: String) {
+    let wallet = match Wallets::read().await.get(&name) {
+        Some(Wallet::KeystoreDir(wallet)) => wallet.clone(),
+        _ => return,
+    };
+
+    let keystores = match wallet.keystores() {
+        Ok(keystores) => keystores,
+        Err(e) => {
+            warn!("failed to read {}: {e}", wallet.dir.display());
+            return;
+        }
+    };
+
+    let mut found = BTreeMap::new();
+    let mut locked = BTreeSet::new();
+    for (key, file) in keystores {
+        if wallet.addresses.contains_key(&key) {
+            continue;
+        }
+
+        match legacy_file_address(&file) {
+            Some(address) => found.insert(key, address),
+            None => locked.insert(key),
+        };
+    }
+
+    if let Err(e) = Wallets::write()
+        .await
+        .update(name, json!({ "addresses": found, "locked": locked }))
+        .await
+    {
+        warn!("failed to update keystore wallet: {e}");
+    }
+}
+
+/// Stops watching the directory of a wallet that was removed
+pub(crate) fn unwatch(name: &str) {
+    WATCHERS.lock().unwrap().remove(name);
+}
+
+/// Offers to unlock, one at a time, the keystores of a new wallet whose address is still unknown
+///
+/// Stops at the first one the user declines. The rest can still be unlocked from the wallet's
+/// settings
+pub(crate) async fn offer_unlock(name: String) {
+    let locked = match Wallets::read().await.get(&name) {
+        Some(Wallet::KeystoreDir(wallet)) => wallet.locked.clone(),
+        _ => return,
+    };
+
+    for key in locked {
+        if let Err(e) = unlock_keystore(name.clone(), key.clone()).await {
+            warn!("keystore `{key}` left locked: {e}");
+            break;
+        }
+    }
+}
+
+/// Unlocks a keystore whose address is still unknown, to learn it
+///
+/// Runs without holding the wallets lock, since the unlock waits on the user
+pub(crate) async fn unlock_keystore(name: String, key: String) -> Result<Address> {
+    let wallet = match Wallets::read().await.get(&name) {
+        Some(Wallet::KeystoreDir(wallet)) => wallet.clone(),
+        _ => return Err(eyre!("invalid keystore wallet `{name}`")),
+    };
+
+    if !wallet.keystores()?.contains_key(&key) {
+        return Err(eyre!("unknown keystore `{key}`"));
+    }
+
+    let cache = wallet.unlock(&key).await?;
+    let address = signer_from_secret(&*cache.read().await?.lock().await).address();
+
+    Wallets::write()
+        .await
+        .update(name, json!({ "addresses": { key: address } }))
+        .await?;
+
+    Ok(address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_keystores_by_file_name() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("deployer"), "{}").unwrap();
+        std::fs::write(dir.path().join("ops"), "{}").unwrap();
+        std::fs::write(dir.path().join(".DS_Store"), "").unwrap();
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+
+        let wallet = KeystoreDirWallet {
+            name: "foundry".into(),
+            dir: dir.path().into(),
+            addresses: Default::default(),
+            locked: Default::default(),
+            current: None,
+            caches: Default::default(),
+        };
+
+        let keys: Vec<_> = wallet.keystores().unwrap().into_keys().collect();
+
+        assert_eq!(keys, vec!["deployer", "ops"]);
+    }
+
+    #[tokio::test]
+    async fn updates_without_the_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let wallet = KeystoreDirWallet {
+            name: "foundry".into(),
+            dir: dir.path().join("missing"),
+            addresses: BTreeMap::from([("deployer".to_string(), Address::ZERO)]),
+            locked: Default::default(),
+            current: None,
+            caches: Default::default(),
+        };
+
+        let Wallet::KeystoreDir(wallet) =
+            wallet.update(json!({ "name": "renamed" })).await.unwrap()
+        else {
+            panic!("expected a keystore dir wallet");
+        };
+
+        assert_eq!(wallet.name, "renamed");
+        assert_eq!(wallet.addresses.len(), 1);
+    }
+
+    #[test]
+    fn stops_watching_once_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let wallet = KeystoreDirWallet {
+            name: "watched".into(),
+            dir: dir.path().into(),
+            addresses: Default::default(),
+            locked: Default::default(),
+            current: None,
+            caches: Default::default(),
+        };
+
+        wallet.watch();
+        assert!(WATCHERS.lock().unwrap().contains_key("watched"));
+
+        unwatch("watched");
+        assert!(!WATCHERS.lock().unwrap().contains_key("watched"));
+    }
+}
@@ -1,13 +1,22 @@
 mod hd_wallet;
 mod impersonator;
 mod json_keystore_wallet;
+mod keystore_dir;
 mod ledger;
 mod plaintext;
 mod private_key;
+mod remote;
+mod safe;
+mod smart_account;
 
 pub use hd_wallet::HDWallet;
 pub use impersonator::Impersonator;
 pub use json_keystore_wallet::JsonKeystoreWallet;
+pub use keystore_dir::KeystoreDirWallet;
+pub(crate) use keystore_dir::{offer_unlock, unlock_keystore, unwatch, watch_all};
 pub use ledger::LedgerWallet;
 pub use plaintext::PlaintextWallet;
 pub use private_key::PrivateKeyWallet;
+pub use remote::RemoteWallet;
+pub use safe::SafeWallet;
+pub use smart_account::SmartAccountWallet;
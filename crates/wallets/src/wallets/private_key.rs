@@ -5,6 +5,7 @@ use async_trait::async_trait;
 use ethui_crypto::{self, EncryptedData};
 use ethui_types::prelude::*;
 use secrets::SecretVec;
+use zeroize::Zeroizing;
 
 use crate::{
     Signer, Wallet, WalletControl,
@@ -77,6 +78,14 @@ impl WalletControl for PrivateKeyWallet {
         signer.set_chain_id(chain_id.into());
         Ok(Signer::Local(signer))
     }
+
+    async fn is_unlocked(&self) -> Option<bool> {
+        Some(self.cache.is_unlocked().await)
+    }
+
+    async fn lock(&self) {
+        self.cache.clear().await;
+    }
 }
 
 impl PrivateKeyWallet {
@@ -101,6 +110,14 @@ impl PrivateKeyWallet {
         })
     }
 
+    /// Re-encrypts the private key under a new password
+    pub(crate) fn change_password(&mut self, old: &str, new: &str) -> Result<()> {
+        let key = Zeroizing::new(ethui_crypto::decrypt(&self.ciphertext, old)?);
+        self.ciphertext = ethui_crypto::encrypt(&*key, new)?;
+
+        Ok(())
+    }
+
     async fn unlock(&self) -> Result<()> {
         let ciphertext = self.ciphertext.clone();
         unlock_with_dialog(&self.cache, &self.name, |password| {
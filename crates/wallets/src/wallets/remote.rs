@@ -0,0 +1,134 @@
+use alloy::signers::Signer as _;
+use async_trait::async_trait;
+use ethui_types::prelude::*;
+
+use crate::{
+    Signer, Wallet, WalletControl,
+    remote_signer::{RemoteApi, RemoteAuth, RemoteEndpoint},
+    wallet::WalletCreate,
+};
+
+/// Keys held by a remote signing service, such as Web3Signer
+///
+/// Every key the service exposes becomes an address of the wallet, with the service's identifier
+/// for it as its path
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteWallet {
+    name: String,
+
+    #[serde(flatten)]
+    endpoint: RemoteEndpoint,
+
+    keys: Vec<(String, Address)>,
+
+    #[serde(default)]
+    current: usize,
+}
+
+#[async_trait]
+impl WalletCreate for RemoteWallet {
+    async fn create(mut params: Json) -> color_eyre::Result<Wallet> {
+        params["keys"] = json!([]);
+        let mut wallet: Self = serde_json::from_value(params)?;
+        wallet.refresh_keys().await?;
+
+        Ok(Wallet::Remote(wallet))
+    }
+}
+
+#[async_trait]
+impl WalletControl for RemoteWallet {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn update(mut self, params: Json) -> color_eyre::Result<Wallet> {
+        if let Some(name) = params["name"].as_str() {
+            self.name = name.into();
+        }
+
+        if !params["url"].is_null() {
+            self.endpoint.url = serde_json::from_value(params["url"].clone())?;
+        }
+
+        if !params["api"].is_null() {
+            self.endpoint.api = serde_json::from_value::<RemoteApi>(params["api"].clone())?;
+        }
+
+        if !params["auth"].is_null() {
+            self.endpoint.auth = serde_json::from_value::<RemoteAuth>(params["auth"].clone())?;
+        }
+
+        if params.get("ca").is_some() {
+            self.endpoint.ca = serde_json::from_value(params["ca"].clone())?;
+        }
+
+        self.refresh_keys().await?;
+
+        Ok(Wallet::Remote(self))
+    }
+
+    async fn get_current_address(&self) -> Address {
+        self.keys
+            .get(self.current)
+            .map(|(_, address)| *address)
+            .unwrap_or_default()
+    }
+
+    fn get_current_path(&self) -> String {
+        self.keys
+            .get(self.current)
+            .map(|(key, _)| key.clone())
+            .unwrap_or_default()
+    }
+
+    async fn set_current_path(&mut self, path: String) -> color_eyre::Result<()> {
+        self.current = self
+            .keys
+            .iter()
+            .position(|(p, _)| p == &path)
+            .with_context(|| format!("unknown wallet key: {path}"))?;
+
+        Ok(())
+    }
+
+    async fn get_all_addresses(&self) -> Vec<(String, Address)> {
+        self.keys.clone()
+    }
+
+    async fn get_address(&self, path: &str) -> color_eyre::Result<Address> {
+        self.keys
+            .iter()
+            .find(|(p, _)| p == path)
+            .map(|(_, a)| *a)
+            .with_context(|| format!("unknown wallet key: {path}"))
+    }
+
+    async fn build_signer(&self, chain_id: u64, path: &str) -> color_eyre::Result<Signer> {
+        let address = self.get_address(path).await?;
+
+        let mut signer = self.endpoint.signer(path, address)?;
+        signer.set_chain_id(Some(chain_id));
+
+        Ok(Signer::Remote(signer))
+    }
+}
+
+impl RemoteWallet {
+    /// Fetches the list of keys from the signer, keeping the current one selected if it's still
+    /// there
+    async fn refresh_keys(&mut self) -> color_eyre::Result<()> {
+        let current = self.get_current_path();
+        let keys = self.endpoint.list_keys().await?;
+
+        if keys.is_empty() {
+            return Err(eyre!("{} holds no keys", self.endpoint.url));
+        }
+
+        self.current = keys.iter().position(|(k, _)| k == &current).unwrap_or(0);
+        self.keys = keys;
+
+        Ok(())
+    }
+}
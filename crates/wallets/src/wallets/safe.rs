@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use ethui_types::prelude::*;
+
+use crate::{Signer, Wallet, WalletControl, wallet::WalletCreate};
+
+/// A Safe multisig
+///
+/// It holds no keys of its own. Transactions are proposed as SafeTxs, signed by whichever of its
+/// owners are also ethui wallets, and executed once the threshold is met
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SafeWallet {
+    pub name: String,
+    pub address: Address,
+}
+
+#[async_trait]
+impl WalletCreate for SafeWallet {
+    async fn create(params: serde_json::Value) -> color_eyre::Result<Wallet> {
+        Ok(Wallet::Safe(serde_json::from_value(params)?))
+    }
+}
+
+#[async_trait]
+impl WalletControl for SafeWallet {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn update(mut self, params: serde_json::Value) -> color_eyre::Result<Wallet> {
+        if let Some(name) = params["name"].as_str() {
+            self.name = name.into();
+        }
+
+        if !params["address"].is_null() {
+            self.address = serde_json::from_value(params["address"].clone())?;
+        }
+
+        Ok(Wallet::Safe(self))
+    }
+
+    async fn get_current_address(&self) -> Address {
+        self.address
+    }
+
+    fn get_current_path(&self) -> String {
+        "0".into()
+    }
+
+    async fn set_current_path(&mut self, _path: String) -> color_eyre::Result<()> {
+        Ok(())
+    }
+
+    async fn get_all_addresses(&self) -> Vec<(String, Address)> {
+        vec![("0".into(), self.address)]
+    }
+
+    async fn get_address(&self, _path: &str) -> color_eyre::Result<Address> {
+        Ok(self.address)
+    }
+
+    async fn build_signer(&self, _chain_id: u64, _path: &str) -> color_eyre::Result<Signer> {
+        Err(eyre!("Safe wallets can only sign through their owners"))
+    }
+}
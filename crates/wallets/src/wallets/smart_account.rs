@@ -0,0 +1,147 @@
+use alloy::primitives::address;
+use async_trait::async_trait;
+use ethui_abis::ISimpleAccountFactory;
+use ethui_networks::{NetworksActorExt as _, networks};
+use ethui_types::prelude::*;
+use url::Url;
+
+use crate::{Signer, Wallet, WalletControl, wallet::WalletCreate};
+
+/// ERC-4337 v0.7 EntryPoint, deployed at the same address on every chain
+const ENTRY_POINT: Address = address!("0x0000000071727De22E5E9d8BAf0edAc6f37da032");
+
+/// The reference `SimpleAccountFactory` for the v0.7 EntryPoint
+const SIMPLE_ACCOUNT_FACTORY: Address = address!("0x91E60e0613810449d098b0b5Ec8b51A0FE8c8985");
+
+/// An ERC-4337 smart account, owned by an account from one of the other wallets
+///
+/// Transactions are sent as user operations, signed by the owner and submitted to a bundler
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SmartAccountWallet {
+    pub name: String,
+
+    /// ethui account that owns, and signs for, the smart account
+    pub owner: Address,
+
+    /// counterfactual address of the account. it may not be deployed yet
+    #[serde(default)]
+    pub address: Address,
+
+    #[serde(default = "default_factory")]
+    pub factory: Address,
+
+    #[serde(default = "default_entry_point")]
+    pub entry_point: Address,
+
+    #[serde(default)]
+    pub salt: U256,
+
+    /// ERC-4337 bundler endpoint, serving `eth_sendUserOperation` & co.
+    pub bundler_url: Url,
+
+    #[serde(default)]
+    pub paymaster: Option<Address>,
+
+    #[serde(default)]
+    pub paymaster_data: Bytes,
+}
+
+#[async_trait]
+impl WalletCreate for SmartAccountWallet {
+    async fn create(params: serde_json::Value) -> color_eyre::Result<Wallet> {
+        let mut wallet: Self = serde_json::from_value(params)?;
+        wallet.address = wallet.counterfactual_address().await?;
+
+        Ok(Wallet::SmartAccount(wallet))
+    }
+}
+
+#[async_trait]
+impl WalletControl for SmartAccountWallet {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn update(mut self, params: serde_json::Value) -> color_eyre::Result<Wallet> {
+        if let Some(name) = params["name"].as_str() {
+            self.name = name.into();
+        }
+
+        if !params["bundlerUrl"].is_null() {
+            self.bundler_url = serde_json::from_value(params["bundlerUrl"].clone())?;
+        }
+
+        if !params["paymaster"].is_null() {
+            self.paymaster = serde_json::from_value(params["paymaster"].clone())?;
+        }
+
+        if !params["paymasterData"].is_null() {
+            self.paymaster_data = serde_json::from_value(params["paymasterData"].clone())?;
+        }
+
+        // any of these yields a different account
+        let mut moved = false;
+        if !params["owner"].is_null() {
+            self.owner = serde_json::from_value(params["owner"].clone())?;
+            moved = true;
+        }
+        if !params["factory"].is_null() {
+            self.factory = serde_json::from_value(params["factory"].clone())?;
+            moved = true;
+        }
+        if !params["salt"].is_null() {
+            self.salt = serde_json::from_value(params["salt"].clone())?;
+            moved = true;
+        }
+        if moved {
+            self.address = self.counterfactual_address().await?;
+        }
+
+        Ok(Wallet::SmartAccount(self))
+    }
+
+    async fn get_current_address(&self) -> Address {
+        self.address
+    }
+
+    fn get_current_path(&self) -> String {
+        "0".into()
+    }
+
+    async fn set_current_path(&mut self, _path: String) -> color_eyre::Result<()> {
+        Ok(())
+    }
+
+    async fn get_all_addresses(&self) -> Vec<(String, Address)> {
+        vec![("0".into(), self.address)]
+    }
+
+    async fn get_address(&self, _path: &str) -> color_eyre::Result<Address> {
+        Ok(self.address)
+    }
+
+    async fn build_signer(&self, _chain_id: u64, _path: &str) -> color_eyre::Result<Signer> {
+        Err(eyre!("Smart accounts can only sign through their owner"))
+    }
+}
+
+impl SmartAccountWallet {
+    /// Asks the factory where the account is (or will be) deployed
+    async fn counterfactual_address(&self) -> color_eyre::Result<Address> {
+        let provider = networks().get_current().await?.get_alloy_provider().await?;
+
+        Ok(ISimpleAccountFactory::new(self.factory, &provider)
+            .getAddress(self.owner, self.salt)
+            .call()
+            .await?)
+    }
+}
+
+fn default_factory() -> Address {
+    SIMPLE_ACCOUNT_FACTORY
+}
+
+fn default_entry_point() -> Address {
+    ENTRY_POINT
+}
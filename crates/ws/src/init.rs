@@ -41,9 +41,7 @@ async fn receiver() -> ! {
                         .broadcast_chain_changed(internal_id, domain, affinity)
                         .await
                 }
-                AccountsChanged(accounts) => {
-                    Peers::read().await.broadcast_accounts_changed(accounts)
-                }
+                AccountsChanged(_) => Peers::write().await.broadcast_accounts_changed().await,
                 _ => {}
             }
         }
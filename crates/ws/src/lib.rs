@@ -3,6 +3,7 @@ mod error;
 mod init;
 pub mod peers;
 mod server;
+mod subscriptions;
 
 pub use error::{WsError, WsResult};
 pub use init::init;
@@ -1,10 +1,13 @@
 use std::net::SocketAddr;
 
+use ethui_connections::Ctx;
 use ethui_networks::{NetworksActorExt as _, networks};
 use ethui_types::{Affinity, NetworkId, prelude::*};
 use serde_json::json;
 use tokio::sync::mpsc;
 
+use crate::subscriptions::Subscriptions;
+
 #[derive(Clone, Debug, Serialize)]
 pub struct Peer {
     pub origin: String,
@@ -12,6 +15,13 @@ pub struct Peer {
     pub socket: SocketAddr,
     #[serde(skip)]
     pub sender: mpsc::UnboundedSender<serde_json::Value>,
+    #[serde(skip)]
+    pub(crate) subscriptions: Subscriptions,
+    // accounts last announced to this peer
+    #[serde(skip)]
+    accounts: Vec<Address>,
+    #[serde(skip)]
+    ctx: Ctx,
 
     // non-alive peers can represent browser tabs with now web3 connection
     pub alive: bool,
@@ -30,13 +40,18 @@ impl Peer {
 
         let url = params.get("url").cloned();
 
-        Self {
+        let mut peer = Self {
             alive: false,
             socket,
             sender,
+            subscriptions: Default::default(),
+            accounts: Default::default(),
+            ctx: Default::default(),
             origin,
             url,
-        }
+        };
+        peer.ctx = Ctx::new(peer.domain());
+        peer
     }
 
     /// Extracts the domain from the url
@@ -51,7 +66,7 @@ impl Peer {
 
 impl From<Peer> for ethui_rpc::Handler {
     fn from(value: Peer) -> Self {
-        Self::new(value.domain())
+        Self::with_ctx(value.ctx)
     }
 }
 
@@ -64,7 +79,8 @@ pub struct Peers {
 
 impl Peers {
     /// Adds a new peer
-    pub async fn add_peer(&mut self, peer: Peer) {
+    pub async fn add_peer(&mut self, mut peer: Peer) {
+        peer.accounts = ethui_rpc::accounts_for(&peer.ctx).await;
         self.map.insert(peer.socket, peer);
         ethui_broadcast::peer_added().await;
         ethui_broadcast::ui_notify(UINotify::PeersUpdated).await;
@@ -81,12 +97,27 @@ impl Peers {
         ethui_broadcast::ui_notify(UINotify::PeersUpdated).await;
     }
 
-    /// Broadcasts an `accountsChanged` event to all peers
-    pub fn broadcast_accounts_changed(&self, new_accounts: Vec<Address>) {
-        self.broadcast(json!({
-            "method": "accountsChanged",
-            "params": new_accounts
-        }));
+    /// Sends an `accountsChanged` event to each peer whose exposed accounts changed
+    ///
+    /// Each domain may see a different set of accounts, so they're recomputed per peer
+    pub async fn broadcast_accounts_changed(&mut self) {
+        for peer in self.map.values_mut() {
+            let accounts = ethui_rpc::accounts_for(&peer.ctx).await;
+
+            if accounts == peer.accounts {
+                continue;
+            }
+
+            peer.sender
+                .send(json!({
+                    "method": "accountsChanged",
+                    "params": accounts,
+                }))
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to send message to peer: {}", e);
+                });
+            peer.accounts = accounts;
+        }
     }
 
     /// Broadcasts a `chainChanged` event to all peers
@@ -115,6 +146,7 @@ impl Peers {
                 if ethui_connections::utils::affinity_matches(peer.domain(), &domain, affinity)
                     .await
                 {
+                    peer.subscriptions.clear();
                     tracing::info!(
                         event = "peer chain changed",
                         domain = peer.domain(),
@@ -131,16 +163,6 @@ impl Peers {
         }
     }
 
-    fn broadcast<T: Serialize + std::fmt::Debug>(&self, msg: T) {
-        self.map.iter().for_each(|(_, peer)| {
-            peer.sender
-                .send(serde_json::to_value(&msg).unwrap())
-                .unwrap_or_else(|e| {
-                    tracing::warn!("Failed to send message to peer: {}", e);
-                });
-        });
-    }
-
     pub(crate) fn by_domain(&self) -> HashMap<String, Vec<Peer>> {
         self.map.values().fold(Default::default(), |mut acc, p| {
             if !p.alive {
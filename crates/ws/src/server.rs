@@ -54,7 +54,8 @@ async fn accept(socket: SocketAddr, stream: TcpStream) {
     let peer = Peer::new(socket, snd, &query_params);
 
     Peers::write().await.add_peer(peer.clone()).await;
-    let res = handle(peer, ws_stream, rcv).await;
+    let res = handle(peer.clone(), ws_stream, rcv).await;
+    peer.subscriptions.clear();
     Peers::write().await.remove_peer(socket).await;
 
     if let Err(e) = res {
@@ -84,7 +85,7 @@ async fn handle(
     let handler: ethui_rpc::Handler = peer.clone().into();
 
     // will be used at most once to mark the peer as live once the first message comes in
-    let mut liveness_checker = Some(peer);
+    let mut liveness_checker = Some(peer.clone());
     let mut interval = tokio::time::interval(std::time::Duration::from_secs(15));
     let (mut ws_sender, mut ws_receiver) = stream.split();
 
@@ -94,7 +95,7 @@ async fn handle(
             Some(msg) = ws_receiver.next() => {
 
                 match msg {
-                    Ok(Message::Text(msg)) => handle_message(msg.to_string(), &handler, &mut ws_sender, &mut liveness_checker).await?,
+                    Ok(Message::Text(msg)) => handle_message(msg.to_string(), &peer, &handler, &mut ws_sender, &mut liveness_checker).await?,
                     Ok(Message::Close(_)) => break,
                     Ok(_) => continue,
                     Err(e) => warn!("websocket error: {}", e),
@@ -127,6 +128,7 @@ async fn handle(
 
 async fn handle_message(
     text: String,
+    peer: &Peer,
     handler: &ethui_rpc::Handler,
     sender: &mut SplitSink<WebSocketStream<TcpStream>, Message>,
     liveness_checker: &mut Option<Peer>,
@@ -140,8 +142,7 @@ async fn handle_message(
     }
 
     let reply = match serde_json::from_str(&text) {
-        Ok(request) => handler
-            .handle(request)
+        Ok(request) => handle_request(request, peer, handler)
             .await
             .map(|r| {
                 serde_json::to_value(&r).unwrap_or_else(|_e| {
@@ -160,6 +161,41 @@ async fn handle_message(
     Ok(())
 }
 
+/// Subscriptions are tied to the peer's socket, so they're handled here rather than by the RPC
+/// handler. Everything else, including batches, is forwarded to it
+///
+/// `eth_subscribe` is only supported as a standalone request, not as part of a batch
+async fn handle_request(
+    request: jsonrpc_core::Request,
+    peer: &Peer,
+    handler: &ethui_rpc::Handler,
+) -> Option<jsonrpc_core::Response> {
+    use jsonrpc_core::{Call, Output, Request, Response};
+
+    let call = match request {
+        Request::Single(Call::MethodCall(call))
+            if call.method == "eth_subscribe" || call.method == "eth_unsubscribe" =>
+        {
+            call
+        }
+        request => return handler.handle(request).await,
+    };
+
+    let result = if call.method == "eth_subscribe" {
+        peer.subscriptions
+            .subscribe(peer.domain(), peer.sender.clone(), call.params)
+            .await
+    } else {
+        peer.subscriptions.unsubscribe(call.params)
+    };
+
+    Some(Response::Single(Output::from(
+        result,
+        call.id,
+        call.jsonrpc,
+    )))
+}
+
 fn json_rpc_error(code: i64, message: String, id: serde_json::Value) -> serde_json::Value {
     serde_json::json!({
         "jsonrpc": "2.0",
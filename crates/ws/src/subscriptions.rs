@@ -0,0 +1,292 @@
+use std::sync::Mutex;
+
+use alloy::{
+    providers::{Provider as _, ProviderBuilder, WsConnect},
+    rpc::types::Filter,
+};
+use ethui_connections::Ctx;
+use ethui_types::prelude::*;
+use futures::{StreamExt as _, stream::BoxStream};
+use jsonrpc_core::{Error as RpcError, Params};
+use tokio::{sync::mpsc, task::JoinHandle};
+use url::Url;
+
+/// Subscriptions (`eth_subscribe`) opened by a single peer
+///
+/// Each subscription is served by its own task, which forwards notifications to the peer.
+/// Networks with a `ws_url` get a real upstream subscription. Others are emulated by polling over
+/// `http_url`
+#[derive(Debug, Clone, Default)]
+pub struct Subscriptions(Arc<Mutex<HashMap<String, JoinHandle<()>>>>);
+
+impl Subscriptions {
+    /// Handles `eth_subscribe`, returning the new subscription id
+    pub async fn subscribe(
+        &self,
+        domain: Option<String>,
+        sender: mpsc::UnboundedSender<Json>,
+        params: Params,
+    ) -> std::result::Result<Json, RpcError> {
+        let params: Vec<Json> = params.parse()?;
+        let kind = params
+            .first()
+            .and_then(|k| k.as_str())
+            .ok_or_else(|| RpcError::invalid_params("missing subscription type"))?
+            .to_owned();
+
+        let network = Ctx::new(domain).network().await;
+
+        let stream = match network.ws_url {
+            Some(ref ws_url) => upstream(ws_url, params).await,
+            None => poll(&network, &kind, params.get(1).cloned()).await,
+        }?;
+
+        let id = format!("0x{}", alloy::hex::encode(rand::random::<[u8; 16]>()));
+        let handle = tokio::spawn(forward(id.clone(), stream, sender));
+        self.0.lock().unwrap().insert(id.clone(), handle);
+
+        Ok(id.into())
+    }
+
+    /// Handles `eth_unsubscribe`
+    pub fn unsubscribe(&self, params: Params) -> std::result::Result<Json, RpcError> {
+        let (id,): (String,) = params.parse()?;
+
+        let handle = self.0.lock().unwrap().remove(&id);
+        if let Some(ref handle) = handle {
+            handle.abort();
+        }
+
+        Ok(handle.is_some().into())
+    }
+
+    /// Tears down all subscriptions
+    ///
+    /// Called when the peer disconnects, or when its chain changes, since subscriptions are bound
+    /// to the network they were opened on
+    pub fn clear(&self) {
+        for (_, handle) in self.0.lock().unwrap().drain() {
+            handle.abort();
+        }
+    }
+}
+
+async fn forward(
+    id: String,
+    mut stream: BoxStream<'static, Json>,
+    sender: mpsc::UnboundedSender<Json>,
+) {
+    while let Some(result) = stream.next().await {
+        let msg = json!({
+            "jsonrpc": "2.0",
+            "method": "eth_subscription",
+            "params": {
+                "subscription": id,
+                "result": result,
+            }
+        });
+
+        // peer is gone
+        if sender.send(msg).is_err() {
+            break;
+        }
+    }
+}
+
+/// Opens the subscription on the network's own websocket endpoint, forwarding params as-is
+async fn upstream(
+    ws_url: &Url,
+    params: Vec<Json>,
+) -> std::result::Result<BoxStream<'static, Json>, RpcError> {
+    let provider = ProviderBuilder::new()
+        .disable_recommended_fillers()
+        .connect_ws(WsConnect::new(ws_url.as_str()))
+        .await
+        .map_err(internal_error)?;
+
+    let sub = provider
+        .subscribe::<_, Json>(params)
+        .await
+        .map_err(internal_error)?;
+
+    Ok(keep_alive(sub.into_stream(), provider))
+}
+
+/// Emulates a subscription using the polling filters (`eth_newFilter` & co.) of the http endpoint
+async fn poll(
+    network: &Network,
+    kind: &str,
+    filter: Option<Json>,
+) -> std::result::Result<BoxStream<'static, Json>, RpcError> {
+    let provider = network.get_provider();
+
+    let stream = match kind {
+        "newHeads" => {
+            let hashes = provider.watch_blocks().await.map_err(internal_error)?;
+            let headers_provider = provider.clone();
+
+            hashes
+                .into_stream()
+                .flat_map(futures::stream::iter)
+                .filter_map(move |hash| {
+                    let provider = headers_provider.clone();
+                    async move {
+                        let block = provider.get_block_by_hash(hash).await.ok().flatten()?;
+                        serde_json::to_value(block.header).ok()
+                    }
+                })
+                .boxed()
+        }
+
+        "logs" => {
+            let filter: Filter = filter
+                .map(serde_json::from_value)
+                .transpose()
+                .map_err(|e| RpcError::invalid_params(e.to_string()))?
+                .unwrap_or_default();
+
+            provider
+                .watch_logs(&filter)
+                .await
+                .map_err(internal_error)?
+                .into_stream()
+                .flat_map(futures::stream::iter)
+                .filter_map(|log| async move { serde_json::to_value(log).ok() })
+                .boxed()
+        }
+
+        "newPendingTransactions" => provider
+            .watch_pending_transactions()
+            .await
+            .map_err(internal_error)?
+            .into_stream()
+            .flat_map(futures::stream::iter)
+            .map(|hash| json!(hash))
+            .boxed(),
+
+        _ => {
+            return Err(RpcError::invalid_params(format!(
+                "unsupported subscription type: {kind}"
+            )));
+        }
+    };
+
+    Ok(keep_alive(stream, provider))
+}
+
+/// Pollers and subscriptions only hold a weak reference to their provider, so it needs to live for
+/// as long as the stream does
+fn keep_alive<T: Send + 'static>(
+    stream: impl futures::Stream<Item = Json> + Send + 'static,
+    provider: T,
+) -> BoxStream<'static, Json> {
+    stream
+        .map(move |item| {
+            let _provider = &provider;
+            item
+        })
+        .boxed()
+}
+
+fn internal_error(e: impl std::fmt::Display) -> RpcError {
+    RpcError {
+        code: jsonrpc_core::ErrorCode::InternalError,
+        message: e.to_string(),
+        data: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::oneshot;
+
+    use super::*;
+
+    /// A subscription task that never ends on its own. The receiver closes once it's aborted
+    fn pending() -> (JoinHandle<()>, oneshot::Receiver<()>) {
+        let (tx, rx) = oneshot::channel();
+        let handle = tokio::spawn(async move {
+            let _tx = tx;
+            futures::future::pending::<()>().await
+        });
+
+        (handle, rx)
+    }
+
+    #[tokio::test]
+    async fn forwards_notifications_tagged_with_the_subscription() {
+        let (sender, mut rx) = mpsc::unbounded_channel();
+        let stream = futures::stream::iter([json!(1), json!(2)]).boxed();
+
+        forward("0x1".into(), stream, sender).await;
+
+        for result in [1, 2] {
+            assert_eq!(
+                rx.recv().await,
+                Some(json!({
+                    "jsonrpc": "2.0",
+                    "method": "eth_subscription",
+                    "params": { "subscription": "0x1", "result": result },
+                }))
+            );
+        }
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn stops_forwarding_once_the_peer_is_gone() {
+        let (sender, rx) = mpsc::unbounded_channel();
+        drop(rx);
+
+        // would never finish if it kept waiting on the stream
+        let stream = futures::stream::iter([json!(1)])
+            .chain(futures::stream::pending())
+            .boxed();
+
+        forward("0x1".into(), stream, sender).await;
+    }
+
+    #[tokio::test]
+    async fn unsubscribes_known_ids_only() {
+        let subscriptions = Subscriptions::default();
+        let (handle, aborted) = pending();
+        subscriptions.0.lock().unwrap().insert("0x1".into(), handle);
+
+        let res = subscriptions.unsubscribe(Params::Array(vec![json!("0x1")]));
+        assert_eq!(res.unwrap(), json!(true));
+        assert!(aborted.await.is_err());
+
+        let res = subscriptions.unsubscribe(Params::Array(vec![json!("0x1")]));
+        assert_eq!(res.unwrap(), json!(false));
+    }
+
+    #[tokio::test]
+    async fn clear_aborts_every_subscription() {
+        let subscriptions = Subscriptions::default();
+        let mut aborted = vec![];
+        for id in ["0x1", "0x2"] {
+            let (handle, rx) = pending();
+            subscriptions.0.lock().unwrap().insert(id.into(), handle);
+            aborted.push(rx);
+        }
+
+        subscriptions.clear();
+
+        assert!(subscriptions.0.lock().unwrap().is_empty());
+        for rx in aborted {
+            assert!(rx.await.is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_subscriptions_without_a_type() {
+        let (sender, _rx) = mpsc::unbounded_channel();
+
+        let err = Subscriptions::default()
+            .subscribe(None, sender, Params::Array(vec![]))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, jsonrpc_core::ErrorCode::InvalidParams);
+    }
+}
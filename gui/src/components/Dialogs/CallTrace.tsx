@@ -0,0 +1,120 @@
+import { cn } from "@ethui/ui/lib/utils";
+import { useState } from "react";
+import { type Address, formatEther, type Hex } from "viem";
+import { AddressView } from "#/components/AddressView";
+
+export interface CallFrame {
+  kind: "call" | "staticCall" | "delegateCall" | "callCode" | "create";
+  from: Address;
+  to: Address;
+  value: Hex;
+  input: Hex;
+  output: Hex;
+  gasLimit: number;
+  gasUsed: number;
+  success: boolean;
+  revertReason?: string;
+  contract?: string;
+  function?: {
+    signature: string;
+    inputs: DecodedParam[];
+    outputs: DecodedParam[];
+  };
+  logs: {
+    log: { address: Address; topics: Hex[]; data: Hex };
+    event?: { signature: string; params: DecodedParam[] };
+  }[];
+  calls: CallFrame[];
+}
+
+interface DecodedParam {
+  name: string;
+  type: string;
+  value: string;
+}
+
+export function CallTrace({ trace }: { trace: CallFrame }) {
+  return (
+    <div className="overflow-x-auto font-mono text-xs">
+      <Frame frame={trace} depth={0} />
+    </div>
+  );
+}
+
+function Frame({ frame, depth }: { frame: CallFrame; depth: number }) {
+  // reverted branches are what's usually worth looking at, so those start expanded
+  const [open, setOpen] = useState(depth < 2 || !frame.success);
+  const value = BigInt(frame.value);
+
+  return (
+    <div className={cn(depth > 0 && "ml-4 border-l pl-2")}>
+      <button
+        type="button"
+        className="flex flex-wrap items-center gap-1 text-left"
+        onClick={() => setOpen(!open)}
+      >
+        <span className="text-muted-foreground">[{frame.gasUsed}]</span>
+        {frame.contract ? (
+          <span className="font-bold">{frame.contract}</span>
+        ) : (
+          <AddressView address={frame.to} />
+        )}
+        <span>::{callName(frame)}</span>
+        {value > 0n && <span>{`{value: ${formatEther(value)}}`}</span>}
+        {frame.kind !== "call" && (
+          <span className="text-muted-foreground">[{frame.kind}]</span>
+        )}
+      </button>
+
+      {open && (
+        <>
+          {frame.calls.map((call, i) => (
+            <Frame key={i} frame={call} depth={depth + 1} />
+          ))}
+
+          {frame.logs.map(({ log, event }, i) => (
+            <div key={i} className="ml-4 pl-2 text-muted-foreground">
+              emit{" "}
+              {event
+                ? `${event.signature.split("(")[0]}(${formatParams(event.params)})`
+                : `${log.topics[0] ?? "anonymous"} ${log.data}`}
+            </div>
+          ))}
+
+          <div
+            className={cn(
+              "ml-4 pl-2",
+              frame.success ? "text-success" : "text-destructive",
+            )}
+          >
+            ← {returnValue(frame)}
+          </div>
+        </>
+      )}
+    </div>
+  );
+}
+
+function callName(frame: CallFrame) {
+  if (frame.kind === "create") return "new";
+  if (!frame.function) return frame.input.slice(0, 10);
+
+  const name = frame.function.signature.split("(")[0];
+  return `${name}(${formatParams(frame.function.inputs)})`;
+}
+
+function returnValue(frame: CallFrame) {
+  if (!frame.success) {
+    return frame.revertReason ? `[Revert] ${frame.revertReason}` : "[Revert]";
+  }
+
+  if (frame.kind === "create") return `${(frame.output.length - 2) / 2} bytes`;
+  if (frame.function) return formatParams(frame.function.outputs) || "[Stop]";
+  return frame.output === "0x" ? "[Stop]" : frame.output;
+}
+
+function formatParams(params: DecodedParam[]) {
+  return params
+    .map(({ name, value }) => (name ? `${name}: ${value}` : value))
+    .join(", ");
+}
@@ -0,0 +1,124 @@
+import { type Address, formatEther, formatUnits, type Hex } from "viem";
+import { AddressView } from "#/components/AddressView";
+
+export interface StateDiff {
+  balances: { address: Address; before: Hex; after: Hex }[];
+  tokens: TokenChange[];
+  storage: { address: Address; slot: Hex; before: Hex; after: Hex }[];
+  deployed: { address: Address; code: Hex }[];
+}
+
+interface TokenChange {
+  token: Address;
+  standard: "erc20" | "erc721" | "erc1155";
+  owner: Address;
+  tokenId?: Hex;
+  delta: string;
+  symbol?: string;
+  decimals?: number;
+}
+
+export function StateDiffView({ diff }: { diff: StateDiff }) {
+  const { balances, tokens, storage, deployed } = diff;
+
+  return (
+    <div className="flex flex-col gap-3 text-sm">
+      {balances.length > 0 && (
+        <Section title="Balance changes">
+          {balances.map(({ address, before, after }) => (
+            <div key={address} className="flex items-center gap-2">
+              <AddressView address={address} />
+              <Delta
+                value={BigInt(after) - BigInt(before)}
+                format={formatEther}
+              />
+              <span>ETH</span>
+            </div>
+          ))}
+        </Section>
+      )}
+
+      {tokens.length > 0 && (
+        <Section title="Token changes">
+          {tokens.map((t, i) => (
+            <div key={i} className="flex items-center gap-2">
+              <AddressView address={t.owner} />
+              <Delta
+                value={BigInt(t.delta)}
+                format={(v) =>
+                  t.standard === "erc20" && t.decimals != null
+                    ? formatUnits(v, t.decimals)
+                    : v.toString()
+                }
+              />
+              {t.symbol ?? <AddressView address={t.token} />}
+              {t.tokenId && <span>#{BigInt(t.tokenId).toString()}</span>}
+            </div>
+          ))}
+        </Section>
+      )}
+
+      {deployed.length > 0 && (
+        <Section title="Deployed contracts">
+          {deployed.map(({ address, code }) => (
+            <div key={address} className="flex items-center gap-2">
+              <AddressView address={address} />
+              <span>{(code.length - 2) / 2} bytes</span>
+            </div>
+          ))}
+        </Section>
+      )}
+
+      {storage.length > 0 && (
+        <Section title="Storage changes">
+          {storage.map(({ address, slot, before, after }) => (
+            <div key={`${address}-${slot}`} className="flex flex-col">
+              <div className="flex items-center gap-2">
+                <AddressView address={address} />
+                <span className="font-mono">{slot}</span>
+              </div>
+              <span className="break-all font-mono text-muted-foreground">
+                {before} → {after}
+              </span>
+            </div>
+          ))}
+        </Section>
+      )}
+    </div>
+  );
+}
+
+function Section({
+  title,
+  children,
+}: {
+  title: string;
+  children: React.ReactNode;
+}) {
+  return (
+    <div className="flex flex-col gap-1">
+      <h3 className="font-bold">{title}</h3>
+      {children}
+    </div>
+  );
+}
+
+function Delta({
+  value,
+  format,
+}: {
+  value: bigint;
+  format: (v: bigint) => string;
+}) {
+  const positive = value >= 0n;
+  const abs = positive ? value : -value;
+
+  return (
+    <span
+      className={positive ? "font-mono text-success" : "font-mono text-destructive"}
+    >
+      {positive ? "+" : "-"}
+      {format(abs)}
+    </span>
+  );
+}
@@ -0,0 +1,153 @@
+import {
+  Alert,
+  AlertDescription,
+  AlertTitle,
+} from "@ethui/ui/components/shadcn/alert";
+import { type Address, formatUnits } from "viem";
+import { AddressView } from "#/components/AddressView";
+
+export interface TypedDataSummary {
+  kind:
+    | "permit"
+    | "permit2Single"
+    | "permit2Batch"
+    | "permit2TransferFrom"
+    | "seaportOrder"
+    | "safeTx";
+  spender?: Address;
+  tokens: TokenAmount[];
+  receives: TokenAmount[];
+  deadline?: number;
+  warnings: Warning[];
+}
+
+interface TokenAmount {
+  token: Address;
+  symbol?: string;
+  decimals?: number;
+  amount: string;
+  tokenId?: string;
+  unlimited: boolean;
+}
+
+type Warning =
+  | { type: "unlimitedAmount"; token: Address }
+  | { type: "farFutureDeadline"; deadline: number }
+  | { type: "nothingInReturn" }
+  | { type: "delegateCall"; to: Address };
+
+const titles: Record<TypedDataSummary["kind"], string> = {
+  permit: "Token permit",
+  permit2Single: "Permit2 allowance",
+  permit2Batch: "Permit2 batch allowance",
+  permit2TransferFrom: "Permit2 transfer",
+  seaportOrder: "Seaport order",
+  safeTx: "Safe transaction",
+};
+
+export function TypedDataSummaryView({
+  summary,
+}: {
+  summary: TypedDataSummary;
+}) {
+  const { kind, spender, tokens, receives, deadline, warnings } = summary;
+
+  return (
+    <div className="flex flex-col gap-2 text-sm">
+      <h2 className="font-bold">{titles[kind]}</h2>
+
+      {spender && (
+        <div className="flex items-center gap-2">
+          {kind === "safeTx" ? "Target" : "Spender"}
+          <AddressView address={spender} />
+        </div>
+      )}
+
+      {tokens.length > 0 && (
+        <div className="flex flex-col gap-1">
+          {kind === "seaportOrder" ? "Offers" : "Tokens"}
+          {tokens.map((t, i) => (
+            <TokenAmountView key={i} {...t} />
+          ))}
+        </div>
+      )}
+
+      {kind === "seaportOrder" && receives.length > 0 && (
+        <div className="flex flex-col gap-1">
+          Receives
+          {receives.map((t, i) => (
+            <TokenAmountView key={i} {...t} />
+          ))}
+        </div>
+      )}
+
+      {deadline !== undefined && deadline !== null && (
+        <div>Valid until {formatDeadline(deadline)}</div>
+      )}
+
+      {warnings.map((w, i) => (
+        <Alert key={i} variant="destructive">
+          <AlertTitle>{warningTitle(w)}</AlertTitle>
+          <AlertDescription>{warningDescription(w)}</AlertDescription>
+        </Alert>
+      ))}
+    </div>
+  );
+}
+
+function TokenAmountView({
+  token,
+  symbol,
+  decimals,
+  amount,
+  tokenId,
+  unlimited,
+}: TokenAmount) {
+  const value = unlimited
+    ? "Unlimited"
+    : decimals !== undefined && decimals !== null
+      ? formatUnits(BigInt(amount), decimals)
+      : BigInt(amount).toString();
+
+  return (
+    <div className="flex items-center gap-2">
+      <span className="font-mono">{value}</span>
+      {symbol ?? <AddressView address={token} />}
+      {tokenId && <span>#{BigInt(tokenId).toString()}</span>}
+    </div>
+  );
+}
+
+function warningTitle(warning: Warning) {
+  switch (warning.type) {
+    case "unlimitedAmount":
+      return "Unlimited amount";
+    case "farFutureDeadline":
+      return "Long-lived signature";
+    case "nothingInReturn":
+      return "Nothing in return";
+    case "delegateCall":
+      return "Delegate call";
+  }
+}
+
+function warningDescription(warning: Warning) {
+  switch (warning.type) {
+    case "unlimitedAmount":
+      return "The spender will be able to move all of this token, now and in the future";
+    case "farFutureDeadline":
+      return `This signature remains usable until ${formatDeadline(warning.deadline)}`;
+    case "nothingInReturn":
+      return "You will not receive anything for the offered items";
+    case "delegateCall":
+      return "The target contract will run with full control over the Safe";
+  }
+}
+
+// Dates are capped at 8.64e15ms, so anything later is effectively forever
+const MAX_DATE = 8.64e15;
+
+function formatDeadline(deadline: number) {
+  if (deadline * 1000 > MAX_DATE) return "forever";
+  return new Date(deadline * 1000).toLocaleString();
+}
@@ -0,0 +1,80 @@
+import { Button } from "@ethui/ui/components/shadcn/button";
+import { Input } from "@ethui/ui/components/shadcn/input";
+import { toast } from "@ethui/ui/hooks/use-toast";
+import { invoke } from "@tauri-apps/api/core";
+import * as tauriClipboard from "@tauri-apps/plugin-clipboard-manager";
+import { Copy, PenLine } from "lucide-react";
+import { useState } from "react";
+import { type Address, isAddress } from "viem";
+import { Json } from "#/components/JsonView";
+
+interface Props {
+  address: Address;
+  chainId: number;
+}
+
+/// Signs an EIP-7702 authorization delegating `address` to a contract. The result is meant to be
+/// handed to whoever sends the type-4 transaction (e.g. a relayer)
+export function SignAuthorization({ address, chainId }: Props) {
+  const [delegate, setDelegate] = useState("");
+  const [nonce, setNonce] = useState("");
+  const [signed, setSigned] = useState<Record<string, unknown> | null>(null);
+
+  const sign = async () => {
+    try {
+      setSigned(
+        await invoke("wallets_sign_authorization", {
+          address,
+          chainId,
+          delegate,
+          nonce: nonce === "" ? null : Number(nonce),
+        }),
+      );
+    } catch (err) {
+      toast({ title: String(err), variant: "destructive" });
+    }
+  };
+
+  return (
+    <div className="flex flex-col gap-2">
+      <h2 className="font-semibold">EIP-7702 delegation</h2>
+      <div className="flex items-center gap-2 text-sm">
+        <Input
+          placeholder="Delegate contract"
+          value={delegate}
+          onChange={(e) => setDelegate(e.target.value)}
+        />
+        <Input
+          type="number"
+          min={0}
+          placeholder="Nonce (current by default)"
+          value={nonce}
+          onChange={(e) => setNonce(e.target.value)}
+        />
+        <Button
+          variant="outline"
+          size="sm"
+          disabled={!isAddress(delegate)}
+          onClick={sign}
+        >
+          <PenLine className="mr-2 h-4 w-4" />
+          Sign
+        </Button>
+      </div>
+
+      {signed && (
+        <div className="flex items-start gap-2 text-sm">
+          <Json src={signed} />
+          <Button
+            variant="outline"
+            size="sm"
+            onClick={() => tauriClipboard.writeText(JSON.stringify(signed))}
+          >
+            <Copy className="mr-2 h-4 w-4" />
+            Copy
+          </Button>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,86 @@
+import { Button } from "@ethui/ui/components/shadcn/button";
+import { toast } from "@ethui/ui/hooks/use-toast";
+import { invoke } from "@tauri-apps/api/core";
+import { FastForward, X } from "lucide-react";
+import type { Address } from "viem";
+import { HashView } from "#/components/HashView";
+import { useEventListener } from "#/hooks/useEventListener";
+import { useInvoke } from "#/hooks/useInvoke";
+
+interface PendingTx {
+  hash: `0x${string}`;
+  chainId: number;
+  dedupId: number;
+  from: Address;
+  nonce: number;
+  replaces?: `0x${string}` | null;
+}
+
+interface Props {
+  address: Address;
+  chainId: number;
+  dedupId: number;
+}
+
+/// Transactions sent from ethui that haven't been mined yet, which can be sped up or cancelled
+export function PendingTransactions({ address, chainId, dedupId }: Props) {
+  const { data: txs, refetch } = useInvoke<PendingTx[]>("rpc_pending_txs", {
+    chainId,
+    dedupId,
+    from: address,
+  });
+  useEventListener({ event: "txs-updated", callback: refetch });
+
+  if (!txs || txs.length === 0) return null;
+
+  const replace = async (
+    command: "rpc_speed_up" | "rpc_cancel",
+    tx: PendingTx,
+  ) => {
+    try {
+      await invoke(command, { hash: tx.hash });
+    } catch (err) {
+      toast({ title: String(err), variant: "destructive" });
+    }
+  };
+
+  return (
+    <div className="flex flex-col gap-2">
+      <h2 className="font-semibold">Pending transactions</h2>
+      {txs.map((tx) => (
+        <div
+          key={tx.hash}
+          className="flex items-center justify-between gap-2 border px-3 py-2 text-sm"
+        >
+          <div className="flex items-center gap-2">
+            <span className="text-muted-foreground">#{tx.nonce}</span>
+            <HashView hash={tx.hash} />
+            {tx.replaces && (
+              <span className="text-muted-foreground text-xs">
+                (replacement)
+              </span>
+            )}
+          </div>
+          <div className="flex gap-2">
+            <Button
+              variant="outline"
+              size="sm"
+              onClick={() => replace("rpc_speed_up", tx)}
+            >
+              <FastForward className="mr-2 h-4 w-4" />
+              Speed up
+            </Button>
+            <Button
+              variant="outline"
+              size="sm"
+              onClick={() => replace("rpc_cancel", tx)}
+            >
+              <X className="mr-2 h-4 w-4" />
+              Cancel
+            </Button>
+          </div>
+        </div>
+      ))}
+    </div>
+  );
+}
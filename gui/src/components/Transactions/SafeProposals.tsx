@@ -0,0 +1,163 @@
+import { Button } from "@ethui/ui/components/shadcn/button";
+import { Input } from "@ethui/ui/components/shadcn/input";
+import { toast } from "@ethui/ui/hooks/use-toast";
+import { invoke } from "@tauri-apps/api/core";
+import { PenLine, Play, Plus } from "lucide-react";
+import { useState } from "react";
+import type { Address, Hash, Hex } from "viem";
+import { HashView } from "#/components/HashView";
+import { useInvoke } from "#/hooks/useInvoke";
+
+interface SafeProposal {
+  safe_tx_hash: Hash;
+  chain_id: number;
+  safe: Address;
+  nonce: number;
+  tx: Record<string, unknown>;
+  signatures: Record<Address, Hex>;
+  executed_in: Hash | null;
+}
+
+interface Props {
+  safe: Address;
+  chainId: number;
+}
+
+/// Transactions proposed to a Safe that haven't been executed yet, along with the signatures
+/// collected for each of them
+export function SafeProposals({ safe, chainId }: Props) {
+  const { data: proposals, refetch } = useInvoke<SafeProposal[]>(
+    "rpc_safe_proposals",
+    { chainId, safe },
+  );
+
+  const run = async (command: string, args: Record<string, unknown>) => {
+    try {
+      await invoke(command, args);
+    } catch (err) {
+      toast({ title: String(err), variant: "destructive" });
+    }
+    refetch();
+  };
+
+  const pending = (proposals ?? []).filter((p) => !p.executed_in);
+
+  return (
+    <div className="flex flex-col gap-2">
+      <h2 className="font-semibold">Safe transactions</h2>
+      {pending.length === 0 && (
+        <span className="text-muted-foreground text-sm">
+          No transactions awaiting signatures
+        </span>
+      )}
+      {pending.map((proposal) => (
+        <Proposal key={proposal.safe_tx_hash} proposal={proposal} run={run} />
+      ))}
+      <Threshold safe={safe} chainId={chainId} run={run} />
+    </div>
+  );
+}
+
+interface ProposalProps {
+  proposal: SafeProposal;
+  run: (command: string, args: Record<string, unknown>) => Promise<void>;
+}
+
+function Proposal({ proposal, run }: ProposalProps) {
+  const [owner, setOwner] = useState("");
+  const [signature, setSignature] = useState("");
+  const safeTxHash = proposal.safe_tx_hash;
+
+  return (
+    <div className="flex flex-col gap-2 border px-3 py-2 text-sm">
+      <div className="flex items-center justify-between gap-2">
+        <div className="flex items-center gap-2">
+          <span className="text-muted-foreground">#{proposal.nonce}</span>
+          <HashView hash={safeTxHash} showLinkExplorer={false} />
+          <span className="text-muted-foreground text-xs">
+            {Object.keys(proposal.signatures).length} signature(s)
+          </span>
+        </div>
+        <div className="flex gap-2">
+          <Button
+            variant="outline"
+            size="sm"
+            onClick={() => run("rpc_safe_sign", { safeTxHash })}
+          >
+            <PenLine className="mr-2 h-4 w-4" />
+            Sign
+          </Button>
+          <Button
+            variant="outline"
+            size="sm"
+            onClick={() => run("rpc_safe_execute", { safeTxHash })}
+          >
+            <Play className="mr-2 h-4 w-4" />
+            Execute
+          </Button>
+        </div>
+      </div>
+
+      <div className="flex items-center gap-2">
+        <Input
+          placeholder="Owner"
+          value={owner}
+          onChange={(e) => setOwner(e.target.value)}
+        />
+        <Input
+          placeholder="Signature"
+          value={signature}
+          onChange={(e) => setSignature(e.target.value)}
+        />
+        <Button
+          variant="outline"
+          size="sm"
+          disabled={!owner || !signature}
+          onClick={() =>
+            run("rpc_safe_add_signature", { safeTxHash, owner, signature })
+          }
+        >
+          <Plus className="mr-2 h-4 w-4" />
+          Add signature
+        </Button>
+      </div>
+    </div>
+  );
+}
+
+interface ThresholdProps {
+  safe: Address;
+  chainId: number;
+  run: (command: string, args: Record<string, unknown>) => Promise<void>;
+}
+
+/// Overrides the Safe's threshold. Only available on dev networks (e.g. an anvil fork)
+function Threshold({ safe, chainId, run }: ThresholdProps) {
+  const [threshold, setThreshold] = useState("");
+
+  return (
+    <div className="flex items-center gap-2 text-sm">
+      <Input
+        type="number"
+        min={1}
+        placeholder="Threshold (dev networks only)"
+        value={threshold}
+        onChange={(e) => setThreshold(e.target.value)}
+      />
+      <Button
+        variant="outline"
+        size="sm"
+        disabled={!threshold}
+        onClick={() =>
+          run("rpc_safe_override_threshold", {
+            chainId,
+            safe,
+            threshold: Number(threshold),
+          })
+        }
+      >
+        Override threshold
+      </Button>
+    </div>
+  );
+}
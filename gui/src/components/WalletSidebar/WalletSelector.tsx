@@ -9,7 +9,10 @@ import { Button } from "@ethui/ui/components/shadcn/button";
 import { ScrollArea } from "@ethui/ui/components/shadcn/scroll-area";
 import { Check } from "lucide-react";
 import { useState } from "react";
+import type { Address } from "viem";
 import { useShallow } from "zustand/shallow";
+import { useInvoke } from "#/hooks/useInvoke";
+import { useNetworks } from "#/store/useNetworks";
 import {
   type AddressInfo,
   useWallets,
@@ -33,6 +36,7 @@ export function WalletSelector({ wallets }: WalletSelectorProps) {
   const [expandedWallet, setExpandedWallet] = useState<string>(
     currentWallet?.name || "",
   );
+  const delegations = useDelegations(wallets);
 
   function handleWalletAddressSelect(walletName: string, addressPath: string) {
     setCurrentWallet(walletName);
@@ -69,6 +73,7 @@ export function WalletSelector({ wallets }: WalletSelectorProps) {
                     walletInfo.addresses,
                   )}
                   onSelect={handleWalletAddressSelect}
+                  delegations={delegations}
                 />
               ))}
             </Accordion>
@@ -84,6 +89,7 @@ interface WalletAccordionItemProps {
   isCurrentWallet: boolean;
   currentAddress: string;
   onSelect: (walletName: string, addressPath: string) => void;
+  delegations: Record<Address, Address>;
 }
 
 function WalletAccordionItem({
@@ -91,6 +97,7 @@ function WalletAccordionItem({
   isCurrentWallet,
   currentAddress,
   onSelect,
+  delegations,
 }: WalletAccordionItemProps) {
   const wallet = walletInfo.wallet;
   const addresses = walletInfo.addresses;
@@ -118,6 +125,7 @@ function WalletAccordionItem({
               address={addressInfo.address}
               className="w-full py-2 pr-2 pl-4"
             />
+            <Delegation delegate={delegations[addressInfo.address]} />
             {isCurrentWallet && addressInfo.key === currentAddress && (
               <Check className="absolute right-2" color="green" />
             )}
@@ -128,6 +136,34 @@ function WalletAccordionItem({
   );
 }
 
+/// EIP-7702 delegations of every listed address on the current network, fetched in a single call
+/// and cached for a while, since they rarely change
+function useDelegations(wallets: WalletInfo[]): Record<Address, Address> {
+  const chainId = useNetworks((s) => s.current?.id.chain_id);
+  const addresses = wallets.flatMap((w) => w.addresses.map((a) => a.address));
+
+  const { data } = useInvoke<Record<Address, Address>>(
+    "wallets_get_delegations",
+    { chainId, addresses },
+    { enabled: !!chainId && addresses.length > 0, staleTime: 60_000 },
+  );
+
+  return data ?? {};
+}
+
+function Delegation({ delegate }: { delegate?: Address }) {
+  if (!delegate) return null;
+
+  return (
+    <span
+      className="shrink-0 pr-8 text-muted-foreground text-xs"
+      title={`Delegates to ${delegate}`}
+    >
+      7702 → {delegate.slice(0, 8)}…
+    </span>
+  );
+}
+
 function getCurrentAddress(wallet: Wallet, addresses: AddressInfo[]): string {
   switch (wallet.type) {
     case "HDWallet":
@@ -139,8 +175,15 @@ function getCurrentAddress(wallet: Wallet, addresses: AddressInfo[]): string {
       return wallet.currentPath || "";
     case "ledger":
       return wallet.addresses[wallet.current || 0][0];
+    case "remote":
+      return wallet.keys[wallet.current || 0]?.[0] || "";
     case "privateKey":
       return wallet.address;
+    case "keystoreDir":
+      return wallet.current || addresses[0]?.key || "";
+    case "safe":
+    case "smartAccount":
+      return "0";
     default:
       return addresses[0]?.key || "";
   }
@@ -1,8 +1,12 @@
 import type { Wallet } from "@ethui/types/wallets";
 import {
+  BotIcon,
   BugIcon,
   FileJsonIcon,
+  FolderKeyIcon,
   KeyRoundIcon,
+  ServerIcon,
+  ShieldIcon,
   UsbIcon,
   VenetianMaskIcon,
   WalletIcon,
@@ -23,6 +27,9 @@ export function WalletView({ name, type }: Props) {
     case "jsonKeystore":
       icon = <FileJsonIcon className="text-highlight" />;
       break;
+    case "keystoreDir":
+      icon = <FolderKeyIcon className="text-highlight" />;
+      break;
     case "privateKey":
       icon = <KeyRoundIcon className="text-highlight" />;
       break;
@@ -35,6 +42,15 @@ export function WalletView({ name, type }: Props) {
     case "ledger":
       icon = <UsbIcon className="text-highlight" />;
       break;
+    case "safe":
+      icon = <ShieldIcon className="text-highlight" />;
+      break;
+    case "smartAccount":
+      icon = <BotIcon className="text-highlight" />;
+      break;
+    case "remote":
+      icon = <ServerIcon className="text-highlight" />;
+      break;
   }
 
   return (
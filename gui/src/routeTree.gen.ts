@@ -28,6 +28,9 @@ import { Route as DialogLMsgSignIdRouteImport } from './routes/dialog/_l/msg-sig
 import { Route as DialogLErc721AddIdRouteImport } from './routes/dialog/_l/erc721-add.$id'
 import { Route as DialogLErc20AddIdRouteImport } from './routes/dialog/_l/erc20-add.$id'
 import { Route as DialogLErc1155AddIdRouteImport } from './routes/dialog/_l/erc1155-add.$id'
+import { Route as DialogLCallsReviewIdRouteImport } from './routes/dialog/_l/calls-review.$id'
+import { Route as DialogLConnectionRequestIdRouteImport } from './routes/dialog/_l/connection-request.$id'
+import { Route as DialogLChainSelectIdRouteImport } from './routes/dialog/_l/chain-select.$id'
 import { Route as DialogLChainSwitchIdRouteImport } from './routes/dialog/_l/chain-switch.$id'
 import { Route as DialogLChainAddIdRouteImport } from './routes/dialog/_l/chain-add.$id'
 import { Route as HomeLWalletsLIndexRouteImport } from './routes/home/_l/wallets/_l/index'
@@ -183,6 +186,21 @@ const DialogLErc1155AddIdRoute = DialogLErc1155AddIdRouteImport.update({
   path: '/erc1155-add/$id',
   getParentRoute: () => DialogLRoute,
 } as any)
+const DialogLCallsReviewIdRoute = DialogLCallsReviewIdRouteImport.update({
+  id: '/calls-review/$id',
+  path: '/calls-review/$id',
+  getParentRoute: () => DialogLRoute,
+} as any)
+const DialogLConnectionRequestIdRoute = DialogLConnectionRequestIdRouteImport.update({
+  id: '/connection-request/$id',
+  path: '/connection-request/$id',
+  getParentRoute: () => DialogLRoute,
+} as any)
+const DialogLChainSelectIdRoute = DialogLChainSelectIdRouteImport.update({
+  id: '/chain-select/$id',
+  path: '/chain-select/$id',
+  getParentRoute: () => DialogLRoute,
+} as any)
 const DialogLChainSwitchIdRoute = DialogLChainSwitchIdRouteImport.update({
   id: '/chain-switch/$id',
   path: '/chain-switch/$id',
@@ -339,6 +357,9 @@ export interface FileRoutesByFullPath {
   '/home/connections': typeof HomeLConnectionsRoute
   '/home/onboarding': typeof HomeLOnboardingRoute
   '/dialog/chain-add/$id': typeof DialogLChainAddIdRoute
+  '/dialog/calls-review/$id': typeof DialogLCallsReviewIdRoute
+  '/dialog/connection-request/$id': typeof DialogLConnectionRequestIdRoute
+  '/dialog/chain-select/$id': typeof DialogLChainSelectIdRoute
   '/dialog/chain-switch/$id': typeof DialogLChainSwitchIdRoute
   '/dialog/erc1155-add/$id': typeof DialogLErc1155AddIdRoute
   '/dialog/erc20-add/$id': typeof DialogLErc20AddIdRoute
@@ -384,6 +405,9 @@ export interface FileRoutesByTo {
   '/home/connections': typeof HomeLConnectionsRoute
   '/home/onboarding': typeof HomeLOnboardingRoute
   '/dialog/chain-add/$id': typeof DialogLChainAddIdRoute
+  '/dialog/calls-review/$id': typeof DialogLCallsReviewIdRoute
+  '/dialog/connection-request/$id': typeof DialogLConnectionRequestIdRoute
+  '/dialog/chain-select/$id': typeof DialogLChainSelectIdRoute
   '/dialog/chain-switch/$id': typeof DialogLChainSwitchIdRoute
   '/dialog/erc1155-add/$id': typeof DialogLErc1155AddIdRoute
   '/dialog/erc20-add/$id': typeof DialogLErc20AddIdRoute
@@ -429,6 +453,9 @@ export interface FileRoutesById {
   '/home/_l/connections': typeof HomeLConnectionsRoute
   '/home/_l/onboarding': typeof HomeLOnboardingRoute
   '/dialog/_l/chain-add/$id': typeof DialogLChainAddIdRoute
+  '/dialog/_l/calls-review/$id': typeof DialogLCallsReviewIdRoute
+  '/dialog/_l/connection-request/$id': typeof DialogLConnectionRequestIdRoute
+  '/dialog/_l/chain-select/$id': typeof DialogLChainSelectIdRoute
   '/dialog/_l/chain-switch/$id': typeof DialogLChainSwitchIdRoute
   '/dialog/_l/erc1155-add/$id': typeof DialogLErc1155AddIdRoute
   '/dialog/_l/erc20-add/$id': typeof DialogLErc20AddIdRoute
@@ -483,6 +510,9 @@ export interface FileRouteTypes {
     | '/home/connections'
     | '/home/onboarding'
     | '/dialog/chain-add/$id'
+    | '/dialog/calls-review/$id'
+    | '/dialog/connection-request/$id'
+    | '/dialog/chain-select/$id'
     | '/dialog/chain-switch/$id'
     | '/dialog/erc1155-add/$id'
     | '/dialog/erc20-add/$id'
@@ -528,6 +558,9 @@ export interface FileRouteTypes {
     | '/home/connections'
     | '/home/onboarding'
     | '/dialog/chain-add/$id'
+    | '/dialog/calls-review/$id'
+    | '/dialog/connection-request/$id'
+    | '/dialog/chain-select/$id'
     | '/dialog/chain-switch/$id'
     | '/dialog/erc1155-add/$id'
     | '/dialog/erc20-add/$id'
@@ -572,6 +605,9 @@ export interface FileRouteTypes {
     | '/home/_l/connections'
     | '/home/_l/onboarding'
     | '/dialog/_l/chain-add/$id'
+    | '/dialog/_l/calls-review/$id'
+    | '/dialog/_l/connection-request/$id'
+    | '/dialog/_l/chain-select/$id'
     | '/dialog/_l/chain-switch/$id'
     | '/dialog/_l/erc1155-add/$id'
     | '/dialog/_l/erc20-add/$id'
@@ -793,6 +829,27 @@ declare module '@tanstack/react-router' {
       preLoaderRoute: typeof DialogLErc1155AddIdRouteImport
       parentRoute: typeof DialogLRoute
     }
+    '/dialog/_l/calls-review/$id': {
+      id: '/dialog/_l/calls-review/$id'
+      path: '/calls-review/$id'
+      fullPath: '/dialog/calls-review/$id'
+      preLoaderRoute: typeof DialogLCallsReviewIdRouteImport
+      parentRoute: typeof DialogLRoute
+    }
+    '/dialog/_l/connection-request/$id': {
+      id: '/dialog/_l/connection-request/$id'
+      path: '/connection-request/$id'
+      fullPath: '/dialog/connection-request/$id'
+      preLoaderRoute: typeof DialogLConnectionRequestIdRouteImport
+      parentRoute: typeof DialogLRoute
+    }
+    '/dialog/_l/chain-select/$id': {
+      id: '/dialog/_l/chain-select/$id'
+      path: '/chain-select/$id'
+      fullPath: '/dialog/chain-select/$id'
+      preLoaderRoute: typeof DialogLChainSelectIdRouteImport
+      parentRoute: typeof DialogLRoute
+    }
     '/dialog/_l/chain-switch/$id': {
       id: '/dialog/_l/chain-switch/$id'
       path: '/chain-switch/$id'
@@ -994,6 +1051,9 @@ declare module '@tanstack/react-router' {
 
 interface DialogLRouteChildren {
   DialogLChainAddIdRoute: typeof DialogLChainAddIdRoute
+  DialogLCallsReviewIdRoute: typeof DialogLCallsReviewIdRoute
+  DialogLConnectionRequestIdRoute: typeof DialogLConnectionRequestIdRoute
+  DialogLChainSelectIdRoute: typeof DialogLChainSelectIdRoute
   DialogLChainSwitchIdRoute: typeof DialogLChainSwitchIdRoute
   DialogLErc1155AddIdRoute: typeof DialogLErc1155AddIdRoute
   DialogLErc20AddIdRoute: typeof DialogLErc20AddIdRoute
@@ -1006,6 +1066,9 @@ interface DialogLRouteChildren {
 
 const DialogLRouteChildren: DialogLRouteChildren = {
   DialogLChainAddIdRoute: DialogLChainAddIdRoute,
+  DialogLCallsReviewIdRoute: DialogLCallsReviewIdRoute,
+  DialogLConnectionRequestIdRoute: DialogLConnectionRequestIdRoute,
+  DialogLChainSelectIdRoute: DialogLChainSelectIdRoute,
   DialogLChainSwitchIdRoute: DialogLChainSwitchIdRoute,
   DialogLErc1155AddIdRoute: DialogLErc1155AddIdRoute,
   DialogLErc20AddIdRoute: DialogLErc20AddIdRoute,
@@ -0,0 +1,154 @@
+import { ChainView } from "@ethui/ui/components/chain-view";
+import { Button } from "@ethui/ui/components/shadcn/button";
+import { createFileRoute } from "@tanstack/react-router";
+import { Check, CheckIcon, X } from "lucide-react";
+import { useEffect, useState } from "react";
+import { type Address, formatEther, type Hex } from "viem";
+import { AddressView } from "#/components/AddressView";
+import { DialogBottom } from "#/components/Dialogs/Bottom";
+import { useDialog } from "#/hooks/useDialog";
+import { useNetworks } from "#/store/useNetworks";
+
+export const Route = createFileRoute("/dialog/_l/calls-review/$id")({
+  component: CallsReviewDialog,
+});
+
+interface Call {
+  to?: Address;
+  data?: Hex;
+  value?: string;
+}
+
+interface CallsRequest {
+  from: Address;
+  chainId: number;
+  walletType: string;
+  calls: Call[];
+}
+
+interface Simulation {
+  success: boolean;
+  gasUsed: bigint;
+  returnData: Hex;
+}
+
+function CallsReviewDialog() {
+  const { id } = Route.useParams();
+  const { data: request, send, listen } = useDialog<CallsRequest>(id);
+  const network = useNetworks((s) =>
+    s.networks.find((n) => n.id.chain_id === request?.chainId),
+  );
+
+  // one entry per call. `null` means the simulation itself failed to run
+  const [simulations, setSimulations] = useState<
+    (Simulation | null)[] | undefined
+  >(undefined);
+
+  useEffect(() => {
+    const unlisten = listen<(Simulation | null)[]>(
+      "simulation-result",
+      ({ payload }) => setSimulations(payload),
+    );
+
+    return () => {
+      unlisten.then((cb) => cb());
+    };
+  }, [listen]);
+
+  useEffect(() => {
+    send({ event: "simulate" });
+  }, [send]);
+
+  if (!request || !network) return null;
+
+  return (
+    <div className="flex flex-col gap-5 pb-16">
+      <div className="flex items-center justify-between">
+        <h1 className="font-xl">
+          Review {request.calls.length} call
+          {request.calls.length === 1 ? "" : "s"}
+        </h1>
+        <ChainView
+          chainId={network.id.chain_id}
+          name={network.name}
+          status={network.status}
+        />
+      </div>
+
+      <div className="flex items-center gap-2">
+        From <AddressView address={request.from} />
+      </div>
+
+      <ol className="flex flex-col gap-3">
+        {request.calls.map((call, i) => (
+          <CallView
+            // biome-ignore lint/suspicious/noArrayIndexKey: calls are positional and never reordered
+            key={i}
+            index={i}
+            call={call}
+            simulation={simulations?.[i]}
+          />
+        ))}
+      </ol>
+
+      <DialogBottom>
+        <div className="m-2 flex items-center justify-center gap-2">
+          <Button
+            variant="destructive"
+            onClick={() => send({ event: "reject" })}
+          >
+            <X />
+            Reject
+          </Button>
+          <Button type="submit" onClick={() => send({ event: "accept" })}>
+            <CheckIcon />
+            Confirm
+          </Button>
+        </div>
+      </DialogBottom>
+    </div>
+  );
+}
+
+interface CallViewProps {
+  index: number;
+  call: Call;
+  simulation?: Simulation | null;
+}
+
+function CallView({ index, call, simulation }: CallViewProps) {
+  return (
+    <li className="flex flex-col gap-1 border p-2">
+      <div className="flex items-center gap-2">
+        <span className="font-bold">#{index + 1}</span>
+        {call.to ? <AddressView address={call.to} /> : "contract deployment"}
+        {call.value && BigInt(call.value) > 0n && (
+          <span>{formatEther(BigInt(call.value))} Ξ</span>
+        )}
+        <SimulationStatus simulation={simulation} />
+      </div>
+      {call.data && (
+        <code className="break-all text-muted-foreground text-xs">
+          {call.data}
+        </code>
+      )}
+    </li>
+  );
+}
+
+function SimulationStatus({ simulation }: { simulation?: Simulation | null }) {
+  if (simulation === undefined) return <span>simulating...</span>;
+  if (simulation === null) return <span>simulation unavailable</span>;
+
+  return simulation.success ? (
+    <div className="flex gap-2">
+      <Check className="stroke-success" />
+      <span>{simulation.gasUsed.toString()} gas</span>
+    </div>
+  ) : (
+    <div className="flex gap-2 font-bold text-destructive">
+      <X className="stroke-destructive" />
+      <span>reverts</span>
+    </div>
+  );
+}
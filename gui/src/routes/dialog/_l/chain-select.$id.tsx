@@ -0,0 +1,70 @@
+import type { Network, NetworkId } from "@ethui/types/network";
+import { ChainView } from "@ethui/ui/components/chain-view";
+import { Button } from "@ethui/ui/components/shadcn/button";
+import { createFileRoute } from "@tanstack/react-router";
+import { useState } from "react";
+import { useDialog } from "#/hooks/useDialog";
+
+export const Route = createFileRoute("/dialog/_l/chain-select/$id")({
+  component: ChainSelectDialog,
+});
+
+interface ChainSelect {
+  domain: string | null;
+  chainId: number;
+  networks: Network[];
+}
+
+function ChainSelectDialog() {
+  const { id } = Route.useParams();
+  const { data: request, send } = useDialog<ChainSelect>(id);
+  const [picked, setPicked] = useState<NetworkId | undefined>();
+
+  if (!request) return null;
+
+  return (
+    <div className="flex flex-col gap-4 p-2">
+      <div className="flex flex-col items-center gap-2">
+        <h1 className="font-semibold text-lg">Select network</h1>
+        <p className="text-center text-muted-foreground text-sm">
+          {request.domain ?? "unknown domain"} wants to switch to chain{" "}
+          {request.chainId}, which several networks share
+        </p>
+      </div>
+
+      <div className="flex flex-col gap-2">
+        {request.networks.map((network) => (
+          <label
+            key={`${network.id.chain_id}-${network.id.dedup_id}`}
+            className="flex items-center gap-2"
+          >
+            <input
+              type="radio"
+              name="network"
+              checked={picked?.dedup_id === network.id.dedup_id}
+              onChange={() => setPicked(network.id)}
+            />
+            <ChainView
+              chainId={network.id.chain_id}
+              name={network.name}
+              status={network.status}
+            />
+          </label>
+        ))}
+      </div>
+
+      <div className="mt-auto flex items-center justify-center gap-3 pt-2">
+        <Button variant="destructive" onClick={() => send({ event: "reject" })}>
+          Cancel
+        </Button>
+        <Button
+          type="submit"
+          disabled={!picked}
+          onClick={() => send({ event: "accept", id: picked })}
+        >
+          Switch
+        </Button>
+      </div>
+    </div>
+  );
+}
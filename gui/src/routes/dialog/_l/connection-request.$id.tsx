@@ -0,0 +1,80 @@
+import { Button } from "@ethui/ui/components/shadcn/button";
+import { createFileRoute } from "@tanstack/react-router";
+import { useEffect, useState } from "react";
+import type { Address } from "viem";
+import { AddressView } from "#/components/AddressView";
+import { useDialog } from "#/hooks/useDialog";
+import { useWallets } from "#/store/useWallets";
+
+export const Route = createFileRoute("/dialog/_l/connection-request/$id")({
+  component: ConnectionRequestDialog,
+});
+
+interface ConnectionRequest {
+  domain: string | null;
+  current: Address;
+}
+
+function ConnectionRequestDialog() {
+  const { id } = Route.useParams();
+  const { data: request, send } = useDialog<ConnectionRequest>(id);
+  const allWalletInfo = useWallets((s) => s.allWalletInfo);
+  const [selected, setSelected] = useState<Address[]>([]);
+
+  // the current address is pre-selected
+  useEffect(() => {
+    if (request) setSelected([request.current]);
+  }, [request]);
+
+  if (!request || !allWalletInfo) return null;
+
+  const toggle = (address: Address) =>
+    setSelected((s) =>
+      s.includes(address) ? s.filter((a) => a !== address) : [...s, address],
+    );
+
+  return (
+    <div className="flex flex-col gap-4 p-2">
+      <div className="flex flex-col items-center gap-2">
+        <h1 className="font-semibold text-lg">Connection request</h1>
+        <p className="text-muted-foreground text-sm">
+          {request.domain ?? "unknown domain"} wants to see your accounts
+        </p>
+      </div>
+
+      <div className="flex flex-col gap-3">
+        {allWalletInfo.map(({ wallet, addresses }) => (
+          <div key={wallet.name} className="flex flex-col gap-1">
+            <p className="font-medium text-sm">{wallet.name}</p>
+            {addresses.map(({ address }) => (
+              <label
+                key={`${wallet.name}-${address}`}
+                className="flex items-center gap-2"
+              >
+                <input
+                  type="checkbox"
+                  checked={selected.includes(address)}
+                  onChange={() => toggle(address)}
+                />
+                <AddressView address={address} />
+              </label>
+            ))}
+          </div>
+        ))}
+      </div>
+
+      <div className="mt-auto flex items-center justify-center gap-3 pt-2">
+        <Button variant="destructive" onClick={() => send({ event: "reject" })}>
+          Reject
+        </Button>
+        <Button
+          type="submit"
+          disabled={selected.length === 0}
+          onClick={() => send({ event: "accept", accounts: selected })}
+        >
+          Connect
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -3,6 +3,10 @@ import { Button } from "@ethui/ui/components/shadcn/button";
 import { ScrollArea } from "@ethui/ui/components/shadcn/scroll-area";
 import { createFileRoute } from "@tanstack/react-router";
 import { type Hex, hexToString } from "viem";
+import {
+  type TypedDataSummary,
+  TypedDataSummaryView,
+} from "#/components/Dialogs/TypedDataSummary";
 import { Json } from "#/components/JsonView";
 import { useDialog } from "#/hooks/useDialog";
 
@@ -12,7 +16,9 @@ export const Route = createFileRoute("/dialog/_l/msg-sign/$id")({
 
 function MsgSignDialog() {
   const { id } = Route.useParams();
-  const { data, send } = useDialog<{ raw: Hex } | { typed: Hex }>(id);
+  const { data, send } = useDialog<
+    { raw: Hex } | { typed: Hex; summary?: TypedDataSummary }
+  >(id);
 
   if (!data) return null;
 
@@ -28,6 +34,9 @@ function MsgSignDialog() {
     <div className="flex h-full flex-col justify-between gap-3">
       <h1 className="font-xl">Sign Message</h1>
       <ScrollArea className="flex-grow">
+        {"typed" in data && data.summary && (
+          <TypedDataSummaryView summary={data.summary} />
+        )}
         <HighlightBox className="whitespace-pre-wrap text-sm">
           {json && <Json src={json} />}
           {!json && msg}
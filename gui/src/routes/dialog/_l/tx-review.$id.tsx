@@ -30,6 +30,8 @@ import {
 import { AddressView } from "#/components/AddressView";
 import { Datapoint } from "#/components/Datapoint";
 import { DialogBottom } from "#/components/Dialogs/Bottom";
+import { type CallFrame, CallTrace } from "#/components/Dialogs/CallTrace";
+import { type StateDiff, StateDiffView } from "#/components/Dialogs/StateDiff";
 import { IconAddress } from "#/components/Icons/Address";
 import { useAllAddresses } from "#/hooks/useAllAddresses";
 import type { Dialog } from "#/hooks/useDialog";
@@ -52,8 +54,35 @@ interface TxRequest {
     | "ledger"
     | "HdWallet"
     | "jsonKeystore"
+    | "keystoreDir"
     | "plaintext"
-    | "impersonator";
+    | "impersonator"
+    | "safe"
+    | "smartAccount"
+    | "remote";
+  userOperation?: UserOperation;
+  authorizationList?: Authorization[];
+  replaces?: `0x${string}`;
+}
+
+interface Authorization {
+  chainId: Hex;
+  address: Address;
+  nonce: Hex;
+  /// the account being delegated, recovered from the signature
+  authority: Address | null;
+}
+
+interface UserOperation {
+  sender: Address;
+  nonce: Hex;
+  factory?: Address;
+  callGasLimit: Hex;
+  verificationGasLimit: Hex;
+  preVerificationGas: Hex;
+  maxFeePerGas: Hex;
+  maxPriorityFeePerGas: Hex;
+  paymaster?: Address;
 }
 
 interface Log {
@@ -69,6 +98,34 @@ interface Simulation {
   blockNumber: bigint;
   logs: Log[];
   returnData: Hex;
+  stateDiff: StateDiff;
+  trace?: CallFrame;
+  revertReason?: RevertReason;
+}
+
+type RevertReason =
+  | { kind: "error"; message: string }
+  | { kind: "panic"; code: Hex; description: string }
+  | {
+      kind: "custom";
+      signature: string;
+      params: { name: string; type: string; value: string }[];
+      contract?: string;
+    };
+
+function formatRevertReason(reason: RevertReason) {
+  switch (reason.kind) {
+    case "error":
+      return reason.message;
+    case "panic":
+      return `panic: ${reason.description}`;
+    case "custom": {
+      const params = reason.params
+        .map(({ name, value }) => (name ? `${name}: ${value}` : value))
+        .join(", ");
+      return `${reason.signature.split("(")[0]}(${params})`;
+    }
+  }
 }
 
 function TxReviewDialog() {
@@ -146,6 +203,14 @@ function Inner({ dialog, request, network }: InnerProps) {
     <>
       <Header {...{ from, to, network }} />
 
+      {request.replaces && (
+        <div className="my-2 border px-3 py-2 text-sm">
+          Replaces pending transaction{" "}
+          <span className="font-mono">{request.replaces}</span>, using the same
+          nonce with higher fees
+        </div>
+      )}
+
       {item && (
         <AbiItemFormWithPreview
           abiFunction={item}
@@ -160,6 +225,14 @@ function Inner({ dialog, request, network }: InnerProps) {
         />
       )}
 
+      {request.authorizationList && request.authorizationList.length > 0 && (
+        <Authorizations authorizations={request.authorizationList} />
+      )}
+
+      {request.userOperation && (
+        <UserOperationDetails userOperation={request.userOperation} />
+      )}
+
       <div className="my-4">
         {simulation && (
           <SimulationResult
@@ -210,6 +283,86 @@ function Header({ from, to, network }: HeaderProps) {
   );
 }
 
+function Authorizations({
+  authorizations,
+}: {
+  authorizations: Authorization[];
+}) {
+  return (
+    <Datapoint
+      label="EIP-7702 Delegations"
+      className="my-4"
+      value={
+        <div className="flex flex-col gap-1">
+          {authorizations.map(({ address, chainId, nonce, authority }) => (
+            <div
+              key={`${authority}-${address}-${nonce}`}
+              className="flex items-center gap-2"
+            >
+              {authority ? (
+                <AddressView address={authority} />
+              ) : (
+                <span className="text-destructive">invalid signature</span>
+              )}
+              <span>→</span>
+              <AddressView address={address} />
+              <span className="text-muted-foreground text-xs">
+                chain {BigInt(chainId).toString()}, nonce{" "}
+                {BigInt(nonce).toString()}
+              </span>
+            </div>
+          ))}
+        </div>
+      }
+    />
+  );
+}
+
+function UserOperationDetails({
+  userOperation,
+}: {
+  userOperation: UserOperation;
+}) {
+  const {
+    paymaster,
+    factory,
+    callGasLimit,
+    verificationGasLimit,
+    preVerificationGas,
+    maxFeePerGas,
+  } = userOperation;
+
+  return (
+    <div className="my-4 grid grid-cols-4 gap-5">
+      <Datapoint
+        label="Paymaster"
+        value={
+          paymaster ? <AddressView address={paymaster} /> : "None (self-paid)"
+        }
+        className="col-span-2"
+      />
+      <Datapoint
+        label="Account"
+        value={factory ? "Deployed with this operation" : "Already deployed"}
+        className="col-span-2"
+      />
+      <Datapoint label="Call Gas" value={BigInt(callGasLimit).toString()} />
+      <Datapoint
+        label="Verification Gas"
+        value={BigInt(verificationGasLimit).toString()}
+      />
+      <Datapoint
+        label="Pre-verification Gas"
+        value={BigInt(preVerificationGas).toString()}
+      />
+      <Datapoint
+        label="Max Fee"
+        value={`${formatUnits(BigInt(maxFeePerGas), 9)} gwei`}
+      />
+    </div>
+  );
+}
+
 interface SimulationResultProps {
   simulation: Simulation;
   chainId: number;
@@ -261,7 +414,11 @@ function SimulationResult({
             <div className="flex gap-2 font-bold text-destructive">
               <X className="stroke-destructive" />
               <span>
-                {"value" in decodedError ? decodedError.value?.errorName : null}
+                {simulation.revertReason
+                  ? formatRevertReason(simulation.revertReason)
+                  : "value" in decodedError
+                    ? decodedError.value?.errorName
+                    : null}
               </span>
             </div>
           )
@@ -275,6 +432,14 @@ function SimulationResult({
           <Log key={i} log={log} chainId={chainId} />
         ))}
       </div>
+      <div className="col-span-4">
+        <StateDiffView diff={simulation.stateDiff} />
+      </div>
+      {simulation.trace && (
+        <div className="col-span-4">
+          <CallTrace trace={simulation.trace} />
+        </div>
+      )}
     </div>
   );
 }
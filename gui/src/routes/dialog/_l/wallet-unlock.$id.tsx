@@ -13,7 +13,7 @@ export const Route = createFileRoute("/dialog/_l/wallet-unlock/$id")({
 
 interface Request {
   name: string;
-  file: string;
+  key?: string | null;
 }
 
 const schema = z.object({ password: z.string() });
@@ -45,7 +45,7 @@ function WalletUnlockDialog() {
 
   if (!data) return null;
 
-  const { name } = data;
+  const { name, key } = data;
 
   const onSubmit = (data: FieldValues) => {
     send(data);
@@ -62,6 +62,12 @@ function WalletUnlockDialog() {
         label={
           <>
             unlock wallet <b>{name}</b>
+            {key && (
+              <>
+                {" "}
+                (key <b>{key}</b>)
+              </>
+            )}
           </>
         }
         name="password"
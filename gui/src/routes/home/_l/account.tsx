@@ -3,6 +3,10 @@ import { createFileRoute, Link } from "@tanstack/react-router";
 import { User } from "lucide-react";
 import { AddressView } from "#/components/AddressView";
 import { BalancesList } from "#/components/BalancesList";
+import { SignAuthorization } from "#/components/SignAuthorization";
+import { PendingTransactions } from "#/components/Transactions/PendingTransactions";
+import { SafeProposals } from "#/components/Transactions/SafeProposals";
+import { useNetworks } from "#/store/useNetworks";
 import { useWallets } from "#/store/useWallets";
 
 export const Route = createFileRoute("/home/_l/account")({
@@ -10,8 +14,14 @@ export const Route = createFileRoute("/home/_l/account")({
   component: Account,
 });
 
+/// wallets that can't sign an EIP-7702 authorization, since there's no key behind their address
+const KEYLESS = ["impersonator", "safe", "smartAccount"];
+
 function Account() {
   const address = useWallets((s) => s.address);
+  const walletType = useWallets((s) => s.currentWallet?.type);
+  const networkId = useNetworks((s) => s.current?.id);
+  const chainId = networkId?.chain_id;
 
   if (!address) return null;
 
@@ -34,6 +44,22 @@ function Account() {
         </Link>
       </div>
 
+      {networkId && (
+        <PendingTransactions
+          address={address}
+          chainId={networkId.chain_id}
+          dedupId={networkId.dedup_id}
+        />
+      )}
+
+      {chainId && walletType === "safe" && (
+        <SafeProposals safe={address} chainId={chainId} />
+      )}
+
+      {chainId && walletType && !KEYLESS.includes(walletType) && (
+        <SignAuthorization address={address} chainId={chainId} />
+      )}
+
       <BalancesList />
     </div>
   );
@@ -191,10 +191,55 @@ function Domain({ domain, peers }: { domain: string; peers: Peer[] }) {
       <div>
         <AffinityForm domain={domain} />
       </div>
+      <div>
+        <AccountForm domain={domain} />
+      </div>
     </div>
   );
 }
 
+function AccountForm({ domain }: { domain: string }) {
+  const allWalletInfo = useWallets((s) => s.allWalletInfo);
+  const { data: account, refetch } = useInvoke<string | null>(
+    "connections_account_for",
+    { domain },
+  );
+
+  useEventListener({ event: "peers-updated", callback: refetch });
+
+  const accounts = uniqBy(
+    (allWalletInfo || []).flatMap((w) => w.addresses),
+    (a) => a.address.toLowerCase(),
+  );
+
+  const handleChange = async (value: string) => {
+    await invoke("connections_set_account", {
+      domain,
+      account: value === "wallet" ? null : value,
+    });
+    refetch();
+  };
+
+  return (
+    <Select value={account ?? "wallet"} onValueChange={handleChange}>
+      <SelectTrigger className="w-36">
+        <SelectValue />
+      </SelectTrigger>
+
+      <SelectContent>
+        <SelectGroup>
+          <SelectItem value="wallet">Current wallet</SelectItem>
+          {accounts.map((a) => (
+            <SelectItem key={a.address} value={a.address}>
+              {a.alias || `${a.address.slice(0, 6)}…${a.address.slice(-4)}`}
+            </SelectItem>
+          ))}
+        </SelectGroup>
+      </SelectContent>
+    </Select>
+  );
+}
+
 function AffinityForm({ domain }: { domain: string }) {
   const [networks, currentGlobalNetwork] = useNetworks(
     useShallow((s) => [s.networks, s.current]),
@@ -1,5 +1,6 @@
 import { type NetworkInputs, networkSchema } from "@ethui/types/network";
 import { Form } from "@ethui/ui/components/form";
+import { AutoSubmitSwitch } from "@ethui/ui/components/form/auto-submit/switch";
 import { Button } from "@ethui/ui/components/shadcn/button";
 import { toast } from "@ethui/ui/hooks/use-toast";
 import { zodResolver } from "@hookform/resolvers/zod";
@@ -84,6 +85,17 @@ function Content({ network }: { network: NetworkInputs }) {
         <Form.NumberField label="Decimals" name="decimals" />
       </div>
 
+      <div className="w-80">
+        <AutoSubmitSwitch
+          name="rpc_cache"
+          label="Cache immutable RPC responses"
+          value={network.rpc_cache ?? false}
+          callback={async (rpcCache: boolean) =>
+            form.setValue("rpc_cache", rpcCache)
+          }
+        />
+      </div>
+
       <div className="flex gap-2">
         <Button variant="destructive" onClick={remove}>
           Remove
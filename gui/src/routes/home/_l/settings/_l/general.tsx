@@ -141,6 +141,28 @@ function SettingsGeneral() {
           }
         />
       </div>
+
+      <div className="w-80">
+        <AutoSubmitSwitch
+          name="persistRpcHistory"
+          label="Persist RPC history"
+          value={general.persistRpcHistory}
+          callback={async (persistRpcHistory: boolean) =>
+            await invoke("settings_set", { params: { persistRpcHistory } })
+          }
+        />
+      </div>
+
+      <div className="w-80">
+        <AutoSubmitSwitch
+          name="redactRpcHistory"
+          label="Redact signatures from RPC history"
+          value={general.redactRpcHistory}
+          callback={async (redactRpcHistory: boolean) =>
+            await invoke("settings_set", { params: { redactRpcHistory } })
+          }
+        />
+      </div>
     </div>
   );
 }
@@ -1,6 +1,7 @@
 import { createFileRoute } from "@tanstack/react-router";
 import { useWallets } from "#/store/useWallets";
 import { WalletEdit } from "./-components/Edit";
+import { WalletSecrets } from "./-components/WalletSecrets";
 
 export const Route = createFileRoute("/home/_l/wallets/_l/$name/edit")({
   // TODO: fetch from invoke directly
@@ -13,6 +14,11 @@ export const Route = createFileRoute("/home/_l/wallets/_l/$name/edit")({
     // TODO: can we show an error here instead?
     if (!wallet) return;
 
-    return <WalletEdit wallet={wallet} />;
+    return (
+      <>
+        <WalletEdit wallet={wallet} />
+        <WalletSecrets wallet={wallet} />
+      </>
+    );
   },
 });
@@ -4,9 +4,13 @@ import { invoke } from "@tauri-apps/api/core";
 import { HDWalletForm } from "./HDWallet";
 import { ImpersonatorForm } from "./Impersonator";
 import { JsonKeystore } from "./JsonKeystore";
+import { KeystoreDir } from "./KeystoreDir";
 import { Ledger } from "./Ledger";
 import { Plaintext } from "./Plaintext";
 import { PrivateKeyForm } from "./PrivateKey";
+import { SafeForm } from "./Safe";
+import { RemoteForm } from "./Remote";
+import { SmartAccountForm } from "./SmartAccount";
 
 export function WalletEdit({ wallet }: { wallet: Wallet }) {
   const navigate = useNavigate();
@@ -33,11 +37,19 @@ export function WalletEdit({ wallet }: { wallet: Wallet }) {
       return <HDWalletForm {...props} wallet={wallet} />;
     case "jsonKeystore":
       return <JsonKeystore {...props} wallet={wallet} />;
+    case "keystoreDir":
+      return <KeystoreDir {...props} wallet={wallet} />;
     case "impersonator":
       return <ImpersonatorForm {...props} wallet={wallet} />;
     case "privateKey":
       return <PrivateKeyForm {...props} wallet={wallet} />;
     case "ledger":
       return <Ledger {...props} wallet={wallet} />;
+    case "safe":
+      return <SafeForm {...props} wallet={wallet} />;
+    case "smartAccount":
+      return <SmartAccountForm {...props} wallet={wallet} />;
+    case "remote":
+      return <RemoteForm {...props} wallet={wallet} />;
   }
 }
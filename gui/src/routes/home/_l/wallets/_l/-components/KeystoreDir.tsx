@@ -0,0 +1,87 @@
+import type { KeystoreDirWallet, Wallet } from "@ethui/types/wallets";
+import { Form } from "@ethui/ui/components/form";
+import { Button } from "@ethui/ui/components/shadcn/button";
+import { toast } from "@ethui/ui/hooks/use-toast";
+import { zodResolver } from "@hookform/resolvers/zod";
+import { invoke } from "@tauri-apps/api/core";
+import { LockOpen } from "lucide-react";
+import { useForm } from "react-hook-form";
+import { z } from "zod";
+
+const createSchema = z.object({
+  name: z.string().min(1),
+  dir: z.string().optional(),
+});
+
+const updateSchema = createSchema.pick({ name: true });
+
+interface KeystoreDirProps {
+  wallet?: KeystoreDirWallet;
+  onSubmit: (data: Wallet) => void;
+  onRemove: () => void;
+}
+
+export function KeystoreDir({ wallet, onSubmit, onRemove }: KeystoreDirProps) {
+  const schema = wallet ? updateSchema : createSchema;
+  const form = useForm({
+    mode: "onBlur",
+    resolver: zodResolver(schema),
+    defaultValues: wallet,
+  });
+
+  const prepareAndSubmit = (data: z.infer<typeof schema>) => {
+    // an empty directory falls back to foundry's default
+    const dir = "dir" in data && data.dir ? data.dir : undefined;
+    onSubmit({ type: "keystoreDir", ...data, dir } as Wallet);
+    form.reset();
+  };
+
+  return (
+    <Form form={form} onSubmit={prepareAndSubmit} className="gap-4">
+      <Form.Text label="Name" name="name" className="w-full" />
+      {!wallet && (
+        <Form.Text
+          label="Keystore directory (defaults to ~/.foundry/keystores)"
+          name="dir"
+          className="w-full"
+        />
+      )}
+      {wallet && (
+        <p className="text-muted-foreground text-sm">
+          Watching {wallet.dir}. Keystores that don't include their address
+          need to be unlocked once before they can be used.
+        </p>
+      )}
+      {wallet?.locked?.map((key) => (
+        <LockedKeystore key={key} name={wallet.name} keystore={key} />
+      ))}
+
+      <div className="flex gap-2">
+        <Form.Submit label="Save" />
+        <Button variant="destructive" onClick={onRemove}>
+          Remove
+        </Button>
+      </div>
+    </Form>
+  );
+}
+
+function LockedKeystore({ name, keystore }: { name: string; keystore: string }) {
+  const unlock = async () => {
+    try {
+      await invoke("wallets_unlock_keystore", { name, key: keystore });
+    } catch (err) {
+      toast({ title: String(err), variant: "destructive" });
+    }
+  };
+
+  return (
+    <div className="flex items-center justify-between gap-2 text-sm">
+      <span className="font-mono">{keystore}</span>
+      <Button type="button" variant="outline" size="sm" onClick={unlock}>
+        <LockOpen className="mr-2 h-4 w-4" />
+        Unlock
+      </Button>
+    </div>
+  );
+}
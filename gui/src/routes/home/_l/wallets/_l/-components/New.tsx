@@ -3,9 +3,13 @@ import { invoke } from "@tauri-apps/api/core";
 import { HDWalletForm } from "./HDWallet";
 import { ImpersonatorForm } from "./Impersonator";
 import { JsonKeystore } from "./JsonKeystore";
+import { KeystoreDir } from "./KeystoreDir";
 import { Ledger } from "./Ledger";
 import { Plaintext } from "./Plaintext";
 import { PrivateKeyForm } from "./PrivateKey";
+import { SafeForm } from "./Safe";
+import { RemoteForm } from "./Remote";
+import { SmartAccountForm } from "./SmartAccount";
 
 export function WalletNew({ type }: { type: string }) {
   const navigate = useNavigate();
@@ -31,11 +35,19 @@ export function WalletNew({ type }: { type: string }) {
       return <HDWalletForm {...props} />;
     case "jsonKeystore":
       return <JsonKeystore {...props} />;
+    case "keystoreDir":
+      return <KeystoreDir {...props} />;
     case "impersonator":
       return <ImpersonatorForm {...props} />;
     case "privateKey":
       return <PrivateKeyForm {...props} />;
     case "ledger":
       return <Ledger {...props} />;
+    case "safe":
+      return <SafeForm {...props} />;
+    case "smartAccount":
+      return <SmartAccountForm {...props} />;
+    case "remote":
+      return <RemoteForm {...props} />;
   }
 }
@@ -0,0 +1,145 @@
+import type { RemoteWallet, Wallet } from "@ethui/types/wallets";
+import { Form } from "@ethui/ui/components/form";
+import { Button } from "@ethui/ui/components/shadcn/button";
+import { zodResolver } from "@hookform/resolvers/zod";
+import { useForm } from "react-hook-form";
+import { z } from "zod";
+
+const schema = z
+  .object({
+    name: z.string().min(1),
+    url: z.string().url(),
+    api: z.enum(["web3Signer", "jsonRpc"]),
+    authType: z.enum(["none", "bearer", "mtls"]),
+    tokenPath: z.string().optional(),
+    cert: z.string().optional(),
+    key: z.string().optional(),
+    ca: z.string().optional(),
+  })
+  .refine((data) => data.authType !== "bearer" || !!data.tokenPath, {
+    message: "Required",
+    path: ["tokenPath"],
+  })
+  .refine((data) => data.authType !== "mtls" || (!!data.cert && !!data.key), {
+    message: "Both the certificate and key are required",
+    path: ["key"],
+  });
+
+type Schema = z.infer<typeof schema>;
+
+const apis = [
+  { value: "web3Signer", label: "Web3Signer REST API" },
+  { value: "jsonRpc", label: "JSON-RPC (eth_sign / eth_signTransaction)" },
+];
+
+const authTypes = [
+  { value: "none", label: "None" },
+  { value: "bearer", label: "Bearer token" },
+  { value: "mtls", label: "Client certificate (mTLS)" },
+];
+
+interface Props {
+  wallet?: RemoteWallet;
+  onSubmit: (data: Wallet) => void;
+  onRemove: () => void;
+}
+
+function toDefaults(wallet?: RemoteWallet): Partial<Schema> {
+  if (!wallet) return { api: "web3Signer", authType: "none" };
+
+  const { auth } = wallet;
+  return {
+    name: wallet.name,
+    url: wallet.url,
+    api: wallet.api,
+    authType: auth.type,
+    tokenPath: auth.type === "bearer" ? auth.path : undefined,
+    cert: auth.type === "mtls" ? auth.cert : undefined,
+    key: auth.type === "mtls" ? auth.key : undefined,
+    ca: wallet.ca ?? undefined,
+  };
+}
+
+export function RemoteForm({ wallet, onSubmit, onRemove }: Props) {
+  const form = useForm({
+    mode: "onBlur",
+    resolver: zodResolver(schema),
+    defaultValues: toDefaults(wallet),
+  });
+  const authType = form.watch("authType");
+
+  const prepareAndSubmit = (data: Schema) => {
+    const auth: RemoteWallet["auth"] =
+      data.authType === "bearer"
+        ? { type: "bearer", path: data.tokenPath ?? "" }
+        : data.authType === "mtls"
+          ? { type: "mtls", cert: data.cert ?? "", key: data.key ?? "" }
+          : { type: "none" };
+
+    // the list of keys is fetched from the signer by the backend
+    onSubmit({
+      type: "remote",
+      name: data.name,
+      url: data.url,
+      api: data.api,
+      auth,
+      ca: data.ca || null,
+      keys: wallet?.keys ?? [],
+    });
+    form.reset(data);
+  };
+
+  return (
+    <Form form={form} onSubmit={prepareAndSubmit} className="gap-4">
+      <Form.Text label="Name" name="name" className="w-full" />
+      <Form.Text label="Signer URL" name="url" className="w-full" />
+      <Form.Select
+        label="API"
+        name="api"
+        items={apis}
+        toValue={(item) => item.value}
+        render={(item) => item.label}
+      />
+      <Form.Select
+        label="Authentication"
+        name="authType"
+        items={authTypes}
+        toValue={(item) => item.value}
+        render={(item) => item.label}
+      />
+      {authType === "bearer" && (
+        <Form.Text
+          label="Token (file containing it)"
+          name="tokenPath"
+          className="w-full"
+        />
+      )}
+      {authType === "mtls" && (
+        <>
+          <Form.Text
+            label="Client certificate (PEM file)"
+            name="cert"
+            className="w-full"
+          />
+          <Form.Text
+            label="Client key (PEM file)"
+            name="key"
+            className="w-full"
+          />
+        </>
+      )}
+      <Form.Text
+        label="CA certificate (optional, PEM file)"
+        name="ca"
+        className="w-full"
+      />
+
+      <div className="flex gap-2">
+        <Form.Submit label="Save" />
+        <Button variant="destructive" onClick={onRemove}>
+          Remove
+        </Button>
+      </div>
+    </Form>
+  );
+}
@@ -0,0 +1,50 @@
+import {
+  addressSchema,
+  type SafeWallet,
+  type Wallet,
+} from "@ethui/types/wallets";
+import { Form } from "@ethui/ui/components/form";
+import { Button } from "@ethui/ui/components/shadcn/button";
+import { zodResolver } from "@hookform/resolvers/zod";
+import { useForm } from "react-hook-form";
+import { z } from "zod";
+
+const schema = z.object({
+  name: z.string().min(1),
+  address: addressSchema,
+});
+
+type Schema = z.infer<typeof schema>;
+
+interface Props {
+  wallet?: SafeWallet;
+  onSubmit: (data: Wallet) => void;
+  onRemove: () => void;
+}
+
+export function SafeForm({ wallet, onSubmit, onRemove }: Props) {
+  const form = useForm({
+    mode: "onBlur",
+    resolver: zodResolver(schema),
+    defaultValues: wallet,
+  });
+
+  const prepareAndSubmit = (data: Schema) => {
+    onSubmit({ ...data, type: "safe" });
+    form.reset(data);
+  };
+
+  return (
+    <Form form={form} onSubmit={prepareAndSubmit} className="gap-4">
+      <Form.Text label="Name" name="name" className="w-full" />
+      <Form.Text label="Safe address" name="address" className="w-full" />
+
+      <div className="flex gap-2">
+        <Form.Submit label="Save" />
+        <Button variant="destructive" onClick={onRemove}>
+          Remove
+        </Button>
+      </div>
+    </Form>
+  );
+}
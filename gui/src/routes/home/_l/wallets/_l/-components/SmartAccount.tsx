@@ -0,0 +1,73 @@
+import {
+  addressSchema,
+  type SmartAccountWallet,
+  type Wallet,
+} from "@ethui/types/wallets";
+import { Form } from "@ethui/ui/components/form";
+import { Button } from "@ethui/ui/components/shadcn/button";
+import { zodResolver } from "@hookform/resolvers/zod";
+import { useForm } from "react-hook-form";
+import { zeroAddress } from "viem";
+import { z } from "zod";
+
+const schema = z.object({
+  name: z.string().min(1),
+  owner: addressSchema,
+  bundlerUrl: z.string().url(),
+  factory: addressSchema.optional().or(z.literal("")),
+  paymaster: addressSchema.optional().or(z.literal("")),
+});
+
+type Schema = z.infer<typeof schema>;
+
+interface Props {
+  wallet?: SmartAccountWallet;
+  onSubmit: (data: Wallet) => void;
+  onRemove: () => void;
+}
+
+export function SmartAccountForm({ wallet, onSubmit, onRemove }: Props) {
+  const form = useForm({
+    mode: "onBlur",
+    resolver: zodResolver(schema),
+    defaultValues: wallet,
+  });
+
+  const prepareAndSubmit = (data: Schema) => {
+    // the account address is derived by the backend, and empty optional fields fall back to the
+    // defaults
+    onSubmit({
+      ...data,
+      type: "smartAccount",
+      address: wallet?.address ?? zeroAddress,
+      factory: data.factory || undefined,
+      paymaster: data.paymaster || undefined,
+    });
+    form.reset(data);
+  };
+
+  return (
+    <Form form={form} onSubmit={prepareAndSubmit} className="gap-4">
+      <Form.Text label="Name" name="name" className="w-full" />
+      <Form.Text label="Owner address" name="owner" className="w-full" />
+      <Form.Text label="Bundler URL" name="bundlerUrl" className="w-full" />
+      <Form.Text
+        label="Account factory (optional)"
+        name="factory"
+        className="w-full"
+      />
+      <Form.Text
+        label="Paymaster (optional)"
+        name="paymaster"
+        className="w-full"
+      />
+
+      <div className="flex gap-2">
+        <Form.Submit label="Save" />
+        <Button variant="destructive" onClick={onRemove}>
+          Remove
+        </Button>
+      </div>
+    </Form>
+  );
+}
@@ -0,0 +1,258 @@
+import type { LockPolicy } from "@ethui/types/settings";
+import type { Wallet } from "@ethui/types/wallets";
+import { Form } from "@ethui/ui/components/form";
+import { Button } from "@ethui/ui/components/shadcn/button";
+import { toast } from "@ethui/ui/hooks/use-toast";
+import { zodResolver } from "@hookform/resolvers/zod";
+import { invoke } from "@tauri-apps/api/core";
+import { useState } from "react";
+import { useForm } from "react-hook-form";
+import { z } from "zod";
+import { useInvoke } from "#/hooks/useInvoke";
+import { useSettings } from "#/store/useSettings";
+
+/// wallets holding their own keys, which can be exported
+const LOCAL_KEY_WALLETS: Wallet["type"][] = [
+  "HDWallet",
+  "privateKey",
+  "plaintext",
+  "jsonKeystore",
+  "keystoreDir",
+];
+
+/// wallets that ask for a password, and so can be locked
+const LOCKABLE_WALLETS: Wallet["type"][] = [
+  "HDWallet",
+  "privateKey",
+  "jsonKeystore",
+  "keystoreDir",
+];
+
+export function WalletSecrets({ wallet }: { wallet: Wallet }) {
+  const hasPassword = wallet.type === "HDWallet" || wallet.type === "privateKey";
+
+  return (
+    <div className="mt-8 flex flex-col gap-8">
+      {LOCKABLE_WALLETS.includes(wallet.type) && (
+        <LockPolicyForm name={wallet.name} />
+      )}
+      {hasPassword && <ChangePassword name={wallet.name} />}
+      {LOCAL_KEY_WALLETS.includes(wallet.type) && (
+        <ExportKeystore name={wallet.name} />
+      )}
+      {wallet.type === "HDWallet" && <RevealMnemonic name={wallet.name} />}
+    </div>
+  );
+}
+
+const lockPolicySchema = z
+  .object({
+    type: z.enum(["timeout", "idle", "onSleep", "never"]),
+    minutes: z.coerce.number().positive().optional(),
+  })
+  .refine(
+    (data) =>
+      (data.type !== "timeout" && data.type !== "idle") || !!data.minutes,
+    { message: "Required", path: ["minutes"] },
+  );
+
+const lockPolicyTypes = [
+  { value: "timeout", label: "Lock a fixed time after unlocking" },
+  { value: "idle", label: "Lock after not being used for a while" },
+  { value: "onSleep", label: "Lock when the computer sleeps" },
+  { value: "never", label: "Never lock (dev wallets only)" },
+];
+
+function LockPolicyForm({ name }: { name: string }) {
+  const policy: LockPolicy = useSettings(
+    (s) => s.settings?.lockPolicies?.[name],
+  ) ?? { type: "timeout", secs: 60 };
+
+  const form = useForm({
+    mode: "onBlur",
+    resolver: zodResolver(lockPolicySchema),
+    values: {
+      type: policy.type,
+      minutes: "secs" in policy ? policy.secs / 60 : undefined,
+    },
+  });
+  const type = form.watch("type");
+
+  const onSubmit = async ({
+    type,
+    minutes,
+  }: z.infer<typeof lockPolicySchema>) => {
+    const policy: LockPolicy =
+      type === "timeout" || type === "idle"
+        ? { type, secs: Math.round((minutes ?? 1) * 60) }
+        : { type };
+
+    try {
+      await invoke("wallets_set_lock_policy", { name, policy });
+      toast({ title: "Lock policy saved" });
+    } catch (err) {
+      toast({ title: String(err), variant: "destructive" });
+    }
+  };
+
+  return (
+    <Form form={form} onSubmit={onSubmit} className="gap-4">
+      <h2 className="font-semibold text-lg">Auto-lock</h2>
+      <Form.Select
+        label="Policy"
+        name="type"
+        items={lockPolicyTypes}
+        toValue={(item) => item.value}
+        render={(item) => item.label}
+      />
+      {(type === "timeout" || type === "idle") && (
+        <Form.NumberField label="Minutes" name="minutes" className="w-full" />
+      )}
+      <Form.Submit label="Save" />
+    </Form>
+  );
+}
+
+const passwordSchema = z
+  .object({
+    oldPassword: z.string().min(1),
+    newPassword: z.string().min(8),
+    confirmation: z.string(),
+  })
+  .refine((data) => data.newPassword === data.confirmation, {
+    message: "Passwords don't match",
+    path: ["confirmation"],
+  });
+
+function ChangePassword({ name }: { name: string }) {
+  const form = useForm({
+    mode: "onBlur",
+    resolver: zodResolver(passwordSchema),
+  });
+
+  const onSubmit = async ({
+    oldPassword,
+    newPassword,
+  }: z.infer<typeof passwordSchema>) => {
+    try {
+      await invoke("wallets_change_password", {
+        name,
+        oldPassword,
+        newPassword,
+      });
+      toast({ title: "Password changed" });
+      form.reset();
+    } catch (err) {
+      toast({ title: String(err), variant: "destructive" });
+    }
+  };
+
+  return (
+    <Form form={form} onSubmit={onSubmit} className="gap-4">
+      <h2 className="font-semibold text-lg">Change password</h2>
+      <Form.Text
+        type="password"
+        label="Current password"
+        name="oldPassword"
+        className="w-full"
+      />
+      <Form.Text
+        type="password"
+        label="New password"
+        name="newPassword"
+        className="w-full"
+      />
+      <Form.Text
+        type="password"
+        label="Confirm new password"
+        name="confirmation"
+        className="w-full"
+      />
+      <Form.Submit label="Change password" />
+    </Form>
+  );
+}
+
+const exportSchema = z.object({
+  path: z.string().min(1),
+  dir: z.string().min(1),
+  password: z.string().min(8),
+});
+
+function ExportKeystore({ name }: { name: string }) {
+  const { data: addresses } = useInvoke<[string, string][]>(
+    "wallets_get_wallet_addresses",
+    { name },
+  );
+  const form = useForm({
+    mode: "onBlur",
+    resolver: zodResolver(exportSchema),
+  });
+
+  const onSubmit = async (data: z.infer<typeof exportSchema>) => {
+    try {
+      const file = await invoke<string>("wallets_export_keystore", {
+        name,
+        ...data,
+      });
+      toast({ title: `Keystore written to ${file}` });
+      form.reset();
+    } catch (err) {
+      toast({ title: String(err), variant: "destructive" });
+    }
+  };
+
+  return (
+    <Form form={form} onSubmit={onSubmit} className="gap-4">
+      <h2 className="font-semibold text-lg">Export as V3 keystore</h2>
+      <Form.Select
+        label="Key"
+        name="path"
+        items={(addresses || []).map(([path, address]) => ({
+          value: path,
+          label: address,
+        }))}
+        toValue={(item) => item.value}
+        render={(item) => item.label}
+      />
+      <Form.Text label="Directory" name="dir" className="w-full" />
+      <Form.Text
+        type="password"
+        label="Keystore password"
+        name="password"
+        className="w-full"
+      />
+      <Form.Submit label="Export" />
+    </Form>
+  );
+}
+
+function RevealMnemonic({ name }: { name: string }) {
+  const [mnemonic, setMnemonic] = useState<string | undefined>();
+
+  const reveal = async () => {
+    try {
+      setMnemonic(await invoke<string>("wallets_reveal_mnemonic", { name }));
+    } catch (err) {
+      toast({ title: String(err), variant: "destructive" });
+    }
+  };
+
+  return (
+    <div className="flex flex-col gap-4">
+      <h2 className="font-semibold text-lg">Recovery phrase</h2>
+      {mnemonic ? (
+        <>
+          <code className="rounded bg-muted p-4">{mnemonic}</code>
+          <Button variant="outline" onClick={() => setMnemonic(undefined)}>
+            Hide
+          </Button>
+        </>
+      ) : (
+        <Button variant="destructive" onClick={reveal}>
+          Reveal recovery phrase
+        </Button>
+      )}
+    </div>
+  );
+}
@@ -2,7 +2,7 @@ import type { Wallet } from "@ethui/types/wallets";
 import { Button } from "@ethui/ui/components/shadcn/button";
 import { createFileRoute, Link } from "@tanstack/react-router";
 import { startCase } from "lodash-es";
-import { Pencil, Plus } from "lucide-react";
+import { Lock, LockOpen, Pencil, Plus } from "lucide-react";
 import type { Address } from "viem";
 import { AddressView } from "#/components/AddressView";
 import { EmptyState } from "#/components/EmptyState";
@@ -15,12 +15,15 @@ export const Route = createFileRoute("/home/_l/wallets/_l/")({
   beforeLoad: () => ({
     breadcrumb: "Wallets",
     breadcrumbActions: (
-      <Button variant="outline" asChild size="sm">
-        <Link to="/home/wallets/new">
-          <Plus className="mr-2 h-4 w-4" />
-          Add Wallet
-        </Link>
-      </Button>
+      <div className="flex gap-2">
+        <LockAllButton />
+        <Button variant="outline" asChild size="sm">
+          <Link to="/home/wallets/new">
+            <Plus className="mr-2 h-4 w-4" />
+            Add Wallet
+          </Link>
+        </Button>
+      </div>
     ),
   }),
   component: WalletsPage,
@@ -47,6 +50,24 @@ function WalletsPage() {
   );
 }
 
+function LockAllButton() {
+  const lockStates = useWallets((s) => s.lockStates);
+  const lockAll = useWallets((s) => s.lockAll);
+  const anyUnlocked = Object.values(lockStates).some(Boolean);
+
+  return (
+    <Button
+      variant="outline"
+      size="sm"
+      disabled={!anyUnlocked}
+      onClick={lockAll}
+    >
+      <Lock className="mr-2 h-4 w-4" />
+      Lock all
+    </Button>
+  );
+}
+
 interface WalletCardProps {
   walletInfo: {
     wallet: Wallet;
@@ -59,14 +80,21 @@ function WalletCard({ walletInfo }: WalletCardProps) {
   const MAX_VISIBLE = 4;
   const visibleAddresses = addresses.slice(0, MAX_VISIBLE);
   const remainingCount = addresses.length - MAX_VISIBLE;
+  const unlocked = useWallets((s) => s.lockStates[wallet.name]);
 
   return (
     <div className="w-64 border">
       <div className="flex items-center justify-between border-b bg-muted px-3 py-2">
         <div className="min-w-0 flex-1">
           <div className="font-medium text-sm">{wallet.name}</div>
-          <div className="text-muted-foreground text-xs">
+          <div className="flex items-center gap-1 text-muted-foreground text-xs">
             {startCase(wallet.type)}
+            {unlocked === true && (
+              <LockOpen className="h-3 w-3" aria-label="Unlocked" />
+            )}
+            {unlocked === false && (
+              <Lock className="h-3 w-3" aria-label="Locked" />
+            )}
           </div>
         </div>
         <Link
@@ -21,6 +21,12 @@ const walletTypes: Array<{
     description:
       "Encrypted wallet file protected by a password. Secure storage compatible with most Ethereum tools.",
   },
+  {
+    type: "keystoreDir",
+    label: "Foundry Keystores",
+    description:
+      "A directory of encrypted keystores, such as the one `cast wallet import` writes to. Shares keys with `forge script --account`.",
+  },
   {
     type: "plaintext",
     label: "Plaintext",
@@ -46,6 +52,24 @@ const walletTypes: Array<{
     description:
       "Simulate transactions as any address without needing keys. For testing and debugging only.",
   },
+  {
+    type: "safe",
+    label: "Safe",
+    description:
+      "Act as a Safe multisig. Transactions are signed by its owners among your other wallets.",
+  },
+  {
+    type: "smartAccount",
+    label: "Smart Account",
+    description:
+      "An ERC-4337 account owned by one of your other wallets. Transactions are sent as user operations through a bundler.",
+  },
+  {
+    type: "remote",
+    label: "Remote Signer",
+    description:
+      "Keys held by a remote signing service, such as Web3Signer. Signing requests are forwarded to it.",
+  },
 ];
 
 export const Route = createFileRoute("/home/_l/wallets/_l/new")({
@@ -11,6 +11,7 @@ interface State {
   address?: Address;
   wallets: Wallet[];
   allWalletInfo?: WalletInfo[];
+  lockStates: Record<string, boolean>;
 }
 
 export interface WalletInfo {
@@ -29,12 +30,15 @@ interface Setters {
   setCurrentWallet: (name: string) => Promise<void>;
   setCurrentAddress: (name: string) => Promise<void>;
   reload: () => Promise<void>;
+  reloadLockStates: () => Promise<void>;
+  lockAll: () => Promise<void>;
 }
 
 type Store = State & Setters;
 
 const store: StateCreator<Store> = (set, get) => ({
   wallets: [],
+  lockStates: {},
 
   async setCurrentWallet(name: string) {
     const { wallets, currentWallet } = get();
@@ -60,6 +64,18 @@ const store: StateCreator<Store> = (set, get) => ({
     const allWalletInfo = await fetchAllWalletInfo(wallets);
 
     set({ wallets, currentWallet, address, allWalletInfo });
+    get().reloadLockStates();
+  },
+
+  async reloadLockStates() {
+    const lockStates = await invoke<Record<string, boolean>>(
+      "wallets_get_lock_states",
+    );
+    set({ lockStates });
+  },
+
+  async lockAll() {
+    await invoke("wallets_lock_all");
   },
 });
 
@@ -73,6 +89,11 @@ trackListener(
 trackListener(
   event.listen("wallets-changed", () => useWallets.getState().reload()),
 );
+trackListener(
+  event.listen("wallets-lock-changed", () =>
+    useWallets.getState().reloadLockStates(),
+  ),
+);
 
 (async () => {
   await useWallets.getState().reload();
@@ -0,0 +1,12 @@
+CREATE TABLE rpc_cache (
+  chain_id INTEGER NOT NULL,
+  dedup_id INTEGER NOT NULL,
+  key TEXT NOT NULL,
+  value TEXT NOT NULL,
+  size INTEGER NOT NULL,
+  accessed_at INTEGER NOT NULL,
+
+  PRIMARY KEY (chain_id, dedup_id, key)
+);
+
+CREATE INDEX rpc_cache_accessed_at ON rpc_cache (accessed_at);
@@ -0,0 +1,14 @@
+CREATE TABLE rpc_history (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  timestamp INTEGER NOT NULL,
+  domain TEXT,
+  chain_id INTEGER NOT NULL,
+  dedup_id INTEGER NOT NULL,
+  method TEXT NOT NULL,
+  params TEXT NOT NULL,
+  result TEXT,
+  error TEXT,
+  latency_ms INTEGER NOT NULL
+);
+
+CREATE INDEX rpc_history_domain_method ON rpc_history (domain, method);
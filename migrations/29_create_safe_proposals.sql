@@ -0,0 +1,11 @@
+CREATE TABLE safe_proposals (
+  safe_tx_hash TEXT PRIMARY KEY NOT NULL,
+  chain_id INTEGER NOT NULL,
+  safe TEXT NOT NULL,
+  nonce INTEGER NOT NULL,
+  tx TEXT NOT NULL,
+  signatures TEXT NOT NULL,
+  executed_in TEXT
+);
+
+CREATE INDEX safe_proposals_chain_id_safe ON safe_proposals (chain_id, safe);
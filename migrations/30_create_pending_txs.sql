@@ -0,0 +1,13 @@
+CREATE TABLE pending_txs (
+  hash TEXT PRIMARY KEY NOT NULL,
+  chain_id INTEGER NOT NULL,
+  dedup_id INTEGER NOT NULL,
+  from_address TEXT NOT NULL,
+  nonce INTEGER NOT NULL,
+  request TEXT NOT NULL,
+  status TEXT NOT NULL,
+  replaces TEXT,
+  created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX pending_txs_network_from_address ON pending_txs (chain_id, dedup_id, from_address);
@@ -14,6 +14,7 @@ export const networkSchema = z.object({
   decimals: z.number("Invalid number"),
   id: networkIdSchema,
   is_stack: z.boolean(),
+  rpc_cache: z.boolean().optional(),
 });
 
 export type NetworkInputs = z.infer<typeof networkSchema>;
@@ -13,6 +13,13 @@ export const onboardingSchema = z.object({
   steps: z.record(onboardingSteps, z.boolean()),
 });
 
+export const lockPolicySchema = z.discriminatedUnion("type", [
+  z.object({ type: z.literal("timeout"), secs: z.number().int().positive() }),
+  z.object({ type: z.literal("idle"), secs: z.number().int().positive() }),
+  z.object({ type: z.literal("onSleep") }),
+  z.object({ type: z.literal("never") }),
+]);
+
 export const generalSettingsSchema = z.object({
   darkMode: z.enum(["auto", "dark", "light"]),
   autostart: z.boolean(),
@@ -28,8 +35,12 @@ export const generalSettingsSchema = z.object({
   onboarding: onboardingSchema,
   rustLog: z.string().optional(),
   runLocalStacks: z.boolean(),
+  persistRpcHistory: z.boolean(),
+  redactRpcHistory: z.boolean(),
+  lockPolicies: z.record(z.string(), lockPolicySchema).optional(),
 });
 
 export type OnboardingStepKey = z.infer<typeof onboardingSteps>;
 export type Onboarding = z.infer<typeof onboardingSchema>;
+export type LockPolicy = z.infer<typeof lockPolicySchema>;
 export type GeneralSettings = z.infer<typeof generalSettingsSchema>;
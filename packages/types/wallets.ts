@@ -55,6 +55,15 @@ export interface JsonKeystoreWallet {
   currentPath?: string;
 }
 
+export interface KeystoreDirWallet {
+  type: "keystoreDir";
+  name: string;
+  dir: string;
+  addresses: Record<string, string>;
+  locked: string[];
+  current?: string;
+}
+
 export interface PrivateKeyWallet {
   type: "privateKey";
   name: string;
@@ -86,10 +95,47 @@ export interface LedgerWallet {
   current?: number;
 }
 
+export interface SafeWallet {
+  type: "safe";
+  name: string;
+  address: string;
+}
+
+export interface SmartAccountWallet {
+  type: "smartAccount";
+  name: string;
+  owner: string;
+  address: string;
+  factory?: string;
+  entryPoint?: string;
+  salt?: string;
+  bundlerUrl: string;
+  paymaster?: string;
+  paymasterData?: string;
+}
+
+export interface RemoteWallet {
+  type: "remote";
+  name: string;
+  url: string;
+  api: "web3Signer" | "jsonRpc";
+  auth:
+    | { type: "none" }
+    | { type: "bearer"; path: string }
+    | { type: "mtls"; cert: string; key: string };
+  ca?: string | null;
+  keys: [string, string][];
+  current?: number;
+}
+
 export type Wallet =
   | HdWallet
   | JsonKeystoreWallet
+  | KeystoreDirWallet
   | PlaintextWallet
   | ImpersonatorWallet
   | LedgerWallet
-  | PrivateKeyWallet;
+  | PrivateKeyWallet
+  | SafeWallet
+  | SmartAccountWallet
+  | RemoteWallet;
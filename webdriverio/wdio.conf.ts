@@ -19,6 +19,8 @@ const args = [
   "9103",
   "--stacks-port",
   "9104",
+  "--http-port",
+  "9105",
   "--config-dir",
   configDir,
 ];